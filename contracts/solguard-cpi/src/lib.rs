@@ -0,0 +1,329 @@
+//! Helpers for Anchor programs that integrate with the SOLGuard registry:
+//! program ID, PDA derivation, account types and typed CPI wrappers.
+
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+pub use solguard_registry::program::SolguardRegistry;
+pub use solguard_registry::{
+    category_proofs_hash, metadata_hash, risk_bloom_positions, Attestation, AttestationStatus, Config, 
+    ConsumerConfig, GradeIndexEntry, GradeIndexPage, GradeWaiver, ID, LatestAttestation, ProofMarker, 
+    RiskBloom, ATTESTATION_ATTESTED_BY_OFFSET, ATTESTATION_CATEGORY_PROOFS_OFFSET, 
+    ATTESTATION_EXPIRED_OFFSET, ATTESTATION_FLAGS_OFFSET, ATTESTATION_FREEZE_AUTHORITY_OFFSET, 
+    ATTESTATION_FROZEN_OFFSET, ATTESTATION_GRADE_OFFSET, ATTESTATION_METADATA_HASH_OFFSET, 
+    ATTESTATION_MINT_AUTHORITY_OFFSET, ATTESTATION_MINT_OFFSET, ATTESTATION_PAYER_OFFSET, 
+    ATTESTATION_REVOKED_OFFSET, ATTESTATION_RISK_FLAGS_OFFSET, ATTESTATION_RULESET_VERSION_OFFSET, 
+    ATTESTATION_SUPERSEDED_OFFSET, ATTESTATION_SUPERSEDES_OFFSET, ATTESTATION_SUPPLY_OFFSET, 
+    FLAG_CONCENTRATED_HOLDERS, FLAG_FREEZE_AUTHORITY, FLAG_HONEYPOT_SUSPECTED, FLAG_IMPERSONATION, 
+    FLAG_LOW_LIQUIDITY, FLAG_MINT_AUTHORITY, FLAG_MUTABLE_METADATA, FLAG_UNLOCKED_LP, GRADE_CRITICAL, 
+    GRADE_GREEN, GRADE_INDEX_PAGE_LEN, GRADE_LEVELS_EXTENDED, GRADE_RED, GRADE_VERIFIED, GRADE_YELLOW, 
+    PAIR_RISK_FAKE_QUOTE, PAIR_RISK_PRICE_DIVERGENCE, PAIR_RISK_SPOOFED_POOL, PAIR_RISK_THIN_LIQUIDITY, 
+    POOL_PROGRAMS, RISK_BLOOM_BYTES, RISK_BLOOM_HASHES, RISK_CONFIDENTIAL_TRANSFER, RISK_DEFAULT_FROZEN, 
+    RISK_NON_TRANSFERABLE, RISK_PERMANENT_DELEGATE, RISK_TRANSFER_FEE, RISK_TRANSFER_HOOK,
+};
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const ORACLE_SEED: &[u8] = b"oracle";
+pub const ATTESTATION_SEED: &[u8] = b"attest";
+pub const HISTORY_SEED: &[u8] = b"history";
+pub const LATEST_ATTESTATION_SEED: &[u8] = b"latest";
+pub const GRADE_INDEX_SEED: &[u8] = b"grade_index";
+pub const GRADE_INDEX_ENTRY_SEED: &[u8] = b"grade_index_entry";
+pub const PROOF_MARKER_SEED: &[u8] = b"proof";
+pub const GRADE_WAIVER_SEED: &[u8] = b"grade_waiver";
+pub const CONSUMER_SEED: &[u8] = b"consumer";
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+pub const BLACKLIST_SEED: &[u8] = b"blacklist";
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+pub const GRADE_OVERRIDE_SEED: &[u8] = b"grade_override";
+pub const COLLECTION_SEED: &[u8] = b"collection";
+pub const PROGRAM_ATTESTATION_SEED: &[u8] = b"attest_program";
+pub const WALLET_ATTESTATION_SEED: &[u8] = b"wallet";
+pub const POOL_ATTESTATION_SEED: &[u8] = b"pool";
+pub const PAIR_ATTESTATION_SEED: &[u8] = b"pair";
+pub const UPGRADE_AUTHORITY_SEED: &[u8] = b"upgrade_authority";
+pub const EPOCH_SNAPSHOT_SEED: &[u8] = b"snapshot";
+pub const MINT_WATCH_SEED: &[u8] = b"watch";
+pub const WATCHER_SEED: &[u8] = b"watcher";
+pub const RISK_BLOOM_SEED: &[u8] = b"risk_bloom";
+/// Derived under the consumer program, not the registry
+pub const CALLER_SEED: &[u8] = b"solguard_caller";
+
+pub fn config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &ID)
+}
+
+/// Hand the registry's upgrade authority to this PDA to put upgrades under admin control.
+pub fn upgrade_authority_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[UPGRADE_AUTHORITY_SEED], &ID)
+}
+
+pub fn oracle_pda(oracle_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORACLE_SEED, oracle_key.as_ref()], &ID)
+}
+
+pub fn attestation_pda(mint: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ATTESTATION_SEED, mint.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+pub fn history_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HISTORY_SEED, mint.as_ref()], &ID)
+}
+
+/// Holds the ruleset version and address of the mint's newest attestation.
+pub fn latest_attestation_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LATEST_ATTESTATION_SEED, mint.as_ref()], &ID)
+}
+
+/// Page `page` of the mints listed under `grade` (extended scale); pages are
+/// contiguous from 0.
+pub fn grade_index_pda(grade: u8, page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GRADE_INDEX_SEED, &[grade], &page.to_le_bytes()], &ID)
+}
+
+pub fn grade_index_entry_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GRADE_INDEX_ENTRY_SEED, mint.as_ref()], &ID)
+}
+
+/// Records which mint first attested with `proofs_hash` under `ruleset_version`.
+pub fn proof_marker_pda(ruleset_version: u16, proofs_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROOF_MARKER_SEED, &ruleset_version.to_le_bytes(), proofs_hash], 
+        &ID
+    )
+}
+
+/// Pending admin waiver of the grade band check for `mint`'s next attestation.
+pub fn grade_waiver_pda(mint: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GRADE_WAIVER_SEED, mint.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+pub fn consumer_config_pda(consumer_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONSUMER_SEED, consumer_program.as_ref()], &ID)
+}
+
+/// The consumer program's signer PDA for verify_attestation's `caller`; sign
+/// with `[CALLER_SEED, &[bump]]` so the registry applies its ConsumerConfig.
+pub fn caller_pda(consumer_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CALLER_SEED], consumer_program)
+}
+
+pub fn subscription_pda(consumer_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SUBSCRIPTION_SEED, consumer_program.as_ref()], &ID)
+}
+
+pub fn blacklist_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BLACKLIST_SEED, mint.as_ref()], &ID)
+}
+
+pub fn allowlist_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWLIST_SEED, mint.as_ref()], &ID)
+}
+
+pub fn grade_override_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GRADE_OVERRIDE_SEED, mint.as_ref()], &ID)
+}
+
+pub fn program_attestation_pda(program_id: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROGRAM_ATTESTATION_SEED, program_id.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+pub fn wallet_attestation_pda(wallet: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[WALLET_ATTESTATION_SEED, wallet.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+pub fn pool_attestation_pda(pool: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POOL_ATTESTATION_SEED, pool.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+/// Pair PDA for either mint order; the registry keys pairs by the sorted mints.
+pub fn pair_attestation_pda(mint_x: &Pubkey, mint_y: &Pubkey, venue: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    let (mint_a, mint_b) = if mint_x < mint_y { (mint_x, mint_y) } else { (mint_y, mint_x) };
+    Pubkey::find_program_address(
+        &[PAIR_ATTESTATION_SEED, mint_a.as_ref(), mint_b.as_ref(), venue.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+pub fn epoch_snapshot_pda(epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EPOCH_SNAPSHOT_SEED, &epoch.to_le_bytes()], &ID)
+}
+
+pub fn mint_watch_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_WATCH_SEED, mint.as_ref()], &ID)
+}
+
+pub fn watcher_pda(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WATCHER_SEED, mint.as_ref(), owner.as_ref()], &ID)
+}
+
+/// Bloom filter of flagged mints; test a mint with `RiskBloom::contains`.
+pub fn risk_bloom_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RISK_BLOOM_SEED], &ID)
+}
+
+pub fn collection_attestation_pda(collection_mint: &Pubkey, ruleset_version: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COLLECTION_SEED, collection_mint.as_ref(), &ruleset_version.to_le_bytes()], 
+        &ID
+    )
+}
+
+/// Checks that `attestation` covers `mint` under `ruleset_version`, is unrevoked,
+/// unexpired and not superseded, and that its normalized grade (extended scale) is
+/// at least `min_grade`.
+///
+/// This only reads the attestation: the caller must have loaded it from the
+/// canonical `attestation_pda`, and the blacklist, allowlist, grade overrides,
+/// staleness window and the registry's active versions are not consulted. Prefer
+/// `cpi::verify_attestation`, which applies all of them, wherever a CPI is affordable.
+pub fn check_attested(attestation: &Attestation, mint: &Pubkey, ruleset_version: u16, min_grade: u8) -> Result<()> {
+    require_keys_eq!(attestation.mint, *mint, SolguardCpiError::AttestationMintMismatch);
+    require!(attestation.ruleset_version == ruleset_version, SolguardCpiError::RulesetVersionMismatch);
+    attestation.require_valid(Clock::get()?.unix_timestamp)?;
+    require!(!attestation.is_superseded(), SolguardCpiError::AttestationSuperseded);
+    require!(attestation.normalized_grade() >= min_grade, SolguardCpiError::GradeBelowMinimum);
+    Ok(())
+}
+
+/// A mint together with its registry attestation, validated on deserialization:
+///
+/// ```ignore
+/// #[derive(Accounts)]
+/// pub struct Borrow<'info> {
+///     pub collateral: AttestedMint<'info, 1, { solguard_cpi::GRADE_GREEN }>,
+///     // ...
+/// }
+/// ```
+///
+/// Consumes three accounts: the mint, its attestation PDA for `RULESET_VERSION`
+/// and its Blacklist PDA. Fails unless both PDAs are canonical, the blacklist
+/// entry does not exist and `check_attested` passes for `MIN_GRADE`. Allowlists,
+/// grade overrides and staleness are still not applied, so this is weaker than
+/// `cpi::verify_attestation`. Read the attestation itself with `attestation.load()`.
+pub struct AttestedMint<'info, const RULESET_VERSION: u16, const MIN_GRADE: u8> {
+    pub mint: UncheckedAccount<'info>,
+    pub attestation: AccountLoader<'info, Attestation>,
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+impl<'info, B, const RULESET_VERSION: u16, const MIN_GRADE: u8> Accounts<'info, B> 
+    for AttestedMint<'info, RULESET_VERSION, MIN_GRADE> 
+{
+    fn try_accounts(
+        program_id: &Pubkey, 
+        accounts: &mut &'info [AccountInfo<'info>], 
+        ix_data: &[u8], 
+        bumps: &mut B, 
+        reallocs: &mut BTreeSet<Pubkey>
+    ) -> Result<Self> {
+        let mint = UncheckedAccount::try_accounts(program_id, accounts, ix_data, bumps, reallocs)?;
+        // Owner and discriminator are checked by AccountLoader itself
+        let attestation: AccountLoader<'info, Attestation> = 
+            AccountLoader::try_accounts(program_id, accounts, ix_data, bumps, reallocs)?;
+        let blacklist = UncheckedAccount::try_accounts(program_id, accounts, ix_data, bumps, reallocs)?;
+        require_keys_eq!(
+            attestation.key(), 
+            attestation_pda(mint.key, RULESET_VERSION).0, 
+            SolguardCpiError::RegistryAccountMismatch
+        );
+        require_keys_eq!(blacklist.key(), blacklist_pda(mint.key).0, SolguardCpiError::RegistryAccountMismatch);
+        require!(blacklist.data_is_empty(), SolguardCpiError::MintBlacklisted);
+        check_attested(&*attestation.load()?, mint.key, RULESET_VERSION, MIN_GRADE)?;
+        Ok(Self { mint, attestation, blacklist })
+    }
+}
+
+impl<'info, const RULESET_VERSION: u16, const MIN_GRADE: u8> ToAccountInfos<'info> 
+    for AttestedMint<'info, RULESET_VERSION, MIN_GRADE> 
+{
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        let mut infos = self.mint.to_account_infos();
+        infos.extend(self.attestation.to_account_infos());
+        infos.extend(self.blacklist.to_account_infos());
+        infos
+    }
+}
+
+impl<'info, const RULESET_VERSION: u16, const MIN_GRADE: u8> ToAccountMetas 
+    for AttestedMint<'info, RULESET_VERSION, MIN_GRADE> 
+{
+    fn to_account_metas(&self, is_signer: Option<bool>) -> Vec<AccountMeta> {
+        let mut metas = self.mint.to_account_metas(is_signer);
+        metas.extend(self.attestation.to_account_metas(is_signer));
+        metas.extend(self.blacklist.to_account_metas(is_signer));
+        metas
+    }
+}
+
+// Read-only: nothing to persist on exit
+impl<'info, const RULESET_VERSION: u16, const MIN_GRADE: u8> AccountsExit<'info> 
+    for AttestedMint<'info, RULESET_VERSION, MIN_GRADE> 
+{
+}
+
+pub mod cpi {
+    use super::*;
+
+    pub use solguard_registry::cpi::accounts::{ReadAttestation, VerifyAttestation};
+
+    /// Errors unless the attestation passes the registry's verification rules
+    /// (see `solguard_registry::verify_attestation`).
+    pub fn verify_attestation<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, VerifyAttestation<'info>>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>
+    ) -> Result<()> {
+        solguard_registry::cpi::verify_attestation(ctx, ruleset_version, min_grade, max_age_secs)
+    }
+
+    /// Effective grade on the extended scale, staleness policy applied.
+    pub fn get_effective_grade<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, ReadAttestation<'info>>, 
+        ruleset_version: u16
+    ) -> Result<u8> {
+        let grade = solguard_registry::cpi::get_effective_grade(ctx, ruleset_version)?;
+        Ok(grade.get())
+    }
+
+    /// Active, UnderReview, Revoked or Expired; does not fail on the attestation's state.
+    pub fn get_attestation_status<'info>(
+        ctx: CpiContext<'_, '_, '_, 'info, ReadAttestation<'info>>, 
+        ruleset_version: u16
+    ) -> Result<AttestationStatus> {
+        let status = solguard_registry::cpi::get_attestation_status(ctx, ruleset_version)?;
+        Ok(status.get())
+    }
+}
+
+#[error_code(offset = 7000)]
+pub enum SolguardCpiError {
+    #[msg("Attestation does not belong to this mint")]
+    AttestationMintMismatch,
+    #[msg("Attested grade is below the required minimum")]
+    GradeBelowMinimum,
+    #[msg("Attestation is for a different ruleset version")]
+    RulesetVersionMismatch,
+    #[msg("Attestation was superseded by a newer ruleset version")]
+    AttestationSuperseded,
+    #[msg("Account is not the mint's registry PDA")]
+    RegistryAccountMismatch,
+    #[msg("Mint is blacklisted")]
+    MintBlacklisted,
+}
@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use solguard_cpi::SolguardRegistry;
+
+declare_id!("SoLGuaRdEscrow11111111111111111111111111111");
+
+/// Reference integration for the registry's CPI gate: funds deposited here are
+/// released to the recipient only if `gated_mint` passes `verify_attestation`
+/// at release time, otherwise the depositor can reclaim them after `refund_after`.
+#[program]
+pub mod solguard_escrow {
+    use super::*;
+
+    pub fn deposit_sol(
+        ctx: Context<DepositSol>,
+        id: u64,
+        amount: u64,
+        recipient: Pubkey,
+        min_grade: u8,
+        ruleset_version: u16,
+        max_age_secs: Option<i64>,
+        refund_after: i64
+    ) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.depositor.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            amount
+        )?;
+
+        let e = &mut ctx.accounts.escrow;
+        e.depositor = ctx.accounts.depositor.key();
+        e.recipient = recipient;
+        e.gated_mint = ctx.accounts.gated_mint.key();
+        e.token_mint = Pubkey::default();
+        e.amount = amount;
+        e.min_grade = min_grade;
+        e.ruleset_version = ruleset_version;
+        e.max_age_secs = max_age_secs;
+        e.refund_after = refund_after;
+        e.id = id;
+        e.bump = ctx.bumps.escrow;
+
+        emit!(EscrowDeposited {
+            escrow: e.key(),
+            depositor: e.depositor,
+            gated_mint: e.gated_mint,
+            token_mint: e.token_mint,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Escrows tokens of `token_mint`, gated on that same mint's attestation.
+    pub fn deposit_token(
+        ctx: Context<DepositToken>,
+        id: u64,
+        amount: u64,
+        recipient: Pubkey,
+        min_grade: u8,
+        ruleset_version: u16,
+        max_age_secs: Option<i64>,
+        refund_after: i64
+    ) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidAmount);
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.depositor_token.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.depositor.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.token_mint.decimals
+        )?;
+
+        let e = &mut ctx.accounts.escrow;
+        e.depositor = ctx.accounts.depositor.key();
+        e.recipient = recipient;
+        e.gated_mint = ctx.accounts.token_mint.key();
+        e.token_mint = ctx.accounts.token_mint.key();
+        e.amount = amount;
+        e.min_grade = min_grade;
+        e.ruleset_version = ruleset_version;
+        e.max_age_secs = max_age_secs;
+        e.refund_after = refund_after;
+        e.id = id;
+        e.bump = ctx.bumps.escrow;
+
+        emit!(EscrowDeposited {
+            escrow: e.key(),
+            depositor: e.depositor,
+            gated_mint: e.gated_mint,
+            token_mint: e.token_mint,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Pays the recipient if the gated mint's attestation passes the registry's
+    /// `verify_attestation` under the terms fixed at deposit; any failure there
+    /// aborts the release.
+    pub fn release(ctx: Context<Settle>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.counterparty.key(), ctx.accounts.escrow.recipient, EscrowError::WrongCounterparty);
+
+        let verify = ctx.accounts.verify_accounts()?;
+        solguard_cpi::cpi::verify_attestation(
+            CpiContext::new_with_signer(
+                ctx.accounts.registry_program.to_account_info(),
+                verify,
+                &[&[solguard_cpi::CALLER_SEED, &[ctx.bumps.caller]]]
+            ),
+            ctx.accounts.escrow.ruleset_version,
+            ctx.accounts.escrow.min_grade,
+            ctx.accounts.escrow.max_age_secs
+        )?;
+
+        ctx.accounts.pay_out()?;
+
+        emit!(EscrowSettled {
+            escrow: ctx.accounts.escrow.key(),
+            to: ctx.accounts.counterparty.key(),
+            amount: ctx.accounts.escrow.amount,
+            released: true,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the funds to the depositor once `refund_after` has passed.
+    pub fn refund(ctx: Context<Settle>) -> Result<()> {
+        require_keys_eq!(ctx.accounts.counterparty.key(), ctx.accounts.escrow.depositor, EscrowError::WrongCounterparty);
+        require!(Clock::get()?.unix_timestamp >= ctx.accounts.escrow.refund_after, EscrowError::RefundLocked);
+
+        ctx.accounts.pay_out()?;
+
+        emit!(EscrowSettled {
+            escrow: ctx.accounts.escrow.key(),
+            to: ctx.accounts.counterparty.key(),
+            amount: ctx.accounts.escrow.amount,
+            released: false,
+        });
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Escrow {
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub gated_mint: Pubkey,  // mint whose attestation unlocks the release
+    pub token_mint: Pubkey,  // Pubkey::default() for SOL escrows
+    pub amount: u64,         // lamports or token base units
+    pub min_grade: u8,       // registry extended scale
+    pub ruleset_version: u16,
+    pub max_age_secs: Option<i64>,
+    pub refund_after: i64,
+    pub id: u64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct DepositSol<'info> {
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + 32 + 32 + 32 + 32 + 8 + 1 + 2 + 9 + 8 + 8 + 1, // discriminator + depositor + recipient + gated mint + token mint + amount + grade + ruleset version + max age + refund after + id + bump
+        seeds = [b"escrow", depositor.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: only the key is recorded
+    pub gated_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct DepositToken<'info> {
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + 32 + 32 + 32 + 32 + 8 + 1 + 2 + 9 + 8 + 8 + 1, // discriminator + depositor + recipient + gated mint + token mint + amount + grade + ruleset version + max age + refund after + id + bump
+        seeds = [b"escrow", depositor.key().as_ref(), &id.to_le_bytes()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = depositor,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+        seeds = [b"vault", escrow.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = token_mint, token::authority = depositor)]
+    pub depositor_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Shared by release and refund, both permissionless since funds can only go to the
+// recorded recipient or depositor and the release terms are recorded at deposit;
+// token accounts are only needed for SPL escrows
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    #[account(
+        mut,
+        close = depositor,
+        seeds = [b"escrow", escrow.depositor.as_ref(), &escrow.id.to_le_bytes()],
+        bump = escrow.bump,
+        has_one = depositor
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: receives rent back; checked against escrow.depositor
+    #[account(mut)]
+    pub depositor: UncheckedAccount<'info>,
+    /// CHECK: recipient on release, depositor on refund; checked in the handler
+    #[account(mut)]
+    pub counterparty: UncheckedAccount<'info>,
+    /// CHECK: checked against escrow.gated_mint
+    #[account(address = escrow.gated_mint)]
+    pub gated_mint: UncheckedAccount<'info>,
+    /// CHECK: registry config, validated by the registry during the CPI
+    pub registry_config: UncheckedAccount<'info>,
+    /// CHECK: attestation PDA, validated by the registry during the CPI
+    pub attestation: UncheckedAccount<'info>,
+    /// CHECK: gated mint's blacklist PDA, validated by the registry during the CPI
+    pub blacklist: UncheckedAccount<'info>,
+    /// CHECK: gated mint's allowlist PDA, validated by the registry during the CPI
+    pub allowlist: UncheckedAccount<'info>,
+    /// CHECK: gated mint's grade override PDA, validated by the registry during the CPI
+    pub grade_override: UncheckedAccount<'info>,
+    /// CHECK: this program's registry subscription, validated by the registry during the CPI
+    pub subscription: Option<UncheckedAccount<'info>>,
+    /// CHECK: this program's caller PDA; signs the registry CPI so the subscription applies
+    #[account(seeds = [solguard_cpi::CALLER_SEED], bump)]
+    pub caller: UncheckedAccount<'info>,
+    pub registry_program: Program<'info, SolguardRegistry>,
+    #[account(mut, seeds = [b"vault", escrow.key().as_ref()], bump)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = escrow.token_mint)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub counterparty_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl<'info> Settle<'info> {
+    fn verify_accounts(&self) -> Result<solguard_cpi::cpi::VerifyAttestation<'info>> {
+        Ok(solguard_cpi::cpi::VerifyAttestation {
+            config: self.registry_config.to_account_info(),
+            mint: self.gated_mint.to_account_info(),
+            attestation: self.attestation.to_account_info(),
+            blacklist: self.blacklist.to_account_info(),
+            allowlist: self.allowlist.to_account_info(),
+            grade_override: self.grade_override.to_account_info(),
+            // Release terms are fixed on the escrow, so no caller-supplied policy
+            consumer_config: None,
+            caller: Some(self.caller.to_account_info()),
+            subscription: self.subscription.as_ref().map(|s| s.to_account_info()),
+        })
+    }
+
+    /// Sends the escrowed amount to the counterparty; the escrow account itself
+    /// is closed to the depositor by the `close` constraint.
+    fn pay_out(&self) -> Result<()> {
+        let e = &self.escrow;
+        if e.token_mint == Pubkey::default() {
+            **e.to_account_info().try_borrow_mut_lamports()? -= e.amount;
+            **self.counterparty.try_borrow_mut_lamports()? += e.amount;
+            return Ok(());
+        }
+
+        let (Some(vault), Some(token_mint), Some(counterparty_token), Some(token_program)) =
+            (&self.vault, &self.token_mint, &self.counterparty_token, &self.token_program)
+        else {
+            return err!(EscrowError::MissingTokenAccounts);
+        };
+        require_keys_eq!(counterparty_token.owner, self.counterparty.key(), EscrowError::WrongCounterparty);
+
+        let id_bytes = e.id.to_le_bytes();
+        let signer_seeds: &[&[u8]] = &[b"escrow", e.depositor.as_ref(), &id_bytes, &[e.bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: vault.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    to: counterparty_token.to_account_info(),
+                    authority: e.to_account_info(),
+                },
+                &[signer_seeds]
+            ),
+            e.amount,
+            token_mint.decimals
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: vault.to_account_info(),
+                destination: self.depositor.to_account_info(),
+                authority: e.to_account_info(),
+            },
+            &[signer_seeds]
+        ))
+    }
+}
+
+// Events
+
+#[event]
+pub struct EscrowDeposited {
+    pub escrow: Pubkey,
+    pub depositor: Pubkey,
+    pub gated_mint: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowSettled {
+    pub escrow: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub released: bool, // false for refunds
+}
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Counterparty does not match the escrow")]
+    WrongCounterparty,
+    #[msg("Refund window has not opened yet")]
+    RefundLocked,
+    #[msg("Token escrows require vault, mint, counterparty token account and token program")]
+    MissingTokenAccounts,
+}
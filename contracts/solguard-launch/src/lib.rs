@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use solguard_cpi::SolguardRegistry;
+
+declare_id!("SoLGuaRdLaunch11111111111111111111111111111");
+
+/// Grade a mint must verify at before a pool can be created for it.
+pub const LAUNCH_MIN_GRADE: u8 = solguard_cpi::GRADE_GREEN;
+
+/// Reference enforcement for "green-only" launchpads: pool creation is routed
+/// through here and only reaches the AMM if the launched mint passes
+/// `verify_attestation` at `LAUNCH_MIN_GRADE`.
+#[program]
+pub mod solguard_launch {
+    use super::*;
+
+    /// Verifies `base_mint`, then forwards `data` and the remaining accounts
+    /// unchanged to the pool program. The base mint must be among the forwarded
+    /// accounts so the pool can only be created for the mint that was checked.
+    pub fn launch_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, LaunchPool<'info>>,
+        ruleset_version: u16,
+        data: Vec<u8>
+    ) -> Result<()> {
+        solguard_cpi::cpi::verify_attestation(
+            CpiContext::new_with_signer(
+                ctx.accounts.registry_program.to_account_info(),
+                solguard_cpi::cpi::VerifyAttestation {
+                    config: ctx.accounts.registry_config.to_account_info(),
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                    attestation: ctx.accounts.attestation.to_account_info(),
+                    blacklist: ctx.accounts.blacklist.to_account_info(),
+                    allowlist: ctx.accounts.allowlist.to_account_info(),
+                    grade_override: ctx.accounts.grade_override.to_account_info(),
+                    consumer_config: None,
+                    caller: Some(ctx.accounts.caller.to_account_info()),
+                    subscription: ctx.accounts.subscription.as_ref().map(|s| s.to_account_info()),
+                },
+                &[&[solguard_cpi::CALLER_SEED, &[ctx.bumps.caller]]]
+            ),
+            ruleset_version,
+            LAUNCH_MIN_GRADE,
+            None
+        )?;
+
+        let base_mint = ctx.accounts.base_mint.key();
+        require!(
+            ctx.remaining_accounts.iter().any(|a| a.key() == base_mint),
+            LaunchError::MintNotRouted
+        );
+
+        let pool_program = &ctx.accounts.pool_program;
+        let ix = Instruction {
+            program_id: pool_program.key(),
+            accounts: ctx.remaining_accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.key(),
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        let mut infos = ctx.remaining_accounts.to_vec();
+        infos.push(pool_program.to_account_info());
+        invoke(&ix, &infos)?;
+
+        emit!(PoolLaunched {
+            creator: ctx.accounts.creator.key(),
+            base_mint,
+            pool_program: pool_program.key(),
+            ruleset_version,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct LaunchPool<'info> {
+    pub creator: Signer<'info>,
+    /// CHECK: only the key is used; must also appear in the forwarded accounts
+    pub base_mint: UncheckedAccount<'info>,
+    /// CHECK: registry config, validated by the registry during the CPI
+    pub registry_config: UncheckedAccount<'info>,
+    /// CHECK: validated by the registry during the CPI
+    #[account(address = solguard_cpi::attestation_pda(&base_mint.key(), ruleset_version).0 @ LaunchError::WrongAttestation)]
+    pub attestation: UncheckedAccount<'info>,
+    /// CHECK: base mint's blacklist PDA, validated by the registry during the CPI
+    pub blacklist: UncheckedAccount<'info>,
+    /// CHECK: base mint's allowlist PDA, validated by the registry during the CPI
+    #[account(address = solguard_cpi::allowlist_pda(&base_mint.key()).0 @ LaunchError::WrongAttestation)]
+    pub allowlist: UncheckedAccount<'info>,
+    /// CHECK: base mint's grade override PDA, validated by the registry during the CPI
+    #[account(address = solguard_cpi::grade_override_pda(&base_mint.key()).0 @ LaunchError::WrongAttestation)]
+    pub grade_override: UncheckedAccount<'info>,
+    /// CHECK: this program's registry subscription, validated by the registry during the CPI
+    pub subscription: Option<UncheckedAccount<'info>>,
+    /// CHECK: this program's caller PDA; signs the registry CPI so the subscription applies
+    #[account(seeds = [solguard_cpi::CALLER_SEED], bump)]
+    pub caller: UncheckedAccount<'info>,
+    pub registry_program: Program<'info, SolguardRegistry>,
+    /// CHECK: restricted to the AMMs the registry grades pools for
+    #[account(
+        executable,
+        constraint = solguard_cpi::POOL_PROGRAMS.contains(&pool_program.key()) @ LaunchError::UnsupportedPoolProgram
+    )]
+    pub pool_program: UncheckedAccount<'info>,
+}
+
+// Events
+
+#[event]
+pub struct PoolLaunched {
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub pool_program: Pubkey,
+    pub ruleset_version: u16,
+}
+
+#[error_code]
+pub enum LaunchError {
+    #[msg("Base mint is not part of the pool creation accounts")]
+    MintNotRouted,
+    #[msg("Pool program is not supported")]
+    UnsupportedPoolProgram,
+    #[msg("Attestation account does not match the base mint")]
+    WrongAttestation,
+}
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Bumps;
+use anchor_lang::solana_program::keccak;
+
+use crate::errors::ErrorCode;
+use crate::instructions::*;
+use crate::state::*;
+
+/// Checks admin authorization. With a single admin the signer must be
+/// `config.admin`; once an admin set is enabled the signer plus any signing
+/// co-signers passed as remaining accounts must reach the set's threshold.
+pub(crate) fn require_admin(
+    config: &Config,
+    admin: &Pubkey,
+    admin_set: Option<&AdminSet>,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    if !config.multisig {
+        require_keys_eq!(*admin, config.admin, ErrorCode::Unauthorized);
+        return Ok(());
+    }
+    
+    let set = admin_set.ok_or(ErrorCode::AdminSetRequired)?;
+    let mut approvals: Vec<Pubkey> = Vec::with_capacity(set.members.len());
+    let signers = co_signers.iter().filter(|info| info.is_signer).map(|info| info.key());
+    for key in std::iter::once(*admin).chain(signers) {
+        if set.members.contains(&key) && !approvals.contains(&key) {
+            approvals.push(key);
+        }
+    }
+    require!(approvals.len() >= set.threshold as usize, ErrorCode::InsufficientAdminSignatures);
+    
+    Ok(())
+}
+
+/// Checks that the signer holds `role`, falling back to full admin authorization.
+/// Once an admin set is enabled a grant never stands in for its threshold, so a
+/// single delegate cannot act (or move funds) alone.
+pub(crate) fn require_role(
+    config: &Config,
+    authority: &Pubkey,
+    role: Role,
+    role_grant: Option<&RoleGrant>,
+    admin_set: Option<&AdminSet>,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    if config.multisig {
+        return require_admin(config, authority, admin_set, co_signers);
+    }
+    if let Some(grant) = role_grant {
+        if grant.role == role && grant.holder == *authority {
+            return Ok(());
+        }
+    }
+    require_admin(config, authority, admin_set, co_signers)
+}
+
+/// require_role, but Config.guardian passes too. Only for the emergency
+/// instructions the guardian key may call: pause and freeze_attestation.
+pub(crate) fn require_role_or_guardian(
+    config: &Config,
+    authority: &Pubkey,
+    role: Role,
+    role_grant: Option<&RoleGrant>,
+    admin_set: Option<&AdminSet>,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    if config.guardian != Pubkey::default() && config.guardian == *authority {
+        return Ok(());
+    }
+    require_role(config, authority, role, role_grant, admin_set, co_signers)
+}
+
+/// Audit log entry for an admin instruction. `action` is the instruction
+/// discriminator and `args` its arguments in order, so `payload_hash` is the
+/// keccak hash of the instruction data following the discriminator.
+pub(crate) fn audit_entry(action: [u8; 8], actor: &Pubkey, args: &impl AnchorSerialize) -> Result<AuditEntry> {
+    Ok(AuditEntry {
+        action,
+        actor: *actor,
+        payload_hash: keccak::hash(&args.try_to_vec()?).to_bytes(),
+        slot: Clock::get()?.slot,
+    })
+}
+
+pub(crate) fn record_admin_action(
+    audit_log: &AccountLoader<AuditLog>,
+    actor: &Pubkey,
+    action: [u8; 8],
+    args: &impl AnchorSerialize,
+) -> Result<()> {
+    let entry = audit_entry(action, actor, args)?;
+    audit_log.load_mut()?.push(entry);
+    Ok(())
+}
+
+/// The accounts every admin-gated instruction carries: `config`, the `admin`
+/// signer, the optional `admin_set` and the `audit_log`. `role_grant` is only
+/// read by instructions a delegated role may call.
+pub(crate) trait AdminAccounts<'info> {
+    fn config(&self) -> &Config;
+    fn admin(&self) -> Pubkey;
+    fn admin_set(&self) -> Option<&AdminSet>;
+    fn audit_log(&self) -> &AccountLoader<'info, AuditLog>;
+    fn role_grant(&self) -> Option<&RoleGrant> {
+        None
+    }
+}
+
+macro_rules! impl_admin_accounts {
+    // Full-admin-only structs. A `role_grant` here is the grant being edited,
+    // never an authorization
+    (full: $($name:ident),* $(,)?) => {$(
+        impl<'info> AdminAccounts<'info> for $name<'info> {
+            fn config(&self) -> &Config {
+                &self.config
+            }
+            fn admin(&self) -> Pubkey {
+                self.admin.key()
+            }
+            fn audit_log(&self) -> &AccountLoader<'info, AuditLog> {
+                &self.audit_log
+            }
+            fn admin_set(&self) -> Option<&AdminSet> {
+                self.admin_set.as_deref()
+            }
+        }
+    )*};
+    // Structs with an optional `role_grant` held by the admin signer
+    ($($name:ident),* $(,)?) => {$(
+        impl<'info> AdminAccounts<'info> for $name<'info> {
+            fn config(&self) -> &Config {
+                &self.config
+            }
+            fn admin(&self) -> Pubkey {
+                self.admin.key()
+            }
+            fn audit_log(&self) -> &AccountLoader<'info, AuditLog> {
+                &self.audit_log
+            }
+            fn admin_set(&self) -> Option<&AdminSet> {
+                self.admin_set.as_deref()
+            }
+            fn role_grant(&self) -> Option<&RoleGrant> {
+                self.role_grant.as_deref()
+            }
+        }
+    )*};
+}
+
+impl_admin_accounts!(
+    AddScopeMint, AllowlistMint, AwardBounty, BlacklistMint, BumpRulesetVersion, CloseOracle, 
+    ConfigureCouncil, DeclareRulesetCompatible, InitCompressedTree, InitFeeTokenVault, InitFeeVault, 
+    InitRiskBloom, ManageOracle, OnlyAdmin, PublishRuleset, QueueAdminAction, RejectReport, 
+    RemoveAllowlistedMint, RemoveScopeMint, ResetRiskBloom, ResolveAdminAction, Revoke, 
+    RevokeCollection, RevokePair, RevokePool, RevokeProgram, RevokeWallet, SetForeignEmitter, 
+    SetRulesetCircuit, SetRulesetValidity, UpdateOracle, WithdrawFeeTokens, WithdrawFees, 
+);
+
+impl_admin_accounts!(full: GrantRole, ManageUpgradeAuthority, RevokeRole, SlashOracle, WaiveGradeConsistency);
+
+impl<'info> AdminAccounts<'info> for ConfigureAdminSet<'info> {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+    fn admin(&self) -> Pubkey {
+        self.admin.key()
+    }
+    fn audit_log(&self) -> &AccountLoader<'info, AuditLog> {
+        &self.audit_log
+    }
+    fn admin_set(&self) -> Option<&AdminSet> {
+        Some(&self.admin_set)
+    }
+}
+
+/// require_role for `ctx.accounts.admin`, then the audit log entry. The
+/// preamble of every role-gated admin instruction.
+pub(crate) fn authorize_admin_action<'info, T: Bumps + AdminAccounts<'info>>(
+    ctx: &Context<'_, '_, '_, 'info, T>, 
+    role: Role, 
+    action: [u8; 8], 
+    args: &impl AnchorSerialize
+) -> Result<()> {
+    let accounts = &*ctx.accounts;
+    require_role(accounts.config(), &accounts.admin(), role, accounts.role_grant(), accounts.admin_set(), ctx.remaining_accounts)?;
+    record_admin_action(accounts.audit_log(), &accounts.admin(), action, args)
+}
+
+/// authorize_admin_action for instructions no role grant covers.
+pub(crate) fn authorize_full_admin_action<'info, T: Bumps + AdminAccounts<'info>>(
+    ctx: &Context<'_, '_, '_, 'info, T>, 
+    action: [u8; 8], 
+    args: &impl AnchorSerialize
+) -> Result<()> {
+    let accounts = &*ctx.accounts;
+    require_admin(accounts.config(), &accounts.admin(), accounts.admin_set(), ctx.remaining_accounts)?;
+    record_admin_action(accounts.audit_log(), &accounts.admin(), action, args)
+}
+
+/// authorize_admin_action that also lets Config.guardian through (see
+/// require_role_or_guardian).
+pub(crate) fn authorize_guardian_action<'info, T: Bumps + AdminAccounts<'info>>(
+    ctx: &Context<'_, '_, '_, 'info, T>, 
+    role: Role, 
+    action: [u8; 8], 
+    args: &impl AnchorSerialize
+) -> Result<()> {
+    let accounts = &*ctx.accounts;
+    require_role_or_guardian(accounts.config(), &accounts.admin(), role, accounts.role_grant(), accounts.admin_set(), ctx.remaining_accounts)?;
+    record_admin_action(accounts.audit_log(), &accounts.admin(), action, args)
+}
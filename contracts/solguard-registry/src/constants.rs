@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+use crate::state::*;
+
+pub const MAX_ADMIN_SET_MEMBERS: usize = 10;
+pub const MAX_COUNCIL_MEMBERS: usize = 16;
+pub const MAX_ORACLE_NAME_LEN: usize = 32;
+pub const MAX_ORACLE_URI_LEN: usize = 200;
+pub const MAX_RULESET_URI_LEN: usize = 200;
+pub const MAX_CONSUMER_VERSIONS: usize = 8;
+pub const MAX_ACTIVE_RULESETS: usize = 4; // versions besides the current one consumers may still verify against
+pub const HISTORY_LEN: usize = 16;
+pub const GRADE_INDEX_PAGE_LEN: usize = 64;
+pub const AUDIT_LOG_LEN: usize = 64;
+pub const RISK_BLOOM_BYTES: usize = 4096; // 32768 bits, ~2% false positives at 3000 mints
+pub const RISK_BLOOM_HASHES: usize = 8;
+pub const MAX_BATCH_SIZE: usize = 20;
+pub const BATCH_ITEM_ACCOUNTS: usize = 10; // remaining accounts per attest_tokens_batch item
+pub const MAX_MERKLE_DEPTH: usize = 32;
+pub const SUBSCORE_COUNT: usize = 5; // liquidity, authority, holders, contract risk, social
+pub const SUBSCORE_LIQUIDITY: usize = 0;
+pub const SUBSCORE_AUTHORITY: usize = 1;
+pub const SUBSCORE_HOLDERS: usize = 2;
+pub const SUBSCORE_CONTRACT: usize = 3;
+pub const SUBSCORE_SOCIAL: usize = 4;
+pub const REPORT_SCHEME_NONE: u8 = 0;
+pub const REPORT_SCHEME_IPFS: u8 = 1;    // content_id = sha2-256 digest of a CIDv1 raw block
+pub const REPORT_SCHEME_ARWEAVE: u8 = 2; // content_id = transaction id
+pub const ATTEST_SIGNATURE_DOMAIN: &[u8] = b"solguard-registry:attest:v1"; // prefixes signed attestation payloads
+pub const WORMHOLE_PAYLOAD_ATTESTATION: u8 = 1; // payload id for attestation messages
+pub const WORMHOLE_EMITTER_SEED: &[u8] = b"emitter";
+pub const WORMHOLE_PAYLOAD_LEN: usize = 1 + 32 + 2 + 2 + 1 + 32 + 8;
+pub const ZK_PUBLIC_INPUTS: usize = 4; // see zk_public_inputs
+pub const GRADE_LEVELS_LEGACY: u8 = 3;   // red / yellow / green
+pub const GRADE_LEVELS_EXTENDED: u8 = 5; // critical / red / yellow / green / verified
+// Grades on the extended scale; legacy grades map onto GRADE_RED..=GRADE_GREEN
+pub const GRADE_CRITICAL: u8 = 0;
+pub const GRADE_RED: u8 = 1;
+pub const GRADE_YELLOW: u8 = 2;
+pub const GRADE_GREEN: u8 = 3;
+pub const GRADE_VERIFIED: u8 = 4;
+// Attestation.risk_flags bits, read from the mint's Token-2022 extensions
+pub const RISK_TRANSFER_FEE: u16 = 1 << 0;
+pub const RISK_PERMANENT_DELEGATE: u16 = 1 << 1;
+pub const RISK_TRANSFER_HOOK: u16 = 1 << 2;          // hooks into SOLGUARD_TRANSFER_HOOK_ID are not flagged
+pub const RISK_CONFIDENTIAL_TRANSFER: u16 = 1 << 3;
+pub const RISK_NON_TRANSFERABLE: u16 = 1 << 4;
+pub const RISK_DEFAULT_FROZEN: u16 = 1 << 5;
+// Extensions that let the issuer seize or trap holders' tokens; not allowed at GRADE_GREEN and above
+pub const HIGH_GRADE_FORBIDDEN_RISK_FLAGS: u16 = RISK_PERMANENT_DELEGATE | RISK_TRANSFER_HOOK | RISK_NON_TRANSFERABLE | RISK_DEFAULT_FROZEN;
+// Attestation.flags bits, set by the oracle from its off-chain analysis
+pub const FLAG_FREEZE_AUTHORITY: u64 = 1 << 0;      // freeze authority still set
+pub const FLAG_MINT_AUTHORITY: u64 = 1 << 1;        // supply can still be inflated
+pub const FLAG_MUTABLE_METADATA: u64 = 1 << 2;
+pub const FLAG_LOW_LIQUIDITY: u64 = 1 << 3;
+pub const FLAG_UNLOCKED_LP: u64 = 1 << 4;           // LP tokens neither burned nor locked
+pub const FLAG_HONEYPOT_SUSPECTED: u64 = 1 << 5;    // simulated sells fail or are heavily taxed
+pub const FLAG_CONCENTRATED_HOLDERS: u64 = 1 << 6;
+pub const FLAG_IMPERSONATION: u64 = 1 << 7;         // name or symbol copies a known token
+
+// PairAttestation.risk_flags bits, set by the oracle
+pub const PAIR_RISK_FAKE_QUOTE: u16 = 1 << 0;        // quote mint impersonates a known asset
+pub const PAIR_RISK_SPOOFED_POOL: u16 = 1 << 1;      // venue market is not the canonical one for the pair
+pub const PAIR_RISK_THIN_LIQUIDITY: u16 = 1 << 2;
+pub const PAIR_RISK_PRICE_DIVERGENCE: u16 = 1 << 3;  // price far off the pair's other venues
+pub const SOLGUARD_TRANSFER_HOOK_ID: Pubkey = pubkey!("SoLGuaRdHook1111111111111111111111111111111");
+// discriminator + admin + version + grade + bump + pending admin + multisig + paused + timelock + unbonding + consensus + heartbeat + min weight
+// + deposit + ttl + dispute bond + thresholds + grade levels + extended thresholds + stale window + default max age + active rulesets + version
+// + attestation fee + fee mint + subscription price + report bond + revocation quorum + downgrade cosign delta + cooldown
+// + challenge bond + challenge window + guardian + crank reward + governance
+pub const CONFIG_SPACE: usize = 8 + 32 + 2 + 1 + 1 + 32 + 1 + 1 + 8 + 8 + 1 + 2 + 8 + 4 + 8 + 8 + 8 + 2 + 2 + 1 + 2 + 2 + 8 + 8 + 3 * MAX_ACTIVE_RULESETS + 1 + 8 + 32 + 8 + 8 + 1 + 2 + 8 + 8 + 8 + 32 + 8 + 32;
+// discriminator + bump + active + stake + unbonding + unbonding end + last seen + weight + deposit + depositor + deactivated + scoped + evm address + version
+pub const ORACLE_SPACE: usize = 8 + 1 + 1 + 8 + 8 + 8 + 8 + 2 + 8 + 32 + 8 + 1 + 20 + 1;
+// discriminator + zero-copy body, see the layout table on Attestation
+pub const ATTESTATION_SPACE: usize = 8 + std::mem::size_of::<Attestation>();
+// Borsh Attestation layout used before the zero-copy conversion, see migrate_account
+pub(crate) const LEGACY_ATTESTATION_SPACE: usize = 8 + 32 + 2 + 2 + 1 + 32 + 32 + 8 + 1 + 1 + 8 + 32 + 8 + 1 + 32 + 33 + 2 * SUBSCORE_COUNT + 1 + 2 + 1;
+// Current account layout versions; migrate_account upgrades anything older
+// Config: 2: attestation_fee, 3: fee_mint, 4: subscription_price, 5: report_bond, 6: revocation_quorum,
+// 7: downgrade_cosign_delta_bps, 8: attestation_cooldown, 9: challenge_bond and challenge_window, 10: guardian,
+// 11: crank_reward, 12: governance
+pub const CONFIG_LAYOUT_VERSION: u8 = 12;
+pub const ORACLE_LAYOUT_VERSION: u8 = 1;
+// RegistryStats: 1: live_score_sum
+pub const REGISTRY_STATS_LAYOUT_VERSION: u8 = 1;
+// Attestation: 2: frozen, 3: metadata_hash, 4: mint snapshot, 5: risk_flags, 6: authorities, 7: cooldown_waived,
+// 8: challenged, 9: expired, 10: category_proofs, 11: flags, 12: supersede links
+pub const ATTESTATION_LAYOUT_VERSION: u8 = 12;
+// Attestation data offsets (discriminator included) for getProgramAccounts memcmp filters
+pub const ATTESTATION_MINT_OFFSET: usize = 8;
+pub const ATTESTATION_ATTESTED_BY_OFFSET: usize = 40;
+pub const ATTESTATION_PAYER_OFFSET: usize = 72;
+pub const ATTESTATION_RULESET_VERSION_OFFSET: usize = 224;
+pub const ATTESTATION_GRADE_OFFSET: usize = 240;
+pub const ATTESTATION_REVOKED_OFFSET: usize = 242;
+pub const ATTESTATION_FROZEN_OFFSET: usize = 248;
+pub const ATTESTATION_RISK_FLAGS_OFFSET: usize = 250;
+pub const ATTESTATION_EXPIRED_OFFSET: usize = 254;
+pub const ATTESTATION_SUPERSEDED_OFFSET: usize = 255;
+pub const ATTESTATION_METADATA_HASH_OFFSET: usize = 256;
+pub const ATTESTATION_SUPPLY_OFFSET: usize = 288;
+pub const ATTESTATION_MINT_AUTHORITY_OFFSET: usize = 304;
+pub const ATTESTATION_FREEZE_AUTHORITY_OFFSET: usize = 336;
+pub const ATTESTATION_CATEGORY_PROOFS_OFFSET: usize = 368;
+pub const ATTESTATION_FLAGS_OFFSET: usize = 528;
+pub const ATTESTATION_SUPERSEDES_OFFSET: usize = 536;
+// discriminator + attestations + revocations + live by grade + active oracles + bump + live score sum + version
+pub const REGISTRY_STATS_SPACE: usize = 8 + 8 + 8 + 8 * GRADE_LEVELS_EXTENDED as usize + 4 + 1 + 8 + 1;
+// discriminator + mint + grade + governance + set at + payer + bump
+pub const GRADE_OVERRIDE_SPACE: usize = 8 + 32 + 1 + 32 + 8 + 32 + 1;
+// discriminator + epoch + live by grade + live total + average score + attestations + revocations + active oracles
+// + slot + taken at + taken by + bump
+pub const EPOCH_SNAPSHOT_SPACE: usize = 8 + 8 + 8 * GRADE_LEVELS_EXTENDED as usize + 8 + 2 + 8 + 8 + 4 + 8 + 8 + 32 + 1;
+// discriminator + collection mint + version + score + grade + grade levels + proofs hash + attested by + attested at + valid until
+// + inherit + item override + revoked + bump
+pub const COLLECTION_ATTESTATION_SPACE: usize = 8 + 32 + 2 + 2 + 1 + 1 + 32 + 32 + 8 + 8 + 1 + 1 + 1 + 1;
+// discriminator + program + version + score + grade + grade levels + proofs hash + upgrade authority + deploy slot + build hash
+// + attested by + attested at + valid until + revoked + bump
+pub const PROGRAM_ATTESTATION_SPACE: usize = 8 + 32 + 2 + 2 + 1 + 1 + 32 + 33 + 8 + 32 + 32 + 8 + 8 + 1 + 1;
+// discriminator + wallet + version + score + grade + grade levels + proofs hash + created + rugged + attested by + attested at
+// + valid until + revoked + bump
+pub const WALLET_ATTESTATION_SPACE: usize = 8 + 32 + 2 + 2 + 1 + 1 + 32 + 4 + 4 + 32 + 8 + 8 + 1 + 1;
+// discriminator + pool + dex program + base mint + quote mint + version + score + grade + grade levels + proofs hash
+// + burned + locked + unlock time + attested by + attested at + valid until + revoked + bump
+pub const POOL_ATTESTATION_SPACE: usize = 8 + 32 + 32 + 32 + 32 + 2 + 2 + 1 + 1 + 32 + 2 + 2 + 8 + 32 + 8 + 8 + 1 + 1;
+// discriminator + mint a + mint b + venue + version + score + grade + grade levels + proofs hash + risk flags
+// + attested by + attested at + valid until + revoked + bump
+pub const PAIR_ATTESTATION_SPACE: usize = 8 + 32 + 32 + 32 + 2 + 2 + 1 + 1 + 32 + 2 + 32 + 8 + 8 + 1 + 1;
+// AMM programs whose pool accounts may be attested
+pub const POOL_PROGRAMS: [Pubkey; 6] = [
+    pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"), // Raydium AMM v4
+    pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C"), // Raydium CPMM
+    pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK"), // Raydium CLMM
+    pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"),  // Orca Whirlpools
+    pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo"),  // Meteora DLMM
+    pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB"), // Meteora dynamic AMM
+];
+// discriminator + mint + version + item (score + grade + proofs hash + nonce + report + subscores + flags) + proposer + payer
+// + base attested at + proposed at + bump
+pub const PENDING_DOWNGRADE_SPACE: usize = 8 + 32 + 2 + (2 + 1 + 32 + 8 + 33 + 2 * SUBSCORE_COUNT + 8) + 32 + 32 + 8 + 8 + 1;
+// discriminator + mint + watchers + changes + last changed slot + bump
+pub const MINT_WATCH_SPACE: usize = 8 + 32 + 4 + 8 + 8 + 1;
+// discriminator + owner + mint + seen changes + registered at + bump
+pub const WATCHER_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1;
+// discriminator + zero-copy body, see AuditLog
+pub const AUDIT_LOG_SPACE: usize = 8 + std::mem::size_of::<AuditLog>();
+// discriminator + zero-copy body, see RiskBloom
+pub const RISK_BLOOM_SPACE: usize = 8 + std::mem::size_of::<RiskBloom>();
+// discriminator + mint + ruleset version + attestation + attested at + bump
+pub const LATEST_ATTESTATION_SPACE: usize = 8 + 32 + 2 + 32 + 8 + 1;
+// discriminator + proofs hash + ruleset version + mint + oracle + claimed at + bump
+pub const PROOF_MARKER_SPACE: usize = 8 + 32 + 2 + 32 + 32 + 8 + 1;
+// discriminator + grade + page + mints + bump
+pub const GRADE_INDEX_PAGE_SPACE: usize = 8 + 1 + 4 + 4 + 32 * GRADE_INDEX_PAGE_LEN + 1;
+// discriminator + mint + attestation + grade + page + listed + bump
+pub const GRADE_INDEX_ENTRY_SPACE: usize = 8 + 32 + 32 + 1 + 4 + 1 + 1;
+// discriminator + mint + head + len + entries + bump
+pub const HISTORY_SPACE: usize = 8 + 32 + 1 + 1 + (2 + 1 + 32 + 8) * HISTORY_LEN + 1;
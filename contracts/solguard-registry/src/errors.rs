@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Oracle inactive or not authorized")]
+    OracleInactive,
+    #[msg("Invalid grade value for the configured grade scale")]
+    InvalidGrade,
+    #[msg("Invalid score value (must be 0-10000)")]
+    InvalidScore,
+    #[msg("Invalid ruleset version")]
+    InvalidRulesetVersion,
+    #[msg("Attestation already revoked")]
+    AlreadyRevoked,
+    #[msg("Invalid admin pubkey")]
+    InvalidAdmin,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("Signer is not authorized as admin")]
+    Unauthorized,
+    #[msg("Admin set account required while multisig is enabled")]
+    AdminSetRequired,
+    #[msg("Not enough admin set members signed")]
+    InsufficientAdminSignatures,
+    #[msg("Invalid admin set members or threshold")]
+    InvalidAdminSet,
+    #[msg("Registry is paused")]
+    RegistryPaused,
+    #[msg("Timelock enabled; queue this action instead")]
+    TimelockRequired,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    #[msg("Invalid unbonding period")]
+    InvalidUnbondingPeriod,
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount,
+    #[msg("Insufficient oracle stake")]
+    InsufficientStake,
+    #[msg("Unbonding period has not elapsed")]
+    UnbondingNotElapsed,
+    #[msg("Oracle name too long")]
+    OracleNameTooLong,
+    #[msg("Oracle operator URI too long")]
+    OracleUriTooLong,
+    #[msg("Consensus mode enabled; submit instead of attesting directly")]
+    ConsensusRequired,
+    #[msg("Consensus mode is not enabled")]
+    ConsensusDisabled,
+    #[msg("Not enough matching oracle submissions")]
+    InsufficientConsensus,
+    #[msg("Invalid oracle submission")]
+    InvalidSubmission,
+    #[msg("Submission predates the current attestation")]
+    StaleSubmission,
+    #[msg("Duplicate oracle submission")]
+    DuplicateSubmission,
+    #[msg("Oracle submissions do not agree")]
+    SubmissionMismatch,
+    #[msg("Remaining account not owned by the registry")]
+    InvalidRemainingAccount,
+    #[msg("Signer did not create this attestation")]
+    NotAttester,
+    #[msg("Consensus attestations cannot be revoked by a single oracle")]
+    ConsensusAttestation,
+    #[msg("Oracle weight must be at least 1")]
+    InvalidOracleWeight,
+    #[msg("Oracle must be removed before closing")]
+    OracleStillActive,
+    #[msg("Oracle still has stake or unbonding lamports")]
+    OracleStakeOutstanding,
+    #[msg("Mint is outside this oracle's scope")]
+    MintOutOfScope,
+    #[msg("Invalid attestation TTL")]
+    InvalidAttestationTtl,
+    #[msg("Attestation has been revoked")]
+    AttestationRevoked,
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+    #[msg("Proofs hash does not match the attested report")]
+    ProofsHashMismatch,
+    #[msg("Only revoked or superseded attestations can be closed")]
+    AttestationStillLive,
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+    #[msg("Batch is empty, too large, or missing accounts")]
+    InvalidBatch,
+    #[msg("Attestation nonce must be the current nonce + 1")]
+    StaleNonce,
+    #[msg("Attestation is not revoked")]
+    NotRevoked,
+    #[msg("Unknown report storage scheme")]
+    InvalidReportPointer,
+    #[msg("Invalid grade thresholds")]
+    InvalidGradeThresholds,
+    #[msg("Grade does not match the score band")]
+    InconsistentGrade,
+    #[msg("Grade levels must be 3 or 5")]
+    InvalidGradeLevels,
+    #[msg("Stale window must be non-negative")]
+    InvalidStaleWindow,
+    #[msg("Attestation is older than the accepted age")]
+    AttestationStale,
+    #[msg("Attested grade is below the required minimum")]
+    InsufficientGrade,
+    #[msg("Max age must be non-negative")]
+    InvalidMaxAge,
+    #[msg("Too many accepted ruleset versions")]
+    TooManyRulesetVersions,
+    #[msg("Signer is not the consumer program's upgrade authority")]
+    NotConsumerAuthority,
+    #[msg("Missing or invalid ed25519 oracle signature")]
+    InvalidOracleSignature,
+    #[msg("Missing or invalid secp256k1 oracle signature")]
+    InvalidEvmSignature,
+    #[msg("Oracle has no EVM address registered")]
+    EvmKeyNotRegistered,
+    #[msg("Foreign emitter is not allowlisted")]
+    InvalidForeignEmitter,
+    #[msg("A newer attestation already exists for this mint")]
+    StaleForeignAttestation,
+    #[msg("Verifying key has the wrong number of input points")]
+    InvalidVerifyingKey,
+    #[msg("Groth16 proof failed verification")]
+    InvalidZkProof,
+    #[msg("Merkle inclusion proof does not match the root")]
+    InvalidMerkleProof,
+    #[msg("Attestation is newer than the root")]
+    StaleRootLeaf,
+    #[msg("Only the oracle that wrote the leaf may replace it")]
+    NotOriginalAttester,
+    #[msg("Ruleset URI too long")]
+    RulesetUriTooLong,
+    #[msg("Ruleset version has not been published")]
+    RulesetNotPublished,
+    #[msg("Ruleset activation slot has not been reached")]
+    RulesetNotActivatable,
+    #[msg("Invalid ruleset validity window")]
+    InvalidValidityWindow,
+    #[msg("Account is not of the requested kind")]
+    AccountKindMismatch,
+    #[msg("Fee vault account required while an attestation fee is set")]
+    FeeVaultRequired,
+    #[msg("Fee vault balance above rent is below the requested amount")]
+    InsufficientFees,
+    #[msg("Fee mint, payer token account, fee token vault and token program required for SPL fees")]
+    FeeTokenAccountsRequired,
+    #[msg("Fee token accounts do not match the configured fee mint")]
+    WrongFeeMint,
+    #[msg("Calling program has no subscription")]
+    SubscriptionRequired,
+    #[msg("Subscription does not cover the current epoch")]
+    SubscriptionInactive,
+    #[msg("Subscriptions are not enabled")]
+    SubscriptionsDisabled,
+    #[msg("Invalid number of subscription epochs")]
+    InvalidSubscriptionEpochs,
+    #[msg("Report is not open")]
+    ReportNotOpen,
+    #[msg("Quorum revocation is not enabled")]
+    QuorumRevocationDisabled,
+    #[msg("Oracle already voted to revoke this attestation")]
+    DuplicateRevocationVote,
+    #[msg("Attestation is frozen pending investigation")]
+    AttestationFrozen,
+    #[msg("Attestation is already frozen")]
+    AlreadyFrozen,
+    #[msg("Attestation is not frozen")]
+    NotFrozen,
+    #[msg("Mint is blacklisted")]
+    MintBlacklisted,
+    #[msg("Mint has no attestation")]
+    AttestationMissing,
+    #[msg("Audit log required for admin actions")]
+    AuditLogRequired,
+    #[msg("Mint is not a verified member of the attested collection")]
+    NotInCollection,
+    #[msg("ProgramData account required for upgradeable programs")]
+    ProgramDataRequired,
+    #[msg("Program was redeployed or changed upgrade authority since it was attested")]
+    ProgramChangedSinceAttestation,
+    #[msg("Program is still upgradeable")]
+    ProgramUpgradeable,
+    #[msg("Rugged token count exceeds tokens created")]
+    InvalidWalletHistory,
+    #[msg("Wallet has rugged more tokens than allowed")]
+    WalletRugHistory,
+    #[msg("Pool account is not owned by a supported AMM program")]
+    UnsupportedPoolProgram,
+    #[msg("Burned and locked LP shares exceed 100%")]
+    InvalidLpShares,
+    #[msg("Too little of the pool's LP supply is burned or locked")]
+    LiquidityNotSecured,
+    #[msg("Mint has Token-2022 extensions not allowed at this grade")]
+    ForbiddenMintExtension,
+    #[msg("Downgrade this large needs a co-signer, use propose_downgrade")]
+    DowngradeRequiresCosign,
+    #[msg("Downgrade does not need a co-signer, attest directly")]
+    CosignNotRequired,
+    #[msg("Another oracle's downgrade is already pending for this attestation")]
+    DowngradeAlreadyPending,
+    #[msg("Attestation was rewritten after the downgrade was proposed")]
+    StalePendingDowngrade,
+    #[msg("The proposing oracle cannot co-sign its own downgrade")]
+    CosignerIsProposer,
+    #[msg("Attestation cooldown must not be negative")]
+    InvalidCooldown,
+    #[msg("Attestation was rewritten too recently")]
+    AttestationCooldown,
+    #[msg("Reveal must land in a later slot than its commitment")]
+    RevealTooEarly,
+    #[msg("Revealed attestation does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Challenges are disabled")]
+    ChallengesDisabled,
+    #[msg("Challenge window must not be negative")]
+    InvalidChallengeWindow,
+    #[msg("Attestation is under challenge")]
+    AttestationUnderChallenge,
+    #[msg("Challenge has already been answered")]
+    ChallengeAlreadyAnswered,
+    #[msg("Challenge answer window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Council needs 1-16 distinct members and a quorum no larger than the membership")]
+    InvalidCouncil,
+    #[msg("Signer is not a council member")]
+    NotCouncilMember,
+    #[msg("Council member has already voted on this case")]
+    DuplicateCouncilVote,
+    #[msg("Account is not an open dispute or challenge")]
+    InvalidCouncilCase,
+    #[msg("Council has already reached quorum on this case")]
+    CouncilCaseDecided,
+    #[msg("Pair mints must differ and mint_a must sort below mint_b")]
+    InvalidPair,
+    #[msg("Pair carries a risk flag the caller forbids")]
+    PairRiskFlagged,
+    #[msg("Registry upgrade authority is not held by its upgrade_authority PDA")]
+    UpgradeAuthorityNotHeld,
+    #[msg("New upgrade authority account required")]
+    NewUpgradeAuthorityRequired,
+    #[msg("Snapshots can only be taken for the current epoch")]
+    InvalidSnapshotEpoch,
+    #[msg("Signer is not the registry's governance authority")]
+    NotGovernance,
+    #[msg("Superseded attestation must be the mint's latest attestation")]
+    SupersededAttestationMismatch,
+    #[msg("Attestation has been superseded by a newer ruleset version")]
+    AttestationSuperseded,
+    #[msg("Version range must have min_version <= max_version, or both zero")]
+    InvalidVersionRange,
+    #[msg("Grade index accounts do not match the mint's entry")]
+    GradeIndexMismatch,
+    #[msg("Grade index page is full")]
+    GradeIndexPageFull,
+    #[msg("Grade index pages must be created in order")]
+    GradeIndexPageOutOfOrder,
+    #[msg("Mint is not blacklisted, revoked or graded red")]
+    NotRiskBloomCandidate,
+    #[msg("Proofs hash was already used for another mint under this ruleset version")]
+    ProofsHashReused,
+    #[msg("Caller is not the consumer program's signer PDA")]
+    CallerMismatch,
+    #[msg("Grade waiver payer missing or not the one that funded the waiver")]
+    GradeWaiverPayerMismatch,
+    #[msg("A foreign attestation cannot replace one written on this chain")]
+    LocalAttestationExists,
+}
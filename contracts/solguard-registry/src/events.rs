@@ -0,0 +1,1063 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub ruleset_version: u16,
+    pub min_grade: u8,
+}
+
+#[event]
+pub struct OracleAdded {
+    pub oracle: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleRemoved {
+    pub oracle: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct MinGradeUpdated {
+    pub old_grade: u8,
+    pub new_grade: u8,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RulesetPublished {
+    pub version: u16,
+    pub definition_hash: [u8; 32],
+    pub uri: String,
+    pub activation_slot: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RulesetValidityUpdated {
+    pub version: u16,
+    pub valid_from: i64,
+    pub valid_until: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RulesetVersionBumped {
+    pub old_version: u16,
+    pub new_version: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub current_admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminSetUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminSetCleared {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RegistryPaused {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RegistryUnpaused {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct TimelockDelayUpdated {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GradeThresholdsUpdated {
+    pub verified_bps: u16,
+    pub green_bps: u16,
+    pub yellow_bps: u16,
+    pub red_bps: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GradeLevelsUpdated {
+    pub old_levels: u8,
+    pub new_levels: u8,
+    pub min_grade: u8, // min_grade after conversion to the new scale
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminActionQueued {
+    pub action: AdminAction,
+    pub eta: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminActionExecuted {
+    pub action: AdminAction,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AdminActionCancelled {
+    pub action: AdminAction,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct UnbondingPeriodUpdated {
+    pub old_period: i64,
+    pub new_period: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleClosed {
+    pub oracle: Pubkey,
+    pub deposit: u64,
+    pub depositor: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ConsumerConfigUpdated {
+    pub consumer_program: Pubkey,
+    pub authority: Pubkey,
+    pub min_grade: u8,
+    pub max_age: Option<i64>,
+    pub accepted_versions: Vec<u16>,
+    pub min_version: u16,
+    pub max_version: u16,
+}
+
+#[event]
+pub struct ActiveRulesetsUpdated {
+    pub rulesets: Vec<ActiveRuleset>,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationFeeUpdated {
+    pub old_fee: u64,
+    pub new_fee: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub total_withdrawn: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct FeeMintUpdated {
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct FeeTokensWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct SubscriptionFunded {
+    pub consumer_program: Pubkey,
+    pub funder: Pubkey,
+    pub epochs: u64,
+    pub amount: u64,
+    pub paid_through_epoch: u64,
+}
+
+#[event]
+pub struct SubscriptionPriceUpdated {
+    pub old_price: u64,
+    pub new_price: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct DefaultMaxAgeUpdated {
+    pub old_max_age: i64,
+    pub new_max_age: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct StaleWindowUpdated {
+    pub old_stale_after: i64,
+    pub new_stale_after: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationTtlUpdated {
+    pub old_ttl: i64,
+    pub new_ttl: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleDepositUpdated {
+    pub old_deposit: u64,
+    pub new_deposit: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleEvmAddressUpdated {
+    pub oracle: Pubkey,
+    pub evm_address: [u8; 20],
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleScopeUpdated {
+    pub oracle: Pubkey,
+    pub scoped: bool,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ScopeMintAdded {
+    pub oracle: Pubkey,
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ScopeMintRemoved {
+    pub oracle: Pubkey,
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleWeightUpdated {
+    pub oracle: Pubkey,
+    pub old_weight: u16,
+    pub new_weight: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct MaxHeartbeatAgeUpdated {
+    pub old_age: u64,
+    pub new_age: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleStaked {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+}
+
+#[event]
+pub struct OracleUnstaked {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub unbonding_ends_at: i64,
+}
+
+#[event]
+pub struct OracleStakeWithdrawn {
+    pub oracle: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OracleSlashed {
+    pub oracle: Pubkey,
+    pub amount: u64,
+    pub treasury: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OracleMetadataUpdated {
+    pub oracle: Pubkey,
+    pub name: String,
+    pub operator_uri: String,
+    pub contact_hash: [u8; 32],
+}
+
+#[event]
+pub struct ConsensusParamsUpdated {
+    pub threshold: u8,
+    pub score_tolerance_bps: u16,
+    pub min_weight: u32,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationSubmitted {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+    pub score: u16,
+    pub grade: u8,
+    pub proofs_hash: [u8; 32],
+}
+
+#[event]
+pub struct ConsensusReached {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub oracles: Vec<Pubkey>,
+}
+
+#[event]
+pub struct TokenAttested {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+/// Emitted alongside TokenAttested with enough state that indexers need not
+/// fetch the attestation to learn what changed.
+#[event]
+pub struct TokenAttestedV2 {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,                   // on the `grade_levels` scale
+    pub grade_levels: u8,
+    pub previous_score: Option<u16>, // None for a first attestation
+    pub previous_grade: Option<u8>,  // extended scale
+    pub flags: u64,                  // FLAG_*
+    pub attested_by: Pubkey,
+    pub oracle_count: u8,
+    pub oracle_weight: u64,          // summed across oracles in consensus mode
+    pub attested_at: i64,
+    pub valid_until: i64,            // 0 = never expires
+    pub nonce: u64,
+}
+
+impl TokenAttestedV2 {
+    pub(crate) fn new(a: &Attestation, prior: Option<(u16, u8)>, oracle_weight: u64) -> Self {
+        Self {
+            mint: a.mint,
+            ruleset_version: a.ruleset_version,
+            score: a.score_bps,
+            grade: a.grade,
+            grade_levels: a.grade_levels,
+            previous_score: prior.map(|(score, _)| score),
+            previous_grade: prior.map(|(_, grade)| grade),
+            flags: a.flags,
+            attested_by: a.attested_by,
+            oracle_count: a.oracle_count,
+            oracle_weight,
+            attested_at: a.attested_at,
+            valid_until: a.valid_until,
+            nonce: a.nonce,
+        }
+    }
+}
+
+/// Emitted after TokenAttestedV2 whenever an attestation is overwritten, so
+/// consumers can react to downgrades without tracking previous state.
+#[event]
+pub struct ScoreChanged {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub old_score: u16,
+    pub new_score: u16,
+    pub score_delta: i16,  // new - old, negative for a downgrade
+    pub old_grade: u8,     // extended scale
+    pub new_grade: u8,     // extended scale
+    pub attested_by: Pubkey,
+    pub nonce: u64,
+}
+
+impl ScoreChanged {
+    /// None for a first attestation.
+    pub(crate) fn new(a: &Attestation, prior: Option<(u16, u8)>) -> Option<Self> {
+        prior.map(|(old_score, old_grade)| Self {
+            mint: a.mint,
+            ruleset_version: a.ruleset_version,
+            old_score,
+            new_score: a.score_bps,
+            score_delta: a.score_bps as i16 - old_score as i16,
+            old_grade,
+            new_grade: a.normalized_grade(),
+            attested_by: a.attested_by,
+            nonce: a.nonce,
+        })
+    }
+}
+
+#[event]
+pub struct CompressedTreeCreated {
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct CompressedAttestationWritten {
+    pub merkle_tree: Pubkey,
+    pub leaf_index: u64,
+    pub leaf: CompressedAttestation,
+}
+
+#[event]
+pub struct AttestationRootPosted {
+    pub oracle: Pubkey,
+    pub ruleset_version: u16,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub posted_at: i64,
+}
+
+#[event]
+pub struct RulesetCircuitUpdated {
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ForeignEmitterUpdated {
+    pub chain: u16,
+    pub address: [u8; 32],
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ForeignAttestationReceived {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8, // extended scale
+    pub origin_chain: u16,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct AttestationPublished {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub nonce: u64,
+    pub wormhole_message: Pubkey,
+}
+
+#[event]
+pub struct CollectionAttested {
+    pub collection_mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub inherit: bool,
+    pub item_override: bool,
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+#[event]
+pub struct CollectionAttestationRevoked {
+    pub collection_mint: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+    pub reason: RevocationReason,
+}
+
+#[event]
+pub struct ProgramAttested {
+    pub program_id: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub upgrade_authority: Option<Pubkey>,
+    pub build_hash: [u8; 32],
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+#[event]
+pub struct ProgramAttestationRevoked {
+    pub program_id: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+    pub reason: RevocationReason,
+}
+
+#[event]
+pub struct WalletAttested {
+    pub wallet: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub tokens_created: u32,
+    pub tokens_rugged: u32,
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+#[event]
+pub struct WalletAttestationRevoked {
+    pub wallet: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+    pub reason: RevocationReason,
+}
+
+#[event]
+pub struct PoolAttested {
+    pub pool: Pubkey,
+    pub dex_program: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub lp_burned_bps: u16,
+    pub lp_locked_bps: u16,
+    pub lp_unlock_at: i64,
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+#[event]
+pub struct PoolAttestationRevoked {
+    pub pool: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+    pub reason: RevocationReason,
+}
+
+#[event]
+pub struct PairAttested {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub venue: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub risk_flags: u16,
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+}
+
+#[event]
+pub struct PairAttestationRevoked {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub venue: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+    pub reason: RevocationReason,
+}
+
+#[event]
+pub struct DowngradeCosignDeltaUpdated {
+    pub old_delta_bps: u16,
+    pub new_delta_bps: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct DowngradeProposed {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub proposer: Pubkey,
+    pub previous_score: u16,
+    pub score: u16,
+    pub grade: u8,
+}
+
+#[event]
+pub struct DowngradeCosigned {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub proposer: Pubkey,
+    pub cosigner: Pubkey,
+    pub score: u16,
+    pub grade: u8,
+}
+
+#[event]
+pub struct AttestationCooldownUpdated {
+    pub old_cooldown: i64,
+    pub new_cooldown: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationCooldownWaived {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GradeConsistencyWaived {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationCommitted {
+    pub oracle: Pubkey,
+    pub hash: [u8; 32],
+    pub committed_slot: u64,
+}
+
+#[event]
+pub struct UpgradeAuthorityChanged {
+    pub new_authority: Option<Pubkey>, // None once burned
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct ChallengeParamsUpdated {
+    pub bond: u64,
+    pub window: i64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationChallenged {
+    pub challenge: Pubkey,
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub evidence_hash: [u8; 32],
+    pub answer_deadline: i64,
+}
+
+#[event]
+pub struct ChallengeAnswered {
+    pub challenge: Pubkey,
+    pub oracle: Pubkey,
+    pub response_hash: [u8; 32],
+}
+
+#[event]
+pub struct ChallengeResolved {
+    pub challenge: Pubkey,
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub challenger: Pubkey,
+    pub upheld: bool,
+    pub bond: u64,
+    pub slashed: u64,
+    pub resolver: Pubkey,
+}
+
+#[event]
+pub struct CouncilUpdated {
+    pub members: Vec<Pubkey>,
+    pub quorum: u8,
+    pub epoch: u32,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct CouncilVoteCast {
+    pub case: Pubkey,
+    pub member: Pubkey,
+    pub uphold: bool,
+    pub uphold_votes: u8,
+    pub reject_votes: u8,
+    pub quorum: u8,
+}
+
+#[event]
+pub struct WatcherRegistered {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub watchers: u32,
+}
+
+#[event]
+pub struct WatcherUnregistered {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub watchers: u32,
+}
+
+/// Emitted only for mints with a MintWatch, so wallets can follow the mints
+/// they hold without indexing every attestation.
+#[event]
+pub struct WatchedMintChanged {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub changes: u64,
+    pub watchers: u32,
+}
+
+#[event]
+pub struct AttestationRevoked {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,
+    pub reason: RevocationReason,
+    pub evidence_hash: [u8; 32],
+}
+
+/// Emitted by every revocation path next to its v1 event.
+#[event]
+pub struct AttestationRevokedV2 {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub source: RevocationSource,
+    pub revoked_by: Pubkey,          // admin, deciding voter or the attesting oracle
+    pub reason: RevocationReason,
+    pub evidence_hash: [u8; 32],
+    pub score: u16,                  // as attested
+    pub grade: u8,                   // extended scale
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub revoked_at: i64,
+}
+
+impl AttestationRevokedV2 {
+    pub(crate) fn new(a: &Attestation, source: RevocationSource, revoked_by: Pubkey, reason: RevocationReason) -> Result<Self> {
+        Ok(Self {
+            mint: a.mint,
+            ruleset_version: a.ruleset_version,
+            source,
+            revoked_by,
+            reason,
+            evidence_hash: a.revocation_evidence,
+            score: a.score_bps,
+            grade: a.normalized_grade(),
+            attested_by: a.attested_by,
+            attested_at: a.attested_at,
+            revoked_at: Clock::get()?.unix_timestamp,
+        })
+    }
+}
+
+#[event]
+pub struct MintBlacklisted {
+    pub mint: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RiskBloomReset {
+    pub inserted: u64, // insertions cleared
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct MintAllowlisted {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct MintRemovedFromAllowlist {
+    pub mint: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GovernanceUpdated {
+    pub old_governance: Pubkey,
+    pub new_governance: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GradeOverrideSet {
+    pub mint: Pubkey,
+    pub old_grade: Option<u8>, // None when no override was in place
+    pub new_grade: u8,
+    pub governance: Pubkey,
+}
+
+#[event]
+pub struct GradeOverrideCleared {
+    pub mint: Pubkey,
+    pub grade: u8,
+    pub governance: Pubkey,
+}
+
+#[event]
+pub struct RevocationQuorumUpdated {
+    pub old_quorum: u8,
+    pub new_quorum: u8,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RevocationVoteCast {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+    pub reason: RevocationReason,
+    pub votes: u8,
+    pub quorum: u8,
+}
+
+#[event]
+pub struct AttestationRevokedByQuorum {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub votes: u8,
+    pub reason: RevocationReason,
+    pub evidence_hash: [u8; 32],
+}
+
+#[event]
+pub struct AttestationFrozen {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub authority: Pubkey,
+    pub evidence_hash: [u8; 32],
+}
+
+#[event]
+pub struct AttestationUnfrozen {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct AttestationReinstated {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub previous_reason: u8,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct CategoryProofsAttached {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub proofs_hash: [u8; 32],
+    pub category_proofs: [[u8; 32]; SUBSCORE_COUNT], // SUBSCORE_* order
+    pub oracle: Pubkey,
+}
+
+#[event]
+pub struct AttestationRenewed {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+    pub previous_attested_at: i64,
+    pub attested_at: i64,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct RulesetCompatibilityDeclared {
+    pub from_version: u16,
+    pub to_version: u16,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct AttestationMigrated {
+    pub mint: Pubkey,
+    pub from_version: u16,
+    pub to_version: u16,
+    pub migrated_by: Pubkey,
+}
+
+#[event]
+pub struct AttestationSuperseded {
+    pub mint: Pubkey,
+    pub old_attestation: Pubkey,
+    pub old_version: u16,
+    pub new_attestation: Pubkey,
+    pub new_version: u16,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub kind: AccountKind,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct AttestationExpired {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub valid_until: i64,
+    pub cranker: Pubkey,
+}
+
+#[event]
+pub struct ExpiryCranked {
+    pub expired: u64,
+    pub reward: u64,
+    pub cranker: Pubkey,
+}
+
+#[event]
+pub struct CrankRewardUpdated {
+    pub old_reward: u64,
+    pub new_reward: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct EpochSnapshotTaken {
+    pub epoch: u64,
+    pub live_by_grade: [u64; GRADE_LEVELS_EXTENDED as usize],
+    pub live_total: u64,
+    pub average_score_bps: u16,
+    pub taken_by: Pubkey,
+}
+
+#[event]
+pub struct GradeIndexUpdated {
+    pub mint: Pubkey,
+    pub from_grade: Option<u8>, // None when the mint was not listed
+    pub to_grade: Option<u8>,   // None when unlisted on revocation
+    pub page: u32,
+}
+
+#[event]
+pub struct AttestationClosed {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub payer: Pubkey,
+    pub closed_by: Pubkey,
+}
+
+#[event]
+pub struct DisputeBondUpdated {
+    pub old_bond: u64,
+    pub new_bond: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct DisputeFiled {
+    pub dispute: Pubkey,
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub evidence_hash: [u8; 32],
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dispute: Pubkey,
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub challenger: Pubkey,
+    pub sustained: bool,
+    pub bond: u64,
+    pub reward: u64,
+    pub resolver: Pubkey,
+}
+
+#[event]
+pub struct ReportBondUpdated {
+    pub old_bond: u64,
+    pub new_bond: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RugReportSubmitted {
+    pub report: Pubkey,
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub reporter: Pubkey,
+    pub bond: u64,
+    pub evidence_hash: [u8; 32],
+}
+
+#[event]
+pub struct BountyAwarded {
+    pub report: Pubkey,
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub reporter: Pubkey,
+    pub bond: u64,
+    pub bounty: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RugReportRejected {
+    pub report: Pubkey,
+    pub attestation: Pubkey,
+    pub reporter: Pubkey,
+    pub bond: u64,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct OwnAttestationRevoked {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+}
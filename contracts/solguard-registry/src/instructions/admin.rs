@@ -0,0 +1,581 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn init_config(ctx: Context<InitConfig>, ruleset_version: u16, min_grade: u8) -> Result<()> {
+    let cfg = &mut ctx.accounts.config;
+    cfg.admin = ctx.accounts.admin.key();
+    cfg.ruleset_version = ruleset_version;
+    cfg.min_grade = min_grade; // 0=red,1=yellow,2=green
+    cfg.bump = ctx.bumps.config;
+    cfg.pending_admin = Pubkey::default();
+    cfg.grade_levels = GRADE_LEVELS_LEGACY;
+    cfg.version = CONFIG_LAYOUT_VERSION;
+    
+    emit!(ConfigInitialized {
+        admin: cfg.admin,
+        ruleset_version,
+        min_grade,
+    });
+    
+    Ok(())
+}
+
+pub fn init_audit_log(ctx: Context<InitAuditLog>) -> Result<()> {
+    require_role(&ctx.accounts.config, &ctx.accounts.admin.key(), Role::ConfigAdmin, ctx.accounts.role_grant.as_deref(), ctx.accounts.admin_set.as_deref(), ctx.remaining_accounts)?;
+    let entry = audit_entry(instruction::InitAuditLog::DISCRIMINATOR, &ctx.accounts.admin.key(), &())?;
+    let mut log = ctx.accounts.audit_log.load_init()?;
+    log.bump = ctx.bumps.audit_log;
+    log.push(entry);
+    Ok(())
+}
+
+pub fn set_stale_after(ctx: Context<OnlyAdmin>, stale_after: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetStaleAfter::DISCRIMINATOR, &stale_after)?;
+    require!(stale_after >= 0, ErrorCode::InvalidStaleWindow);
+    let old_stale_after = ctx.accounts.config.stale_after;
+    ctx.accounts.config.stale_after = stale_after;
+    
+    emit!(StaleWindowUpdated {
+        old_stale_after,
+        new_stale_after: stale_after,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_attestation_ttl(ctx: Context<OnlyAdmin>, ttl: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetAttestationTtl::DISCRIMINATOR, &ttl)?;
+    require!(ttl >= 0, ErrorCode::InvalidAttestationTtl);
+    let old_ttl = ctx.accounts.config.attestation_ttl;
+    ctx.accounts.config.attestation_ttl = ttl;
+    
+    emit!(AttestationTtlUpdated {
+        old_ttl,
+        new_ttl: ttl,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_max_heartbeat_age(ctx: Context<OnlyAdmin>, max_age_slots: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetMaxHeartbeatAge::DISCRIMINATOR, &max_age_slots)?;
+    let old_age = ctx.accounts.config.max_heartbeat_age;
+    ctx.accounts.config.max_heartbeat_age = max_age_slots;
+    
+    emit!(MaxHeartbeatAgeUpdated {
+        old_age,
+        new_age: max_age_slots,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_min_grade(ctx: Context<OnlyAdmin>, min_grade: u8) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetMinGrade::DISCRIMINATOR, &min_grade)?;
+    require!(ctx.accounts.config.timelock_delay == 0, ErrorCode::TimelockRequired);
+    apply_admin_action(&mut ctx.accounts.config, AdminAction::SetMinGrade { min_grade }, ctx.accounts.admin.key())
+}
+
+pub fn set_timelock_delay(ctx: Context<OnlyAdmin>, delay: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetTimelockDelay::DISCRIMINATOR, &delay)?;
+    // Once enabled, the delay itself can only change through the queue
+    require!(ctx.accounts.config.timelock_delay == 0, ErrorCode::TimelockRequired);
+    apply_admin_action(&mut ctx.accounts.config, AdminAction::SetTimelockDelay { delay }, ctx.accounts.admin.key())
+}
+
+pub fn set_grade_thresholds(
+    ctx: Context<OnlyAdmin>, 
+    verified_bps: u16, 
+    green_bps: u16, 
+    yellow_bps: u16, 
+    red_bps: u16
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetGradeThresholds::DISCRIMINATOR, &(verified_bps, green_bps, yellow_bps, red_bps))?;
+    require!(ctx.accounts.config.timelock_delay == 0, ErrorCode::TimelockRequired);
+    apply_admin_action(
+        &mut ctx.accounts.config, 
+        AdminAction::SetGradeThresholds { verified_bps, green_bps, yellow_bps, red_bps }, 
+        ctx.accounts.admin.key()
+    )
+}
+
+pub fn set_grade_levels(ctx: Context<OnlyAdmin>, levels: u8) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetGradeLevels::DISCRIMINATOR, &levels)?;
+    require!(ctx.accounts.config.timelock_delay == 0, ErrorCode::TimelockRequired);
+    apply_admin_action(&mut ctx.accounts.config, AdminAction::SetGradeLevels { levels }, ctx.accounts.admin.key())
+}
+
+pub fn queue_admin_action(ctx: Context<QueueAdminAction>, action: AdminAction) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::QueueAdminAction::DISCRIMINATOR, &action)?;
+    action.validate()?;
+    
+    let p = &mut ctx.accounts.pending_action;
+    p.action = action;
+    p.eta = Clock::get()?.unix_timestamp
+        .checked_add(ctx.accounts.config.timelock_delay)
+        .ok_or(ErrorCode::InvalidTimelockDelay)?;
+    p.queued_by = ctx.accounts.admin.key();
+    p.bump = ctx.bumps.pending_action;
+    
+    emit!(AdminActionQueued {
+        action,
+        eta: p.eta,
+        admin: p.queued_by,
+    });
+    
+    Ok(())
+}
+
+pub fn execute_admin_action(ctx: Context<ResolveAdminAction>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::ExecuteAdminAction::DISCRIMINATOR, &())?;
+    let p = &ctx.accounts.pending_action;
+    require!(Clock::get()?.unix_timestamp >= p.eta, ErrorCode::TimelockNotElapsed);
+    
+    let action = p.action;
+    if let AdminAction::BumpRulesetVersion { version } = action {
+        activate_ruleset(ctx.accounts.ruleset.as_deref_mut(), ctx.accounts.previous_ruleset.as_deref_mut(), version)?;
+    }
+    apply_admin_action(&mut ctx.accounts.config, action, ctx.accounts.admin.key())?;
+    
+    emit!(AdminActionExecuted {
+        action,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn cancel_admin_action(ctx: Context<ResolveAdminAction>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::CancelAdminAction::DISCRIMINATOR, &())?;
+    
+    emit!(AdminActionCancelled {
+        action: ctx.accounts.pending_action.action,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn propose_admin(ctx: Context<OnlyAdmin>, new_admin: Pubkey) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::ProposeAdmin::DISCRIMINATOR, &new_admin)?;
+    require!(new_admin != Pubkey::default(), ErrorCode::InvalidAdmin);
+    ctx.accounts.config.pending_admin = new_admin;
+    
+    emit!(AdminProposed {
+        current_admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+    });
+    
+    Ok(())
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    record_admin_action(&ctx.accounts.audit_log, &ctx.accounts.pending_admin.key(), instruction::AcceptAdmin::DISCRIMINATOR, &())?;
+    let cfg = &mut ctx.accounts.config;
+    let old_admin = cfg.admin;
+    cfg.admin = ctx.accounts.pending_admin.key();
+    cfg.pending_admin = Pubkey::default();
+    
+    emit!(AdminTransferred {
+        old_admin,
+        new_admin: cfg.admin,
+    });
+    
+    Ok(())
+}
+
+pub fn set_admin_set(ctx: Context<ConfigureAdminSet>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::SetAdminSet::DISCRIMINATOR, &(&members, threshold))?;
+    require!(!members.is_empty() && members.len() <= MAX_ADMIN_SET_MEMBERS, ErrorCode::InvalidAdminSet);
+    require!(threshold >= 1 && threshold as usize <= members.len(), ErrorCode::InvalidAdminSet);
+    for (i, member) in members.iter().enumerate() {
+        require!(*member != Pubkey::default(), ErrorCode::InvalidAdminSet);
+        require!(!members[..i].contains(member), ErrorCode::InvalidAdminSet);
+    }
+    
+    let set = &mut ctx.accounts.admin_set;
+    set.threshold = threshold;
+    set.members = members;
+    set.bump = ctx.bumps.admin_set;
+    ctx.accounts.config.multisig = true;
+    
+    emit!(AdminSetUpdated {
+        members: set.members.clone(),
+        threshold,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn clear_admin_set(ctx: Context<OnlyAdmin>) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::ClearAdminSet::DISCRIMINATOR, &())?;
+    ctx.accounts.config.multisig = false;
+    
+    emit!(AdminSetCleared {
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn transfer_upgrade_authority(ctx: Context<ManageUpgradeAuthority>) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.as_ref().ok_or(ErrorCode::NewUpgradeAuthorityRequired)?.key();
+    authorize_full_admin_action(&ctx, instruction::TransferUpgradeAuthority::DISCRIMINATOR, &new_authority)?;
+    set_upgrade_authority(ctx.accounts, ctx.bumps.upgrade_authority, Some(&new_authority))
+}
+
+pub fn burn_upgrade_authority(ctx: Context<ManageUpgradeAuthority>) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::BurnUpgradeAuthority::DISCRIMINATOR, &())?;
+    set_upgrade_authority(ctx.accounts, ctx.bumps.upgrade_authority, None)
+}
+
+pub fn pause(ctx: Context<OnlyAdmin>) -> Result<()> {
+    authorize_guardian_action(&ctx, Role::ConfigAdmin, instruction::Pause::DISCRIMINATOR, &())?;
+    ctx.accounts.config.paused = true;
+    
+    emit!(RegistryPaused {
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn unpause(ctx: Context<OnlyAdmin>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::Unpause::DISCRIMINATOR, &())?;
+    ctx.accounts.config.paused = false;
+    
+    emit!(RegistryUnpaused {
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, role: Role) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::GrantRole::DISCRIMINATOR, &role)?;
+    
+    let g = &mut ctx.accounts.role_grant;
+    g.role = role;
+    g.holder = ctx.accounts.holder.key();
+    g.granted_by = ctx.accounts.admin.key();
+    g.bump = ctx.bumps.role_grant;
+    
+    emit!(RoleGranted {
+        role,
+        holder: g.holder,
+        admin: g.granted_by,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::RevokeRole::DISCRIMINATOR, &())?;
+    
+    emit!(RoleRevoked {
+        role: ctx.accounts.role_grant.role,
+        holder: ctx.accounts.role_grant.holder,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_governance(ctx: Context<OnlyAdmin>, governance: Pubkey) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::SetGovernance::DISCRIMINATOR, &governance)?;
+    let old_governance = ctx.accounts.config.governance;
+    ctx.accounts.config.governance = governance;
+    
+    emit!(GovernanceUpdated {
+        old_governance,
+        new_governance: governance,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_guardian(ctx: Context<OnlyAdmin>, guardian: Pubkey) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::SetGuardian::DISCRIMINATOR, &guardian)?;
+    let old_guardian = ctx.accounts.config.guardian;
+    ctx.accounts.config.guardian = guardian;
+    
+    emit!(GuardianUpdated {
+        old_guardian,
+        new_guardian: guardian,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+/// CPI to the upgradeable loader, signed by the `upgrade_authority` PDA.
+fn set_upgrade_authority(accounts: &ManageUpgradeAuthority, bump: u8, new_authority: Option<&Pubkey>) -> Result<()> {
+    let authority = accounts.upgrade_authority.to_account_info();
+    let ix = bpf_loader_upgradeable::set_upgrade_authority(&crate::ID, authority.key, new_authority);
+    let mut infos = vec![accounts.program_data.to_account_info(), authority];
+    if let Some(new) = accounts.new_authority.as_ref().filter(|_| new_authority.is_some()) {
+        infos.push(new.to_account_info());
+    }
+    infos.push(accounts.bpf_loader.to_account_info());
+    invoke_signed(&ix, &infos, &[&[b"upgrade_authority", &[bump]]])?;
+    
+    emit!(UpgradeAuthorityChanged {
+        new_authority: new_authority.copied(),
+        admin: accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init, 
+        payer = payer, 
+        space = CONFIG_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Admin pubkey, doesn't need to be signer for init
+    pub admin: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitAuditLog<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(
+        init, 
+        seeds = [b"audit_log"], 
+        bump, 
+        payer = payer, 
+        space = AUDIT_LOG_SPACE
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OnlyAdmin<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureAdminSet<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"admin_set"], 
+        bump, 
+        payer = payer, 
+        space = 8 + 1 + 4 + 32 * MAX_ADMIN_SET_MEMBERS + 1 // discriminator + threshold + members + bump
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.pending_admin == pending_admin.key() @ ErrorCode::NotPendingAdmin
+    )]
+    pub config: Account<'info, Config>,
+    pub pending_admin: Signer<'info>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+#[instruction(action: AdminAction)]
+pub struct QueueAdminAction<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"pending_action".as_ref(), &[action.kind()]], 
+        bump, 
+        payer = payer, 
+        space = 8 + 1 + 8 + 8 + 32 + 1 // discriminator + action + eta + queued_by + bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveAdminAction<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        has_one = queued_by,
+        close = queued_by,
+        seeds = [b"pending_action".as_ref(), &[pending_action.action.kind()]], 
+        bump = pending_action.bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+    /// CHECK: receives the pending action rent
+    #[account(mut)]
+    pub queued_by: UncheckedAccount<'info>,
+    // Only for BumpRulesetVersion actions, see bump_ruleset_version
+    #[account(mut, seeds = [b"ruleset", &ruleset.version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        mut, 
+        seeds = [b"ruleset", &config.ruleset_version.to_le_bytes()], 
+        bump = previous_ruleset.bump
+    )]
+    pub previous_ruleset: Option<Account<'info, Ruleset>>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role)]
+pub struct GrantRole<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"role".as_ref(), &[role as u8], holder.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 1 + 32 + 32 + 1 // discriminator + role + holder + granted_by + bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+    /// CHECK: Role holder pubkey
+    pub holder: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        close = admin,
+        seeds = [b"role".as_ref(), &[role_grant.role as u8], role_grant.holder.as_ref()], 
+        bump = role_grant.bump
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+}
+
+#[derive(Accounts)]
+pub struct ManageUpgradeAuthority<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    /// CHECK: signs the loader CPI; holds no data
+    #[account(seeds = [b"upgrade_authority"], bump)]
+    pub upgrade_authority: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        seeds = [crate::ID.as_ref()], 
+        bump, 
+        seeds::program = bpf_loader_upgradeable::ID, 
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ ErrorCode::UpgradeAuthorityNotHeld
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    /// CHECK: any key; only needed by transfer_upgrade_authority
+    pub new_authority: Option<UncheckedAccount<'info>>,
+    /// CHECK: the upgradeable loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader: UncheckedAccount<'info>,
+}
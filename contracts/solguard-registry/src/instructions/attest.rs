@@ -0,0 +1,927 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::secp256k1_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::metadata::{Metadata, MetadataAccount};
+use anchor_spl::token_interface::Mint;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+#[allow(clippy::too_many_arguments)]
+pub fn attest_token(
+    ctx: Context<AttestToken>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32],
+    nonce: u64,
+    report: ReportPointer,
+    subscores: [u16; SUBSCORE_COUNT],
+    flags: u64
+) -> Result<()> {
+    let item = BatchAttestation { score, grade, proofs_hash, nonce, report, subscores, flags };
+    write_attestation(ctx.accounts, &ctx.bumps, ruleset_version, item, false)
+}
+
+pub fn commit_attestation(ctx: Context<CommitAttestation>, hash: [u8; 32]) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    require!(ctx.accounts.oracle.active, ErrorCode::OracleInactive);
+    
+    let c = &mut ctx.accounts.commitment;
+    c.oracle = ctx.accounts.signer.key();
+    c.hash = hash;
+    c.committed_slot = Clock::get()?.slot;
+    c.bump = ctx.bumps.commitment;
+    
+    emit!(AttestationCommitted {
+        oracle: c.oracle,
+        hash,
+        committed_slot: c.committed_slot,
+    });
+    
+    Ok(())
+}
+
+pub fn reveal_attestation(
+    ctx: Context<RevealAttestation>, 
+    ruleset_version: u16, 
+    item: BatchAttestation, 
+    salt: [u8; 32]
+) -> Result<()> {
+    let c = &ctx.accounts.commitment;
+    require!(Clock::get()?.slot > c.committed_slot, ErrorCode::RevealTooEarly);
+    let hash = commitment_hash(&ctx.accounts.attest.mint.key(), ruleset_version, &item, &salt)?;
+    require!(hash == c.hash, ErrorCode::CommitmentMismatch);
+    ctx.accounts.commitment.close(ctx.accounts.attest.signer.to_account_info())?;
+    
+    write_attestation(&mut ctx.accounts.attest, &ctx.bumps.attest, ruleset_version, item, false)
+}
+
+pub fn attest_with_signature(ctx: Context<AttestWithSignature>, payload: SignedAttestation) -> Result<()> {
+    let oracle_key = ctx.accounts.oracle_key.key();
+    let mut message = ATTEST_SIGNATURE_DOMAIN.to_vec();
+    message.extend(payload.try_to_vec()?);
+    verify_ed25519_signature(&ctx.accounts.instructions, &oracle_key, &message)?;
+    
+    apply_signed_attestation(ctx, oracle_key, payload)
+}
+
+pub fn attest_with_evm_signature(ctx: Context<AttestWithSignature>, payload: SignedAttestation) -> Result<()> {
+    let evm_address = ctx.accounts.oracle.evm_address;
+    require!(evm_address != [0; 20], ErrorCode::EvmKeyNotRegistered);
+    let mut signed = ATTEST_SIGNATURE_DOMAIN.to_vec();
+    signed.extend(payload.try_to_vec()?);
+    let mut message = format!("\x19Ethereum Signed Message:\n{}", signed.len()).into_bytes();
+    message.extend(signed);
+    verify_secp256k1_signature(&ctx.accounts.instructions, &evm_address, &message)?;
+    
+    let oracle_key = ctx.accounts.oracle_key.key();
+    apply_signed_attestation(ctx, oracle_key, payload)
+}
+
+pub fn attest_tokens_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, AttestTokensBatch<'info>>, 
+    ruleset_version: u16, 
+    items: Vec<BatchAttestation>
+) -> Result<()> {
+    require!(!items.is_empty() && items.len() <= MAX_BATCH_SIZE, ErrorCode::InvalidBatch);
+    require!(ctx.remaining_accounts.len() == items.len() * BATCH_ITEM_ACCOUNTS, ErrorCode::InvalidBatch);
+    require!(ctx.accounts.config.consensus_threshold <= 1, ErrorCode::ConsensusRequired);
+    
+    let signer = ctx.accounts.signer.key();
+    let payer_info = ctx.accounts.signer.to_account_info();
+    let system_info = ctx.accounts.system_program.to_account_info();
+    charge_attestation_fee(
+        &ctx.accounts.config, 
+        &mut ctx.accounts.fee, 
+        &payer_info, 
+        &system_info, 
+        items.len() as u64
+    )?;
+    let clock = Clock::get()?;
+    let version_bytes = ruleset_version.to_le_bytes();
+    
+    let stats = &mut ctx.accounts.oracle_stats;
+    if stats.oracle == Pubkey::default() {
+        stats.oracle = signer;
+        stats.bump = ctx.bumps.oracle_stats;
+    }
+    let registry_stats = &mut ctx.accounts.registry_stats;
+    registry_stats.bump = ctx.bumps.registry_stats;
+    registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    
+    for (item, accounts) in items.iter().zip(ctx.remaining_accounts.chunks(BATCH_ITEM_ACCOUNTS)) {
+        // Scoped oracles must use attest_token so their ScopeEntry can be checked
+        validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), item.score, item.grade)?;
+        item.report.validate()?;
+        validate_subscores(&item.subscores)?;
+        let mint_account = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let mint = mint_account.key();
+        require_not_blacklisted(&mint, &accounts[3])?;
+        claim_proofs_hash(&accounts[6], &payer_info, &system_info, mint, ruleset_version, item.proofs_hash, signer)?;
+        
+        create_pda_if_needed(
+            &accounts[1], 
+            &payer_info, 
+            &system_info, 
+            &[b"attest", mint.as_ref(), &version_bytes], 
+            ATTESTATION_SPACE
+        )?;
+        let loader = AccountLoader::<Attestation>::try_from_unchecked(&crate::ID, &accounts[1])?;
+        let mut a = load_or_init_mut(&loader)?;
+        let cfg = &ctx.accounts.config;
+        require!(
+            !cfg.requires_downgrade_cosign(a.live_score(), item.score, normalize_grade(item.grade, cfg.grade_scale())), 
+            ErrorCode::DowngradeRequiresCosign
+        );
+        a.consume_cooldown(cfg.attestation_cooldown, clock.unix_timestamp)?;
+        let previous = a.live_score();
+        let prior = a.prior_score();
+        a.advance_nonce(item.nonce)?;
+        a.mint = mint;
+        a.ruleset_version = ruleset_version;
+        a.score_bps = item.score;
+        a.grade = item.grade;
+        a.grade_levels = ctx.accounts.config.grade_scale();
+        a.version = ATTESTATION_LAYOUT_VERSION;
+        a.proofs_hash = item.proofs_hash;
+        a.set_report(item.report);
+        a.metadata_hash = [0; 32];
+        a.set_mint_snapshot(&mint_account, mint_risk_flags(&accounts[0])?, clock.slot);
+        a.require_extensions_allowed()?;
+        a.subscores = item.subscores;
+        a.flags = item.flags;
+        a.attested_by = signer;
+        if a.payer == Pubkey::default() {
+            a.payer = signer;
+        }
+        a.attested_at = clock.unix_timestamp;
+        a.clear_revocation();
+        a.expired = 0;
+        a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+        a.oracle_count = 1;
+        a.origin_chain = 0;
+        a.zk_verified = 0;
+        a.valid_until = ctx.accounts.config.expiry_from(a.attested_at);
+        registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+        if a.normalized_grade() <= GRADE_RED {
+            flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+        }
+        
+        let (mut history, history_bump) = load_or_init_pda::<AttestationHistory>(
+            &accounts[2], 
+            &payer_info, 
+            &system_info, 
+            &[b"history", mint.as_ref()], 
+            HISTORY_SPACE
+        )?;
+        history.mint = mint;
+        history.bump = history_bump;
+        history.push(HistoryEntry {
+            score_bps: item.score,
+            grade: a.normalized_grade(),
+            oracle: signer,
+            slot: clock.slot,
+        });
+        store_program_account(&accounts[2], &history)?;
+        
+        let (mut latest, latest_bump) = load_or_init_pda::<LatestAttestation>(
+            &accounts[4], 
+            &payer_info, 
+            &system_info, 
+            &[b"latest", mint.as_ref()], 
+            LATEST_ATTESTATION_SPACE
+        )?;
+        latest.bump = latest_bump;
+        if let Some(superseded) = record_latest_attestation(&mut latest, Some(&accounts[5]), &mut a, accounts[1].key())? {
+            emit_cpi!(superseded);
+        }
+        store_program_account(&accounts[4], &latest)?;
+        
+        let (mut entry, entry_bump) = load_or_init_pda::<GradeIndexEntry>(
+            &accounts[7], 
+            &payer_info, 
+            &system_info, 
+            &[b"grade_index_entry", mint.as_ref()], 
+            GRADE_INDEX_ENTRY_SPACE
+        )?;
+        let mut pages = GradeIndexPages {
+            from: Account::try_from(&accounts[8]).ok(),
+            to: Account::try_from(&accounts[9]).ok(),
+        };
+        index_attestation(&mut entry, entry_bump, &mut pages, &a, accounts[1].key(), &latest)?;
+        store_program_account(&accounts[7], &entry)?;
+        pages.exit(&crate::ID)?;
+        
+        stats.record_attestation(a.normalized_grade(), a.attested_at);
+        
+        emit_cpi!(TokenAttested {
+            mint,
+            ruleset_version,
+            score: item.score,
+            grade: item.grade,
+            attested_by: signer,
+            attested_at: a.attested_at,
+        });
+        emit_cpi!(TokenAttestedV2::new(&a, prior, ctx.accounts.oracle.weight as u64));
+        if let Some(changed) = ScoreChanged::new(&a, prior) {
+            emit_cpi!(changed);
+        }
+        
+        // Writes the discriminator of freshly created accounts
+        drop(a);
+        loader.exit(&crate::ID)?;
+    }
+    
+    Ok(())
+}
+
+pub fn set_attestation_cooldown(ctx: Context<OnlyAdmin>, cooldown: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetAttestationCooldown::DISCRIMINATOR, &cooldown)?;
+    require!(cooldown >= 0, ErrorCode::InvalidCooldown);
+    let old_cooldown = ctx.accounts.config.attestation_cooldown;
+    ctx.accounts.config.attestation_cooldown = cooldown;
+    
+    emit!(AttestationCooldownUpdated {
+        old_cooldown,
+        new_cooldown: cooldown,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn waive_attestation_cooldown(ctx: Context<Revoke>, ruleset_version: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::WaiveAttestationCooldown::DISCRIMINATOR, &ruleset_version)?;
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    a.cooldown_waived = 1;
+    
+    emit!(AttestationCooldownWaived {
+        mint: a.mint,
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn waive_grade_consistency(ctx: Context<WaiveGradeConsistency>, ruleset_version: u16) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::WaiveGradeConsistency::DISCRIMINATOR, &ruleset_version)?;
+    let w = &mut ctx.accounts.grade_waiver;
+    w.mint = ctx.accounts.mint.key();
+    w.ruleset_version = ruleset_version;
+    w.admin = ctx.accounts.admin.key();
+    w.payer = ctx.accounts.payer.key();
+    w.granted_at = Clock::get()?.unix_timestamp;
+    w.bump = ctx.bumps.grade_waiver;
+    
+    emit!(GradeConsistencyWaived {
+        mint: w.mint,
+        ruleset_version,
+        admin: w.admin,
+    });
+    
+    Ok(())
+}
+
+pub fn renew_attestation(ctx: Context<RenewAttestation>, proofs_hash: [u8; 32]) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    require!(!cfg.paused, ErrorCode::RegistryPaused);
+    require!(ctx.accounts.oracle.active, ErrorCode::OracleInactive);
+    
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_revoked(), ErrorCode::AttestationRevoked);
+    require!(a.ruleset_version == cfg.ruleset_version, ErrorCode::InvalidRulesetVersion);
+    require!(a.proofs_hash == proofs_hash, ErrorCode::ProofsHashMismatch);
+    
+    let previous_attested_at = a.attested_at;
+    a.attested_at = Clock::get()?.unix_timestamp;
+    a.valid_until = cfg.expiry_from(a.attested_at);
+    a.expired = 0;
+    
+    emit!(AttestationRenewed {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        oracle: ctx.accounts.signer.key(),
+        previous_attested_at,
+        attested_at: a.attested_at,
+        valid_until: a.valid_until,
+    });
+    
+    Ok(())
+}
+
+pub fn attach_category_proofs(ctx: Context<AttachCategoryProofs>, category_proofs: [[u8; 32]; SUBSCORE_COUNT]) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    require!(ctx.accounts.oracle.active, ErrorCode::OracleInactive);
+    
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_revoked(), ErrorCode::AttestationRevoked);
+    require!(a.proofs_hash == category_proofs_hash(&category_proofs), ErrorCode::ProofsHashMismatch);
+    a.category_proofs = category_proofs;
+    
+    emit!(CategoryProofsAttached {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        proofs_hash: a.proofs_hash,
+        category_proofs,
+        oracle: ctx.accounts.signer.key(),
+    });
+    
+    Ok(())
+}
+
+/// Validates and writes an attestation whose oracle signature has already been
+/// checked; shared by the relayed attestation paths.
+fn apply_signed_attestation(ctx: Context<AttestWithSignature>, oracle_key: Pubkey, payload: SignedAttestation) -> Result<()> {
+    let in_scope = ctx.accounts.scope_entry.is_some();
+    validate_oracle_submission(&ctx.accounts.config, &ctx.accounts.oracle, in_scope, payload.ruleset_version, ctx.accounts.ruleset.as_deref(), payload.score, payload.grade)?;
+    require_grade_in_band_or_waived(
+        &ctx.accounts.config, 
+        payload.score, 
+        payload.grade, 
+        ctx.accounts.grade_waiver.as_ref(), 
+        ctx.accounts.grade_waiver_payer.as_ref()
+    )?;
+    payload.report.validate()?;
+    validate_subscores(&payload.subscores)?;
+    require!(ctx.accounts.config.consensus_threshold <= 1, ErrorCode::ConsensusRequired);
+    charge_attestation_fee(
+        &ctx.accounts.config, 
+        &mut ctx.accounts.fee, 
+        &ctx.accounts.relayer.to_account_info(), 
+        &ctx.accounts.system_program.to_account_info(), 
+        1
+    )?;
+    claim_proofs_hash(
+        &ctx.accounts.proof_marker, 
+        &ctx.accounts.relayer.to_account_info(), 
+        &ctx.accounts.system_program.to_account_info(), 
+        payload.mint, 
+        payload.ruleset_version, 
+        payload.proofs_hash, 
+        oracle_key
+    )?;
+    
+    let mut a = load_or_init_mut(&ctx.accounts.attestation)?;
+    let cfg = &ctx.accounts.config;
+    require!(
+        !cfg.requires_downgrade_cosign(a.live_score(), payload.score, normalize_grade(payload.grade, cfg.grade_scale())), 
+        ErrorCode::DowngradeRequiresCosign
+    );
+    a.consume_cooldown(cfg.attestation_cooldown, Clock::get()?.unix_timestamp)?;
+    let previous = a.live_score();
+    let prior = a.prior_score();
+    a.advance_nonce(payload.nonce)?;
+    a.mint = payload.mint;
+    a.ruleset_version = payload.ruleset_version;
+    a.score_bps = payload.score;
+    a.grade = payload.grade;
+    a.grade_levels = ctx.accounts.config.grade_scale();
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.proofs_hash = payload.proofs_hash;
+    a.set_report(payload.report);
+    a.metadata_hash = [0; 32];
+    a.set_mint_snapshot(&ctx.accounts.mint, mint_risk_flags(&ctx.accounts.mint.to_account_info())?, Clock::get()?.slot);
+    a.require_extensions_allowed()?;
+    a.subscores = payload.subscores;
+    a.flags = payload.flags;
+    a.attested_by = oracle_key;
+    if a.payer == Pubkey::default() {
+        a.payer = ctx.accounts.relayer.key();
+    }
+    a.attested_at = Clock::get()?.unix_timestamp;
+    a.clear_revocation();
+    a.expired = 0;
+    a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+    a.oracle_count = 1;
+    a.origin_chain = 0;
+    a.zk_verified = 0;
+    a.valid_until = ctx.accounts.config.expiry_from(a.attested_at);
+    let registry_stats = &mut ctx.accounts.registry_stats;
+    registry_stats.bump = ctx.bumps.registry_stats;
+    registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    if a.normalized_grade() <= GRADE_RED {
+        flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    }
+    
+    let stats = &mut ctx.accounts.oracle_stats;
+    if stats.oracle == Pubkey::default() {
+        stats.oracle = oracle_key;
+        stats.bump = ctx.bumps.oracle_stats;
+    }
+    stats.record_attestation(a.normalized_grade(), a.attested_at);
+    
+    let history = &mut ctx.accounts.history;
+    history.mint = a.mint;
+    history.bump = ctx.bumps.history;
+    history.push(HistoryEntry {
+        score_bps: payload.score,
+        grade: a.normalized_grade(),
+        oracle: oracle_key,
+        slot: Clock::get()?.slot,
+    });
+    
+    ctx.accounts.latest.bump = ctx.bumps.latest;
+    if let Some(superseded) = record_latest_attestation(
+        &mut ctx.accounts.latest, 
+        ctx.accounts.superseded.as_deref(), 
+        &mut a, 
+        ctx.accounts.attestation.key()
+    )? {
+        emit_cpi!(superseded);
+    }
+    index_attestation(
+        &mut ctx.accounts.grade_index_entry, 
+        ctx.bumps.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        &a, 
+        ctx.accounts.attestation.key(), 
+        &ctx.accounts.latest
+    )?;
+    
+    emit_cpi!(TokenAttested {
+        mint: a.mint,
+        ruleset_version: payload.ruleset_version,
+        score: payload.score,
+        grade: payload.grade,
+        attested_by: oracle_key,
+        attested_at: a.attested_at,
+    });
+    emit_cpi!(TokenAttestedV2::new(&a, prior, ctx.accounts.oracle.weight as u64));
+    if let Some(changed) = ScoreChanged::new(&a, prior) {
+        emit_cpi!(changed);
+    }
+    
+    Ok(())
+}
+
+/// Checks that the instruction preceding the current one is an ed25519 program
+/// verification of exactly one signature by `signer` over `message`, with the key,
+/// signature and message all carried inline in that instruction.
+fn verify_ed25519_signature(instructions: &AccountInfo, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::InvalidOracleSignature);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::InvalidOracleSignature);
+    
+    // [count, padding, signature offset, signature ix, pubkey offset, pubkey ix,
+    //  message offset, message size, message ix], offsets as u16 LE
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidOracleSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let inline = [read_u16(4), read_u16(8), read_u16(14)].iter().all(|ix| *ix == u16::MAX);
+    require!(inline, ErrorCode::InvalidOracleSignature);
+    
+    let pubkey_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    require!(
+        data.get(pubkey_offset..pubkey_offset + 32) == Some(signer.as_ref()) 
+            && data.get(message_offset..message_offset + message_size) == Some(message), 
+        ErrorCode::InvalidOracleSignature
+    );
+    Ok(())
+}
+
+/// Checks that the instruction preceding the current one is a secp256k1 program
+/// verification of exactly one signature by `eth_address` over `message`, with
+/// all data carried inline in that instruction.
+fn verify_secp256k1_signature(instructions: &AccountInfo, eth_address: &[u8; 20], message: &[u8]) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::InvalidEvmSignature);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(ix.program_id, secp256k1_program::ID, ErrorCode::InvalidEvmSignature);
+    
+    // [count, signature offset, signature ix, address offset, address ix,
+    //  message offset, message size, message ix], offsets as u16 LE, indexes as u8
+    let data = &ix.data;
+    require!(data.len() >= 12 && data[0] == 1, ErrorCode::InvalidEvmSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let own_index = (current - 1) as u8;
+    require!(
+        data[3] == own_index && data[6] == own_index && data[11] == own_index, 
+        ErrorCode::InvalidEvmSignature
+    );
+    
+    let address_offset = read_u16(4);
+    let message_offset = read_u16(7);
+    let message_size = read_u16(9);
+    require!(
+        data.get(address_offset..address_offset + 20) == Some(&eth_address[..]) 
+            && data.get(message_offset..message_offset + message_size) == Some(message), 
+        ErrorCode::InvalidEvmSignature
+    );
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestToken<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    // SPL Token or Token-2022 mint, must be initialized
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    // Pins the mint's name, symbol, uri and update authority, see metadata_hash()
+    #[account(
+        seeds = [b"metadata", Metadata::id().as_ref(), mint.key().as_ref()], 
+        seeds::program = Metadata::id(), 
+        bump
+    )]
+    pub metadata: Option<Box<Account<'info, MetadataAccount>>>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"scope", signer.key().as_ref(), mint.key().as_ref()], 
+        bump = scope_entry.bump
+    )]
+    pub scope_entry: Option<Account<'info, ScopeEntry>>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = ATTESTATION_SPACE
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"history", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = HISTORY_SPACE
+    )]
+    pub history: Box<Account<'info, AttestationHistory>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"latest", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = LATEST_ATTESTATION_SPACE
+    )]
+    pub latest: Box<Account<'info, LatestAttestation>>,
+    /// CHECK: ProofMarker PDA for (ruleset_version, proofs_hash), created on first use in the handler
+    #[account(mut)]
+    pub proof_marker: UncheckedAccount<'info>,
+    // The mint's GradeIndexEntry, created on its first write
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_index_entry", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_INDEX_ENTRY_SPACE
+    )]
+    pub grade_index_entry: Box<Account<'info, GradeIndexEntry>>,
+    pub grade_index: GradeIndexPages<'info>,
+    /// CHECK: the attestation `latest` points at, loaded by record_latest_attestation;
+    /// required when this write moves the mint to a newer ruleset version
+    #[account(mut, address = latest.attestation @ ErrorCode::SupersededAttestationMismatch)]
+    pub superseded: Option<UncheckedAccount<'info>>,
+    // Pending waiver of the grade band check; consumed by this write
+    #[account(
+        mut, 
+        seeds = [b"grade_waiver", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = grade_waiver.bump
+    )]
+    pub grade_waiver: Option<Account<'info, GradeWaiver>>,
+    /// CHECK: GradeWaiver.payer, refunded the waiver's rent; required with grade_waiver
+    #[account(mut)]
+    pub grade_waiver_payer: Option<UncheckedAccount<'info>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this write grades the mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"oracle_stats", signer.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 32 + 8 + 8 + 8 + 8 * GRADE_LEVELS_EXTENDED as usize + 1 // discriminator + oracle + attestations + revocations + last attested + histogram + bump
+    )]
+    pub oracle_stats: Account<'info, OracleStats>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = payer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    pub fee: FeePayment<'info>,
+    // The oracle; writable only so reveal_attestation can refund its commitment
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    // Funds rent and the attestation fee, e.g. a relayer's treasury; may be the oracle itself
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(payload: SignedAttestation)]
+pub struct AttestWithSignature<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: oracle signing key, authenticated by the ed25519 instruction
+    pub oracle_key: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    // SPL Token or Token-2022 mint, must be initialized
+    #[account(address = payload.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"scope", oracle_key.key().as_ref(), mint.key().as_ref()], 
+        bump = scope_entry.bump
+    )]
+    pub scope_entry: Option<Account<'info, ScopeEntry>>,
+    #[account(seeds = [b"ruleset", &payload.ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"attest", mint.key().as_ref(), &payload.ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = relayer, 
+        space = ATTESTATION_SPACE
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"history", mint.key().as_ref()], 
+        bump, 
+        payer = relayer, 
+        space = HISTORY_SPACE
+    )]
+    pub history: Box<Account<'info, AttestationHistory>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"latest", mint.key().as_ref()], 
+        bump, 
+        payer = relayer, 
+        space = LATEST_ATTESTATION_SPACE
+    )]
+    pub latest: Box<Account<'info, LatestAttestation>>,
+    /// CHECK: the attestation `latest` points at, loaded by record_latest_attestation;
+    /// required when this write moves the mint to a newer ruleset version
+    #[account(mut, address = latest.attestation @ ErrorCode::SupersededAttestationMismatch)]
+    pub superseded: Option<UncheckedAccount<'info>>,
+    /// CHECK: ProofMarker PDA for (ruleset_version, proofs_hash), created on first use in the handler
+    #[account(mut)]
+    pub proof_marker: UncheckedAccount<'info>,
+    // The mint's GradeIndexEntry, created on its first write
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_index_entry", mint.key().as_ref()], 
+        bump, 
+        payer = relayer, 
+        space = GRADE_INDEX_ENTRY_SPACE
+    )]
+    pub grade_index_entry: Box<Account<'info, GradeIndexEntry>>,
+    pub grade_index: GradeIndexPages<'info>,
+    // Pending waiver of the grade band check; consumed by this write
+    #[account(
+        mut, 
+        seeds = [b"grade_waiver", mint.key().as_ref(), &payload.ruleset_version.to_le_bytes()], 
+        bump = grade_waiver.bump
+    )]
+    pub grade_waiver: Option<Account<'info, GradeWaiver>>,
+    /// CHECK: GradeWaiver.payer, refunded the waiver's rent; required with grade_waiver
+    #[account(mut)]
+    pub grade_waiver_payer: Option<UncheckedAccount<'info>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this write grades the mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"oracle_stats", oracle_key.key().as_ref()], 
+        bump, 
+        payer = relayer, 
+        space = 8 + 32 + 8 + 8 + 8 + 8 * GRADE_LEVELS_EXTENDED as usize + 1 // discriminator + oracle + attestations + revocations + last attested + histogram + bump
+    )]
+    pub oracle_stats: Account<'info, OracleStats>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = relayer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    pub fee: FeePayment<'info>,
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    /// CHECK: instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(hash: [u8; 32])]
+pub struct CommitAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(
+        init, 
+        seeds = [b"commitment", signer.key().as_ref(), hash.as_ref()], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 32 + 8 + 1 // discriminator + oracle + hash + slot + bump
+    )]
+    pub commitment: Account<'info, AttestationCommitment>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct RevealAttestation<'info> {
+    pub attest: AttestToken<'info>,
+    #[account(
+        mut, 
+        seeds = [b"commitment", attest.signer.key().as_ref(), commitment.hash.as_ref()], 
+        bump = commitment.bump
+    )]
+    pub commitment: Account<'info, AttestationCommitment>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestTokensBatch<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"oracle_stats", signer.key().as_ref()], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 8 + 8 + 8 + 8 * GRADE_LEVELS_EXTENDED as usize + 1 // discriminator + oracle + attestations + revocations + last attested + histogram + bump
+    )]
+    pub oracle_stats: Account<'info, OracleStats>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = signer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    /// CHECK: the RiskBloom PDA, flagged when an item grades its mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    pub fee: FeePayment<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct WaiveGradeConsistency<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    /// CHECK: only the key is recorded; the mint need not be attested yet
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init, 
+        seeds = [b"grade_waiver", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 32 + 2 + 32 + 32 + 8 + 1 // discriminator + mint + version + admin + payer + granted at + bump
+    )]
+    pub grade_waiver: Account<'info, GradeWaiver>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RenewAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: token mint
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump,
+        constraint = attestation.load()?.attested_by == signer.key() @ ErrorCode::NotAttester
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AttachCategoryProofs<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: token mint
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump,
+        constraint = attestation.load()?.attested_by == signer.key() @ ErrorCode::NotAttester
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    pub signer: Signer<'info>,
+}
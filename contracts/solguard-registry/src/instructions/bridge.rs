@@ -0,0 +1,355 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::token_interface::Mint;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn set_foreign_emitter(ctx: Context<SetForeignEmitter>, chain: u16, address: [u8; 32]) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetForeignEmitter::DISCRIMINATOR, &(chain, address))?;
+    require!(chain != wormhole::CHAIN_ID_SOLANA, ErrorCode::InvalidForeignEmitter);
+    
+    let e = &mut ctx.accounts.foreign_emitter;
+    e.chain = chain;
+    e.address = address;
+    e.bump = ctx.bumps.foreign_emitter;
+    
+    emit!(ForeignEmitterUpdated {
+        chain,
+        address,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn receive_foreign_attestation(ctx: Context<ReceiveForeignAttestation>, _vaa_hash: [u8; 32]) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    let vaa = &ctx.accounts.posted_vaa;
+    let msg = vaa.data();
+    require!(ctx.accounts.config.is_active_version(msg.ruleset_version), ErrorCode::InvalidRulesetVersion);
+    require!(msg.grade < GRADE_LEVELS_EXTENDED, ErrorCode::InvalidGrade);
+    require!(msg.score <= 10000, ErrorCode::InvalidScore);
+    claim_proofs_hash(
+        &ctx.accounts.proof_marker, 
+        &ctx.accounts.payer.to_account_info(), 
+        &ctx.accounts.system_program.to_account_info(), 
+        msg.mint, 
+        msg.ruleset_version, 
+        msg.proofs_hash, 
+        ctx.accounts.foreign_emitter.key()
+    )?;
+    
+    let mut a = load_or_init_mut(&ctx.accounts.attestation)?;
+    // A mirror carries none of a local attestation's flags, subscores or category proofs
+    require!(a.mint == Pubkey::default() || a.origin_chain != 0, ErrorCode::LocalAttestationExists);
+    require!(msg.attested_at > a.attested_at, ErrorCode::StaleForeignAttestation);
+    let previous = a.live_score();
+    a.nonce += 1;
+    a.mint = msg.mint;
+    a.ruleset_version = msg.ruleset_version;
+    a.score_bps = msg.score;
+    a.grade = msg.grade;
+    a.grade_levels = GRADE_LEVELS_EXTENDED;
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.proofs_hash = msg.proofs_hash;
+    a.set_report(ReportPointer::default());
+    a.metadata_hash = [0; 32];
+    a.set_mint_snapshot(&ctx.accounts.mint, mint_risk_flags(&ctx.accounts.mint.to_account_info())?, Clock::get()?.slot);
+    a.require_extensions_allowed()?;
+    a.subscores = [0; SUBSCORE_COUNT];
+    a.flags = 0;
+    a.attested_by = ctx.accounts.foreign_emitter.key();
+    if a.payer == Pubkey::default() {
+        a.payer = ctx.accounts.payer.key();
+    }
+    a.attested_at = msg.attested_at;
+    a.clear_revocation();
+    a.expired = 0;
+    a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+    a.oracle_count = 1;
+    a.origin_chain = vaa.emitter_chain();
+    a.zk_verified = 0;
+    a.valid_until = ctx.accounts.config.expiry_from(a.attested_at);
+    let registry_stats = &mut ctx.accounts.registry_stats;
+    registry_stats.bump = ctx.bumps.registry_stats;
+    registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    if a.normalized_grade() <= GRADE_RED {
+        flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    }
+    
+    let r = &mut ctx.accounts.received;
+    r.bump = ctx.bumps.received;
+    
+    let history = &mut ctx.accounts.history;
+    history.mint = a.mint;
+    history.bump = ctx.bumps.history;
+    history.push(HistoryEntry {
+        score_bps: a.score_bps,
+        grade: a.grade,
+        oracle: a.attested_by,
+        slot: Clock::get()?.slot,
+    });
+    
+    ctx.accounts.latest.bump = ctx.bumps.latest;
+    if let Some(superseded) = record_latest_attestation(
+        &mut ctx.accounts.latest, 
+        ctx.accounts.superseded.as_deref(), 
+        &mut a, 
+        ctx.accounts.attestation.key()
+    )? {
+        emit!(superseded);
+    }
+    index_attestation(
+        &mut ctx.accounts.grade_index_entry, 
+        ctx.bumps.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        &a, 
+        ctx.accounts.attestation.key(), 
+        &ctx.accounts.latest
+    )?;
+    
+    emit!(ForeignAttestationReceived {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        score: a.score_bps,
+        grade: a.grade,
+        origin_chain: a.origin_chain,
+        sequence: vaa.sequence(),
+    });
+    
+    Ok(())
+}
+
+pub fn publish_attestation(ctx: Context<PublishAttestation>, _ruleset_version: u16, batch_id: u32) -> Result<()> {
+    let a = ctx.accounts.attestation.load()?;
+    a.require_valid(Clock::get()?.unix_timestamp)?;
+    
+    let fee = ctx.accounts.wormhole_bridge.fee();
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(), 
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                }
+            ), 
+            fee
+        )?;
+    }
+    
+    let emitter_seeds: &[&[u8]] = &[WORMHOLE_EMITTER_SEED, &[ctx.bumps.wormhole_emitter]];
+    wormhole::post_message(
+        CpiContext::new_with_signer(
+            ctx.accounts.wormhole_program.to_account_info(), 
+            wormhole::PostMessage {
+                config: ctx.accounts.wormhole_bridge.to_account_info(),
+                message: ctx.accounts.wormhole_message.to_account_info(),
+                emitter: ctx.accounts.wormhole_emitter.to_account_info(),
+                sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+                payer: ctx.accounts.payer.to_account_info(),
+                fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                clock: ctx.accounts.clock.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            }, 
+            &[emitter_seeds]
+        ), 
+        batch_id, 
+        a.wormhole_payload(), 
+        wormhole::Finality::Finalized
+    )?;
+    
+    emit!(AttestationPublished {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        nonce: a.nonce,
+        wormhole_message: ctx.accounts.wormhole_message.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct SetForeignEmitter<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"foreign_emitter", &chain.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 2 + 32 + 1 // discriminator + chain + address + bump
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ReceiveForeignAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    #[account(
+        seeds = [wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash], 
+        bump, 
+        seeds::program = wormhole_program
+    )]
+    pub posted_vaa: Box<Account<'info, wormhole::PostedVaa<ForeignAttestationMessage>>>,
+    #[account(
+        seeds = [b"foreign_emitter", &posted_vaa.emitter_chain().to_le_bytes()], 
+        bump = foreign_emitter.bump, 
+        constraint = foreign_emitter.address == *posted_vaa.emitter_address() @ ErrorCode::InvalidForeignEmitter
+    )]
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+    #[account(
+        init, 
+        seeds = [b"received", &posted_vaa.emitter_chain().to_le_bytes(), &posted_vaa.sequence().to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 1 // discriminator + bump
+    )]
+    pub received: Account<'info, ReceivedVaa>,
+    // Token mint named in the VAA, must be initialized
+    #[account(address = posted_vaa.data().mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"attest", mint.key().as_ref(), &posted_vaa.data().ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = ATTESTATION_SPACE
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"history", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = HISTORY_SPACE
+    )]
+    pub history: Box<Account<'info, AttestationHistory>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"latest", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = LATEST_ATTESTATION_SPACE
+    )]
+    pub latest: Box<Account<'info, LatestAttestation>>,
+    /// CHECK: the attestation `latest` points at, loaded by record_latest_attestation;
+    /// required when this write moves the mint to a newer ruleset version
+    #[account(mut, address = latest.attestation @ ErrorCode::SupersededAttestationMismatch)]
+    pub superseded: Option<UncheckedAccount<'info>>,
+    /// CHECK: ProofMarker PDA for (ruleset_version, proofs_hash), created on first use in the handler
+    #[account(mut)]
+    pub proof_marker: UncheckedAccount<'info>,
+    // The mint's GradeIndexEntry, created on its first write
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_index_entry", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_INDEX_ENTRY_SPACE
+    )]
+    pub grade_index_entry: Box<Account<'info, GradeIndexEntry>>,
+    pub grade_index: GradeIndexPages<'info>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this write grades the mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = payer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct PublishAttestation<'info> {
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    #[account(
+        mut, 
+        seeds = [wormhole::BridgeData::SEED_PREFIX], 
+        bump, 
+        seeds::program = wormhole_program
+    )]
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+    #[account(
+        mut, 
+        seeds = [wormhole::FeeCollector::SEED_PREFIX], 
+        bump, 
+        seeds::program = wormhole_program
+    )]
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+    /// CHECK: registry emitter PDA, signs the posted message
+    #[account(seeds = [WORMHOLE_EMITTER_SEED], bump)]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+    /// CHECK: emitter sequence tracker, created by Wormhole on the first post
+    #[account(
+        mut, 
+        seeds = [wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter.key().as_ref()], 
+        bump, 
+        seeds::program = wormhole_program
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+    /// Fresh keypair per message
+    #[account(mut)]
+    pub wormhole_message: Signer<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
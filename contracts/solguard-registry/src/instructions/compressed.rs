@@ -0,0 +1,486 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::keccak;
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use anchor_spl::token_interface::Mint;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn post_attestation_root(
+    ctx: Context<PostAttestationRoot>, 
+    ruleset_version: u16, 
+    root: [u8; 32], 
+    leaf_count: u32
+) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    let oracle = &ctx.accounts.oracle;
+    require!(!cfg.paused, ErrorCode::RegistryPaused);
+    require!(oracle.active, ErrorCode::OracleInactive);
+    // Scope can't be checked per leaf at post time
+    require!(!oracle.scoped, ErrorCode::MintOutOfScope);
+    require!(ruleset_version == cfg.ruleset_version, ErrorCode::InvalidRulesetVersion);
+    require!(cfg.consensus_threshold <= 1, ErrorCode::ConsensusRequired);
+    require!(leaf_count > 0, ErrorCode::InvalidBatch);
+    
+    let r = &mut ctx.accounts.attestation_root;
+    r.oracle = ctx.accounts.signer.key();
+    r.ruleset_version = ruleset_version;
+    r.root = root;
+    r.leaf_count = leaf_count;
+    r.posted_at = Clock::get()?.unix_timestamp;
+    r.bump = ctx.bumps.attestation_root;
+    
+    emit!(AttestationRootPosted {
+        oracle: r.oracle,
+        ruleset_version,
+        root,
+        leaf_count,
+        posted_at: r.posted_at,
+    });
+    
+    Ok(())
+}
+
+pub fn materialize_attestation(ctx: Context<MaterializeAttestation>, leaf: RootLeaf, proof: Vec<[u8; 32]>) -> Result<()> {
+    let r = &ctx.accounts.attestation_root;
+    require!(proof.len() <= MAX_MERKLE_DEPTH, ErrorCode::InvalidMerkleProof);
+    require!(verify_merkle_proof(&leaf.hash()?, &proof, &r.root), ErrorCode::InvalidMerkleProof);
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, r.ruleset_version, None, leaf.score, leaf.grade)?;
+    validate_subscores(&leaf.subscores)?;
+    claim_proofs_hash(
+        &ctx.accounts.proof_marker, 
+        &ctx.accounts.payer.to_account_info(), 
+        &ctx.accounts.system_program.to_account_info(), 
+        leaf.mint, 
+        r.ruleset_version, 
+        leaf.proofs_hash, 
+        r.oracle
+    )?;
+    
+    let mut a = load_or_init_mut(&ctx.accounts.attestation)?;
+    // A root only vouches as of its posting time
+    require!(r.posted_at > a.attested_at, ErrorCode::StaleRootLeaf);
+    let cfg = &ctx.accounts.config;
+    require!(
+        !cfg.requires_downgrade_cosign(a.live_score(), leaf.score, normalize_grade(leaf.grade, cfg.grade_scale())), 
+        ErrorCode::DowngradeRequiresCosign
+    );
+    a.consume_cooldown(cfg.attestation_cooldown, r.posted_at)?;
+    let previous = a.live_score();
+    let prior = a.prior_score();
+    a.nonce += 1;
+    a.mint = leaf.mint;
+    a.ruleset_version = r.ruleset_version;
+    a.score_bps = leaf.score;
+    a.grade = leaf.grade;
+    a.grade_levels = ctx.accounts.config.grade_scale();
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.proofs_hash = leaf.proofs_hash;
+    a.set_report(ReportPointer::default());
+    a.metadata_hash = [0; 32];
+    a.set_mint_snapshot(&ctx.accounts.mint, mint_risk_flags(&ctx.accounts.mint.to_account_info())?, Clock::get()?.slot);
+    a.require_extensions_allowed()?;
+    a.subscores = leaf.subscores;
+    a.flags = 0; // compressed leaves carry no flags
+    a.attested_by = r.oracle;
+    if a.payer == Pubkey::default() {
+        a.payer = ctx.accounts.payer.key();
+    }
+    a.attested_at = r.posted_at;
+    a.clear_revocation();
+    a.expired = 0;
+    a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+    a.oracle_count = 1;
+    a.origin_chain = 0;
+    a.zk_verified = 0;
+    a.valid_until = ctx.accounts.config.expiry_from(a.attested_at);
+    let registry_stats = &mut ctx.accounts.registry_stats;
+    registry_stats.bump = ctx.bumps.registry_stats;
+    registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    if a.normalized_grade() <= GRADE_RED {
+        flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    }
+    
+    let history = &mut ctx.accounts.history;
+    history.mint = a.mint;
+    history.bump = ctx.bumps.history;
+    history.push(HistoryEntry {
+        score_bps: a.score_bps,
+        grade: a.normalized_grade(),
+        oracle: a.attested_by,
+        slot: Clock::get()?.slot,
+    });
+    
+    ctx.accounts.latest.bump = ctx.bumps.latest;
+    if let Some(superseded) = record_latest_attestation(
+        &mut ctx.accounts.latest, 
+        ctx.accounts.superseded.as_deref(), 
+        &mut a, 
+        ctx.accounts.attestation.key()
+    )? {
+        emit_cpi!(superseded);
+    }
+    index_attestation(
+        &mut ctx.accounts.grade_index_entry, 
+        ctx.bumps.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        &a, 
+        ctx.accounts.attestation.key(), 
+        &ctx.accounts.latest
+    )?;
+    
+    emit_cpi!(TokenAttested {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        score: a.score_bps,
+        grade: a.grade,
+        attested_by: a.attested_by,
+        attested_at: a.attested_at,
+    });
+    emit_cpi!(TokenAttestedV2::new(&a, prior, ctx.accounts.oracle.weight as u64));
+    if let Some(changed) = ScoreChanged::new(&a, prior) {
+        emit_cpi!(changed);
+    }
+    
+    Ok(())
+}
+
+pub fn init_compressed_tree(ctx: Context<InitCompressedTree>, max_depth: u32, max_buffer_size: u32) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::InitCompressedTree::DISCRIMINATOR, &(max_depth, max_buffer_size))?;
+    
+    let t = &mut ctx.accounts.tree_config;
+    t.merkle_tree = ctx.accounts.merkle_tree.key();
+    t.leaf_count = 0;
+    t.bump = ctx.bumps.tree_config;
+    
+    let merkle_tree = t.merkle_tree;
+    let signer_seeds: &[&[u8]] = &[b"compressed_tree", merkle_tree.as_ref(), &[t.bump]];
+    spl_account_compression::cpi::init_empty_merkle_tree(
+        CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(), 
+            spl_account_compression::cpi::accounts::Initialize {
+                authority: ctx.accounts.tree_config.to_account_info(),
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                noop: ctx.accounts.noop_program.to_account_info(),
+            }, 
+            &[signer_seeds]
+        ), 
+        max_depth, 
+        max_buffer_size
+    )?;
+    
+    emit!(CompressedTreeCreated {
+        merkle_tree,
+        max_depth,
+        max_buffer_size,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn append_compressed_attestation(
+    ctx: Context<ModifyCompressedTree>, 
+    mint: Pubkey, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32]
+) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    // No ScopeEntry lookup per leaf, as with batches
+    validate_submission(cfg, &ctx.accounts.oracle, false, cfg.ruleset_version, None, score, grade)?;
+    require!(cfg.consensus_threshold <= 1, ErrorCode::ConsensusRequired);
+    let blacklist = ctx.remaining_accounts.first().ok_or(ErrorCode::InvalidRemainingAccount)?;
+    require_not_blacklisted(&mint, blacklist)?;
+    
+    let leaf = CompressedAttestation {
+        mint,
+        ruleset_version: cfg.ruleset_version,
+        score_bps: score,
+        grade,
+        grade_levels: cfg.grade_scale(),
+        proofs_hash,
+        attested_by: ctx.accounts.signer.key(),
+        attested_at: Clock::get()?.unix_timestamp,
+        revoked: false,
+    };
+    let leaf_index = ctx.accounts.tree_config.leaf_count;
+    let leaf_hash = ctx.accounts.log_leaf(&leaf)?;
+    
+    let merkle_tree = ctx.accounts.tree_config.merkle_tree;
+    let signer_seeds: &[&[u8]] = &[b"compressed_tree", merkle_tree.as_ref(), &[ctx.accounts.tree_config.bump]];
+    spl_account_compression::cpi::append(
+        CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(), 
+            ctx.accounts.modify_accounts(), 
+            &[signer_seeds]
+        ), 
+        leaf_hash
+    )?;
+    ctx.accounts.tree_config.leaf_count += 1;
+    
+    emit!(CompressedAttestationWritten {
+        merkle_tree,
+        leaf_index,
+        leaf,
+    });
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn replace_compressed_attestation<'info>(
+    ctx: Context<'_, '_, '_, 'info, ModifyCompressedTree<'info>>, 
+    root: [u8; 32], 
+    leaf_index: u32, 
+    previous: CompressedAttestation, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32], 
+    revoked: bool
+) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    require_keys_eq!(previous.attested_by, ctx.accounts.signer.key(), ErrorCode::NotOriginalAttester);
+    validate_submission(cfg, &ctx.accounts.oracle, false, previous.ruleset_version, None, score, grade)?;
+    
+    let previous_hash = previous.hash()?;
+    let leaf = CompressedAttestation {
+        score_bps: score,
+        grade,
+        grade_levels: cfg.grade_scale(),
+        proofs_hash,
+        attested_at: Clock::get()?.unix_timestamp,
+        revoked,
+        ..previous
+    };
+    let leaf_hash = ctx.accounts.log_leaf(&leaf)?;
+    
+    let merkle_tree = ctx.accounts.tree_config.merkle_tree;
+    let signer_seeds: &[&[u8]] = &[b"compressed_tree", merkle_tree.as_ref(), &[ctx.accounts.tree_config.bump]];
+    spl_account_compression::cpi::replace_leaf(
+        CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(), 
+            ctx.accounts.modify_accounts(), 
+            &[signer_seeds]
+        ).with_remaining_accounts(ctx.remaining_accounts.to_vec()), 
+        root, 
+        previous_hash, 
+        leaf_hash, 
+        leaf_index
+    )?;
+    
+    emit!(CompressedAttestationWritten {
+        merkle_tree,
+        leaf_index: leaf_index as u64,
+        leaf,
+    });
+    
+    Ok(())
+}
+
+/// Sorted-pair merkle inclusion check, see `post_attestation_root`.
+fn verify_merkle_proof(leaf: &[u8; 32], proof: &[[u8; 32]], root: &[u8; 32]) -> bool {
+    let node = proof.iter().fold(*leaf, |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        keccak::hashv(&[&[1u8], &left, &right]).to_bytes()
+    });
+    node == *root
+}
+
+#[derive(Accounts)]
+pub struct InitCompressedTree<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"compressed_tree", merkle_tree.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 32 + 8 + 1 // discriminator + tree + leaf count + bump
+    )]
+    pub tree_config: Account<'info, TreeConfig>,
+    /// CHECK: allocated by the client, initialized by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub noop_program: Program<'info, Noop>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModifyCompressedTree<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(
+        mut, 
+        seeds = [b"compressed_tree", merkle_tree.key().as_ref()], 
+        bump = tree_config.bump
+    )]
+    pub tree_config: Account<'info, TreeConfig>,
+    /// CHECK: validated by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub signer: Signer<'info>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub noop_program: Program<'info, Noop>,
+}
+
+impl<'info> ModifyCompressedTree<'info> {
+    pub(crate) fn modify_accounts(&self) -> spl_account_compression::cpi::accounts::Modify<'info> {
+        spl_account_compression::cpi::accounts::Modify {
+            authority: self.tree_config.to_account_info(),
+            merkle_tree: self.merkle_tree.to_account_info(),
+            noop: self.noop_program.to_account_info(),
+        }
+    }
+
+    /// Logs the full leaf for indexers and returns its hash.
+    pub(crate) fn log_leaf(&self, leaf: &CompressedAttestation) -> Result<[u8; 32]> {
+        spl_account_compression::wrap_application_data_v1(leaf.try_to_vec()?, &self.noop_program.to_account_info())?;
+        leaf.hash()
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16, root: [u8; 32])]
+pub struct PostAttestationRoot<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(
+        init, 
+        seeds = [b"attestation_root", signer.key().as_ref(), &root], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 2 + 32 + 4 + 8 + 1 // discriminator + oracle + version + root + leaf count + posted + bump
+    )]
+    pub attestation_root: Account<'info, AttestationRoot>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(leaf: RootLeaf)]
+pub struct MaterializeAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"attestation_root", attestation_root.oracle.as_ref(), &attestation_root.root], 
+        bump = attestation_root.bump
+    )]
+    pub attestation_root: Account<'info, AttestationRoot>,
+    #[account(
+        seeds = [b"oracle", attestation_root.oracle.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    // Token mint named in the leaf, must be initialized
+    #[account(address = leaf.mint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"attest", mint.key().as_ref(), &attestation_root.ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = ATTESTATION_SPACE
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"history", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = HISTORY_SPACE
+    )]
+    pub history: Box<Account<'info, AttestationHistory>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"latest", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = LATEST_ATTESTATION_SPACE
+    )]
+    pub latest: Box<Account<'info, LatestAttestation>>,
+    /// CHECK: the attestation `latest` points at, loaded by record_latest_attestation;
+    /// required when this write moves the mint to a newer ruleset version
+    #[account(mut, address = latest.attestation @ ErrorCode::SupersededAttestationMismatch)]
+    pub superseded: Option<UncheckedAccount<'info>>,
+    /// CHECK: ProofMarker PDA for (ruleset_version, proofs_hash), created on first use in the handler
+    #[account(mut)]
+    pub proof_marker: UncheckedAccount<'info>,
+    // The mint's GradeIndexEntry, created on its first write
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_index_entry", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_INDEX_ENTRY_SPACE
+    )]
+    pub grade_index_entry: Box<Account<'info, GradeIndexEntry>>,
+    pub grade_index: GradeIndexPages<'info>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this write grades the mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = payer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,384 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn set_consensus_params(
+    ctx: Context<OnlyAdmin>, 
+    threshold: u8, 
+    score_tolerance_bps: u16, 
+    min_weight: u32
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetConsensusParams::DISCRIMINATOR, &(threshold, score_tolerance_bps, min_weight))?;
+    require!(score_tolerance_bps <= 10000, ErrorCode::InvalidScore);
+    let cfg = &mut ctx.accounts.config;
+    cfg.consensus_threshold = threshold;
+    cfg.score_tolerance_bps = score_tolerance_bps;
+    cfg.consensus_min_weight = min_weight;
+    
+    emit!(ConsensusParamsUpdated {
+        threshold,
+        score_tolerance_bps,
+        min_weight,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn submit_attestation(
+    ctx: Context<SubmitAttestation>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32],
+    report: ReportPointer,
+    subscores: [u16; SUBSCORE_COUNT],
+    flags: u64
+) -> Result<()> {
+    let in_scope = ctx.accounts.scope_entry.is_some();
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, in_scope, ruleset_version, None, score, grade)?;
+    report.validate()?;
+    validate_subscores(&subscores)?;
+    
+    let s = &mut ctx.accounts.submission;
+    s.mint = ctx.accounts.mint.key();
+    s.ruleset_version = ruleset_version;
+    s.oracle = ctx.accounts.signer.key();
+    s.score_bps = score;
+    s.grade = grade;
+    s.proofs_hash = proofs_hash;
+    s.report = report;
+    s.subscores = subscores;
+    s.flags = flags;
+    s.submitted_at = Clock::get()?.unix_timestamp;
+    s.bump = ctx.bumps.submission;
+    
+    let stats = &mut ctx.accounts.oracle_stats;
+    if stats.oracle == Pubkey::default() {
+        stats.oracle = s.oracle;
+        stats.bump = ctx.bumps.oracle_stats;
+    }
+    stats.record_attestation(normalize_grade(grade, ctx.accounts.config.grade_scale()), s.submitted_at);
+    
+    emit!(AttestationSubmitted {
+        mint: s.mint,
+        ruleset_version,
+        oracle: s.oracle,
+        score,
+        grade,
+        proofs_hash,
+    });
+    
+    Ok(())
+}
+
+pub fn finalize_consensus(ctx: Context<FinalizeConsensus>, ruleset_version: u16) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    require!(!cfg.paused, ErrorCode::RegistryPaused);
+    require!(cfg.consensus_threshold > 1, ErrorCode::ConsensusDisabled);
+    require!(ruleset_version == cfg.ruleset_version, ErrorCode::InvalidRulesetVersion);
+    require!(ctx.remaining_accounts.len() % 2 == 0, ErrorCode::InvalidSubmission);
+    
+    let mint = ctx.accounts.mint.key();
+    let mut a = load_or_init_mut(&ctx.accounts.attestation)?;
+    a.consume_cooldown(cfg.attestation_cooldown, Clock::get()?.unix_timestamp)?;
+    let previous_at = a.attested_at;
+    let previous = a.live_score();
+    let prior = a.prior_score();
+    let mut oracles: Vec<Pubkey> = Vec::new();
+    let mut agreed: Option<(u8, [u8; 32], ReportPointer)> = None;
+    let (mut min_score, mut max_score) = (u16::MAX, 0u16);
+    let (mut weighted_sum, mut total_weight) = (0u64, 0u64);
+    let mut weighted_subscores = [0u64; SUBSCORE_COUNT];
+    let mut flags = 0u64;
+    
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let sub: Submission = load_program_account(&pair[0])?;
+        let oracle: Oracle = load_program_account(&pair[1])?;
+        let oracle_pda = Pubkey::create_program_address(
+            &[b"oracle", sub.oracle.as_ref(), &[oracle.bump]], 
+            &crate::ID
+        ).map_err(|_| ErrorCode::InvalidSubmission)?;
+        require_keys_eq!(oracle_pda, pair[1].key(), ErrorCode::InvalidSubmission);
+        require!(oracle.active, ErrorCode::OracleInactive);
+        require!(sub.mint == mint && sub.ruleset_version == ruleset_version, ErrorCode::InvalidSubmission);
+        require!(sub.submitted_at > previous_at, ErrorCode::StaleSubmission);
+        require!(!oracles.contains(&sub.oracle), ErrorCode::DuplicateSubmission);
+        
+        match agreed {
+            Some((grade, hash, _)) => require!(sub.grade == grade && sub.proofs_hash == hash, ErrorCode::SubmissionMismatch),
+            None => agreed = Some((sub.grade, sub.proofs_hash, sub.report)),
+        }
+        min_score = min_score.min(sub.score_bps);
+        max_score = max_score.max(sub.score_bps);
+        weighted_sum += sub.score_bps as u64 * oracle.weight as u64;
+        for (sum, subscore) in weighted_subscores.iter_mut().zip(sub.subscores) {
+            *sum += subscore as u64 * oracle.weight as u64;
+        }
+        flags |= sub.flags;
+        total_weight += oracle.weight as u64;
+        oracles.push(sub.oracle);
+    }
+    
+    require!(oracles.len() >= cfg.consensus_threshold as usize, ErrorCode::InsufficientConsensus);
+    require!(total_weight >= cfg.consensus_min_weight as u64, ErrorCode::InsufficientConsensus);
+    require!(max_score - min_score <= cfg.score_tolerance_bps, ErrorCode::SubmissionMismatch);
+    let (grade, proofs_hash, report) = agreed.ok_or(ErrorCode::InsufficientConsensus)?;
+    claim_proofs_hash(
+        &ctx.accounts.proof_marker, 
+        &ctx.accounts.payer.to_account_info(), 
+        &ctx.accounts.system_program.to_account_info(), 
+        mint, 
+        ruleset_version, 
+        proofs_hash, 
+        oracles[0]
+    )?;
+    // Senior oracles pull the aggregate toward their score
+    let score = (weighted_sum / total_weight.max(1)) as u16;
+    let subscores = weighted_subscores.map(|sum| (sum / total_weight.max(1)) as u16);
+    // Each submission was band-checked on its own; the weighted score may still
+    // land outside the band of the grade they agreed on
+    require_grade_in_band_or_waived(
+        cfg, 
+        score, 
+        grade, 
+        ctx.accounts.grade_waiver.as_ref(), 
+        ctx.accounts.grade_waiver_payer.as_ref()
+    )?;
+    require!(
+        !cfg.requires_downgrade_cosign(previous, score, normalize_grade(grade, cfg.grade_scale())), 
+        ErrorCode::DowngradeRequiresCosign
+    );
+    
+    a.nonce += 1;
+    a.mint = mint;
+    a.ruleset_version = ruleset_version;
+    a.score_bps = score;
+    a.grade = grade;
+    a.grade_levels = cfg.grade_scale();
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.proofs_hash = proofs_hash;
+    a.set_report(report);
+    a.metadata_hash = [0; 32];
+    a.set_mint_snapshot(&ctx.accounts.mint, mint_risk_flags(&ctx.accounts.mint.to_account_info())?, Clock::get()?.slot);
+    a.require_extensions_allowed()?;
+    a.subscores = subscores;
+    a.flags = flags;
+    a.attested_by = oracles[0];
+    if a.payer == Pubkey::default() {
+        a.payer = ctx.accounts.payer.key();
+    }
+    a.attested_at = Clock::get()?.unix_timestamp;
+    a.clear_revocation();
+    a.expired = 0;
+    a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+    a.oracle_count = oracles.len() as u8;
+    a.origin_chain = 0;
+    a.zk_verified = 0;
+    a.valid_until = cfg.expiry_from(a.attested_at);
+    let registry_stats = &mut ctx.accounts.registry_stats;
+    registry_stats.bump = ctx.bumps.registry_stats;
+    registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    if a.normalized_grade() <= GRADE_RED {
+        flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    }
+    
+    let history = &mut ctx.accounts.history;
+    history.mint = mint;
+    history.bump = ctx.bumps.history;
+    history.push(HistoryEntry {
+        score_bps: score,
+        grade: a.normalized_grade(),
+        oracle: a.attested_by,
+        slot: Clock::get()?.slot,
+    });
+    
+    ctx.accounts.latest.bump = ctx.bumps.latest;
+    if let Some(superseded) = record_latest_attestation(
+        &mut ctx.accounts.latest, 
+        ctx.accounts.superseded.as_deref(), 
+        &mut a, 
+        ctx.accounts.attestation.key()
+    )? {
+        emit_cpi!(superseded);
+    }
+    index_attestation(
+        &mut ctx.accounts.grade_index_entry, 
+        ctx.bumps.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        &a, 
+        ctx.accounts.attestation.key(), 
+        &ctx.accounts.latest
+    )?;
+    
+    emit_cpi!(TokenAttested {
+        mint,
+        ruleset_version,
+        score,
+        grade,
+        attested_by: a.attested_by,
+        attested_at: a.attested_at,
+    });
+    emit_cpi!(TokenAttestedV2::new(&a, prior, total_weight));
+    if let Some(changed) = ScoreChanged::new(&a, prior) {
+        emit_cpi!(changed);
+    }
+    
+    emit!(ConsensusReached {
+        mint,
+        ruleset_version,
+        score,
+        grade,
+        oracles,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct SubmitAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: token mint
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"scope", signer.key().as_ref(), mint.key().as_ref()], 
+        bump = scope_entry.bump
+    )]
+    pub scope_entry: Option<Account<'info, ScopeEntry>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"submission", mint.key().as_ref(), &ruleset_version.to_le_bytes(), signer.key().as_ref()], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 2 + 32 + 2 + 1 + 32 + 33 + 2 * SUBSCORE_COUNT + 8 + 8 + 1 // discriminator + mint + version + oracle + score + grade + hash + report + subscores + flags + time + bump
+    )]
+    pub submission: Account<'info, Submission>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"oracle_stats", signer.key().as_ref()], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 8 + 8 + 8 + 8 * GRADE_LEVELS_EXTENDED as usize + 1 // discriminator + oracle + attestations + revocations + last attested + histogram + bump
+    )]
+    pub oracle_stats: Account<'info, OracleStats>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct FinalizeConsensus<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    // SPL Token or Token-2022 mint, must be initialized
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: the mint's Blacklist PDA, which must not exist
+    #[account(
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        constraint = blacklist.data_is_empty() @ ErrorCode::MintBlacklisted
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = ATTESTATION_SPACE
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"history", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = HISTORY_SPACE
+    )]
+    pub history: Box<Account<'info, AttestationHistory>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"latest", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = LATEST_ATTESTATION_SPACE
+    )]
+    pub latest: Box<Account<'info, LatestAttestation>>,
+    /// CHECK: the attestation `latest` points at, loaded by record_latest_attestation;
+    /// required when this write moves the mint to a newer ruleset version
+    #[account(mut, address = latest.attestation @ ErrorCode::SupersededAttestationMismatch)]
+    pub superseded: Option<UncheckedAccount<'info>>,
+    /// CHECK: ProofMarker PDA for (ruleset_version, proofs_hash), created on first use in the handler
+    #[account(mut)]
+    pub proof_marker: UncheckedAccount<'info>,
+    // The mint's GradeIndexEntry, created on its first write
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_index_entry", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_INDEX_ENTRY_SPACE
+    )]
+    pub grade_index_entry: Box<Account<'info, GradeIndexEntry>>,
+    pub grade_index: GradeIndexPages<'info>,
+    // Pending waiver of the grade band check; consumed by this write
+    #[account(
+        mut, 
+        seeds = [b"grade_waiver", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = grade_waiver.bump
+    )]
+    pub grade_waiver: Option<Account<'info, GradeWaiver>>,
+    /// CHECK: GradeWaiver.payer, refunded the waiver's rent; required with grade_waiver
+    #[account(mut)]
+    pub grade_waiver_payer: Option<UncheckedAccount<'info>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this write grades the mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = payer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
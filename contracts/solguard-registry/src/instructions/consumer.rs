@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn get_effective_grade(ctx: Context<ReadAttestation>, _ruleset_version: u16) -> Result<u8> {
+    let now = Clock::get()?.unix_timestamp;
+    let a = ctx.accounts.attestation.load()?;
+    a.require_valid(now)?;
+    Ok(a.effective_grade(&ctx.accounts.config, now))
+}
+
+pub fn get_attestation_status(ctx: Context<ReadAttestation>, _ruleset_version: u16) -> Result<AttestationStatus> {
+    let a = ctx.accounts.attestation.load()?;
+    Ok(a.status(Clock::get()?.unix_timestamp))
+}
+
+pub fn verify_attestation(
+    ctx: Context<VerifyAttestation>, 
+    ruleset_version: u16, 
+    min_grade: u8, 
+    max_age_secs: Option<i64>
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cfg = &ctx.accounts.config;
+    let (min_grade, max_age_secs, pinned) = match &ctx.accounts.consumer_config {
+        Some(consumer) => {
+            require_caller(ctx.accounts.caller.as_ref(), &consumer.consumer_program)?;
+            require!(consumer.accepts_version(ruleset_version, cfg), ErrorCode::InvalidRulesetVersion);
+            (min_grade.max(consumer.min_grade), max_age_secs.or(consumer.max_age), consumer.pins_version(ruleset_version))
+        }
+        None => {
+            require!(cfg.is_active_version(ruleset_version), ErrorCode::InvalidRulesetVersion);
+            (min_grade, max_age_secs, false)
+        }
+    };
+    let min_grade = min_grade.max(cfg.ruleset_min_grade(ruleset_version));
+    if cfg.subscription_price > 0 {
+        require_subscription(ctx.accounts.subscription.as_deref(), ctx.accounts.caller.as_ref())?;
+    }
+    
+    let mut grade = if !ctx.accounts.grade_override.data_is_empty() {
+        load_program_account::<GradeOverride>(&ctx.accounts.grade_override)?.grade
+    } else if !ctx.accounts.allowlist.data_is_empty() {
+        GRADE_GREEN
+    } else {
+        let info = &ctx.accounts.attestation;
+        require!(info.owner == &crate::ID && !info.data_is_empty(), ErrorCode::AttestationMissing);
+        let loader = AccountLoader::<Attestation>::try_from(info.as_ref())?;
+        let a = loader.load()?;
+        a.require_valid(now)?;
+        a.require_fresh(now, cfg.max_age_or_default(max_age_secs)?)?;
+        require!(!a.is_superseded() || pinned, ErrorCode::AttestationSuperseded);
+        a.effective_grade(cfg, now)
+    };
+    if !ctx.accounts.blacklist.data_is_empty() {
+        grade = grade.min(GRADE_RED);
+    }
+    require!(grade >= min_grade, ErrorCode::InsufficientGrade);
+    Ok(())
+}
+
+pub fn set_consumer_config(
+    ctx: Context<SetConsumerConfig>, 
+    min_grade: u8, 
+    max_age: Option<i64>, 
+    accepted_versions: Vec<u16>, 
+    min_version: u16, 
+    max_version: u16
+) -> Result<()> {
+    require!(min_grade < GRADE_LEVELS_EXTENDED, ErrorCode::InvalidGrade);
+    require!(max_age.unwrap_or(0) >= 0, ErrorCode::InvalidMaxAge);
+    require!(accepted_versions.len() <= MAX_CONSUMER_VERSIONS, ErrorCode::TooManyRulesetVersions);
+    require!(
+        if max_version == 0 { min_version == 0 } else { min_version <= max_version }, 
+        ErrorCode::InvalidVersionRange
+    );
+    
+    let c = &mut ctx.accounts.consumer_config;
+    c.consumer_program = ctx.accounts.consumer_program.key();
+    c.authority = ctx.accounts.authority.key();
+    c.min_grade = min_grade;
+    c.max_age = max_age;
+    c.accepted_versions = accepted_versions;
+    c.updated_at = Clock::get()?.unix_timestamp;
+    c.bump = ctx.bumps.consumer_config;
+    c.min_version = min_version;
+    c.max_version = max_version;
+    
+    emit!(ConsumerConfigUpdated {
+        consumer_program: c.consumer_program,
+        authority: c.authority,
+        min_grade,
+        max_age,
+        accepted_versions: c.accepted_versions.clone(),
+        min_version,
+        max_version,
+    });
+    
+    Ok(())
+}
+
+pub fn fund_subscription(ctx: Context<FundSubscription>, epochs: u64) -> Result<()> {
+    let price = ctx.accounts.config.subscription_price;
+    require!(price > 0, ErrorCode::SubscriptionsDisabled);
+    require!(epochs > 0, ErrorCode::InvalidSubscriptionEpochs);
+    let amount = price.checked_mul(epochs).ok_or(ErrorCode::InvalidSubscriptionEpochs)?;
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(), 
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.fee_vault.to_account_info(),
+            }
+        ), 
+        amount
+    )?;
+    ctx.accounts.fee_vault.total_collected += amount;
+    
+    let current_epoch = Clock::get()?.epoch;
+    let sub = &mut ctx.accounts.subscription;
+    let start = if sub.is_active(current_epoch) { sub.paid_through_epoch + 1 } else { current_epoch };
+    sub.consumer_program = ctx.accounts.consumer_program.key();
+    sub.paid_through_epoch = start + epochs - 1;
+    sub.total_paid += amount;
+    sub.bump = ctx.bumps.subscription;
+    
+    emit!(SubscriptionFunded {
+        consumer_program: sub.consumer_program,
+        funder: ctx.accounts.funder.key(),
+        epochs,
+        amount,
+        paid_through_epoch: sub.paid_through_epoch,
+    });
+    
+    Ok(())
+}
+
+pub fn set_subscription_price(ctx: Context<OnlyAdmin>, price: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetSubscriptionPrice::DISCRIMINATOR, &price)?;
+    let old_price = ctx.accounts.config.subscription_price;
+    ctx.accounts.config.subscription_price = price;
+    
+    emit!(SubscriptionPriceUpdated {
+        old_price,
+        new_price: price,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_default_max_age(ctx: Context<OnlyAdmin>, max_age_secs: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetDefaultMaxAge::DISCRIMINATOR, &max_age_secs)?;
+    require!(max_age_secs >= 0, ErrorCode::InvalidMaxAge);
+    let old_max_age = ctx.accounts.config.default_max_age;
+    ctx.accounts.config.default_max_age = max_age_secs;
+    
+    emit!(DefaultMaxAgeUpdated {
+        old_max_age,
+        new_max_age: max_age_secs,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct ReadAttestation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VerifyAttestation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: may not exist for allowlisted mints; ownership checked in the handler
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: UncheckedAccount<'info>,
+    /// CHECK: the mint's Blacklist PDA; only its existence is checked
+    #[account(seeds = [b"blacklist", mint.key().as_ref()], bump)]
+    pub blacklist: UncheckedAccount<'info>,
+    /// CHECK: the mint's Allowlist PDA; only its existence is checked
+    #[account(seeds = [b"allowlist", mint.key().as_ref()], bump)]
+    pub allowlist: UncheckedAccount<'info>,
+    /// CHECK: the mint's GradeOverride PDA; read in the handler when it exists
+    #[account(seeds = [b"grade_override", mint.key().as_ref()], bump)]
+    pub grade_override: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"consumer", consumer_config.consumer_program.as_ref()], 
+        bump = consumer_config.bump
+    )]
+    pub consumer_config: Option<Account<'info, ConsumerConfig>>,
+    /// The consumer program's `[b"solguard_caller"]` PDA, signed through invoke_signed
+    pub caller: Option<Signer<'info>>,
+    #[account(
+        seeds = [b"subscription", subscription.consumer_program.as_ref()], 
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+}
+
+#[derive(Accounts)]
+pub struct FundSubscription<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"subscription", consumer_program.key().as_ref()], 
+        bump, 
+        payer = funder, 
+        space = 8 + 32 + 8 + 8 + 1 // discriminator + program + paid through + total paid + bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    /// CHECK: the subscribing program; only its key is used
+    #[account(executable)]
+    pub consumer_program: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetConsumerConfig<'info> {
+    #[account(
+        init_if_needed, 
+        seeds = [b"consumer", consumer_program.key().as_ref()], 
+        bump, 
+        payer = authority, 
+        space = 8 + 32 + 32 + 1 + 9 + 4 + 2 * MAX_CONSUMER_VERSIONS + 8 + 1 + 2 + 2 // discriminator + program + authority + grade + max age + versions + updated + bump + version range
+    )]
+    pub consumer_config: Account<'info, ConsumerConfig>,
+    /// CHECK: the integrating program; only its key is used
+    #[account(executable)]
+    pub consumer_program: UncheckedAccount<'info>,
+    #[account(
+        seeds = [consumer_program.key().as_ref()], 
+        bump, 
+        seeds::program = bpf_loader_upgradeable::ID, 
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::NotConsumerAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
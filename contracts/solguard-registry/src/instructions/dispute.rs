@@ -0,0 +1,633 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn set_dispute_bond(ctx: Context<OnlyAdmin>, bond: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetDisputeBond::DISCRIMINATOR, &bond)?;
+    let old_bond = ctx.accounts.config.dispute_bond;
+    ctx.accounts.config.dispute_bond = bond;
+    
+    emit!(DisputeBondUpdated {
+        old_bond,
+        new_bond: bond,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn file_dispute(ctx: Context<FileDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+    let attestation = ctx.accounts.attestation.key();
+    let a = ctx.accounts.attestation.load()?;
+    require!(!a.is_revoked(), ErrorCode::AttestationRevoked);
+    
+    let bond = ctx.accounts.config.dispute_bond;
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.challenger.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+    
+    let d = &mut ctx.accounts.dispute;
+    d.attestation = attestation;
+    d.mint = a.mint;
+    d.ruleset_version = a.ruleset_version;
+    d.oracle = a.attested_by;
+    d.challenger = ctx.accounts.challenger.key();
+    d.bond = bond;
+    d.evidence_hash = evidence_hash;
+    d.filed_at = Clock::get()?.unix_timestamp;
+    d.status = DisputeStatus::Open;
+    d.bump = ctx.bumps.dispute;
+    
+    emit!(DisputeFiled {
+        dispute: d.key(),
+        attestation: d.attestation,
+        mint: d.mint,
+        ruleset_version: d.ruleset_version,
+        oracle: d.oracle,
+        challenger: d.challenger,
+        bond,
+        evidence_hash,
+    });
+    
+    Ok(())
+}
+
+pub fn resolve_dispute(ctx: Context<ResolveDispute>, sustain: bool, reward: u64) -> Result<()> {
+    let resolver = ctx.accounts.resolver.key();
+    let decision = council_decision(ctx.accounts.council.as_deref(), ctx.accounts.council_tally.as_deref());
+    let (sustain, reward) = match decision {
+        Some(sustain) => (sustain, ctx.accounts.dispute.bond),
+        None => {
+            require_role(&ctx.accounts.config, &resolver, Role::Revoker, ctx.accounts.role_grant.as_deref(), ctx.accounts.admin_set.as_deref(), ctx.remaining_accounts)?;
+            let audit_log = ctx.accounts.audit_log.as_ref().ok_or(ErrorCode::AuditLogRequired)?;
+            record_admin_action(audit_log, &resolver, instruction::ResolveDispute::DISCRIMINATOR, &(sustain, reward))?;
+            (sustain, reward)
+        }
+    };
+    require!(ctx.accounts.dispute.status == DisputeStatus::Open, ErrorCode::DisputeNotOpen);
+    
+    let bond = ctx.accounts.dispute.bond;
+    let dispute_info = ctx.accounts.dispute.to_account_info();
+    let mut paid_reward = 0;
+    if sustain {
+        let mut a = ctx.accounts.attestation.load_mut()?;
+        if !a.is_revoked() {
+            a.revoke(RevocationReason::Other, ctx.accounts.dispute.evidence_hash);
+            update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.revocation_count += 1)?;
+            if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+                registry_stats.record_revocation(a.score_bps, a.normalized_grade());
+            }
+            notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+            flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+            update_grade_index_at(
+                &ctx.accounts.grade_index_entry, 
+                &mut ctx.accounts.grade_index, 
+                a.mint, 
+                ctx.accounts.attestation.key(), 
+                None
+            )?;
+            emit_cpi!(AttestationRevokedV2::new(&a, RevocationSource::Dispute, resolver, RevocationReason::Other)?);
+        }
+        
+        let o = &mut ctx.accounts.oracle;
+        paid_reward = reward.min(o.stake);
+        o.stake -= paid_reward;
+        move_lamports(&o.to_account_info(), &ctx.accounts.challenger.to_account_info(), paid_reward)?;
+        move_lamports(&dispute_info, &ctx.accounts.challenger.to_account_info(), bond)?;
+    } else {
+        move_lamports(&dispute_info, &ctx.accounts.treasury.to_account_info(), bond)?;
+    }
+    
+    let d = &mut ctx.accounts.dispute;
+    d.bond = 0;
+    d.status = if sustain { DisputeStatus::Sustained } else { DisputeStatus::Dismissed };
+    
+    emit!(DisputeResolved {
+        dispute: d.key(),
+        attestation: d.attestation,
+        mint: d.mint,
+        challenger: d.challenger,
+        sustained: sustain,
+        bond,
+        reward: paid_reward,
+        resolver,
+    });
+    
+    Ok(())
+}
+
+pub fn set_challenge_params(ctx: Context<OnlyAdmin>, bond: u64, window: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetChallengeParams::DISCRIMINATOR, &(bond, window))?;
+    require!(window >= 0, ErrorCode::InvalidChallengeWindow);
+    ctx.accounts.config.challenge_bond = bond;
+    ctx.accounts.config.challenge_window = window;
+    
+    emit!(ChallengeParamsUpdated {
+        bond,
+        window,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn challenge_attestation(ctx: Context<ChallengeAttestation>, evidence_hash: [u8; 32]) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    require!(cfg.challenge_bond > 0, ErrorCode::ChallengesDisabled);
+    let bond = cfg.challenge_bond;
+    let now = Clock::get()?.unix_timestamp;
+    
+    let attestation = ctx.accounts.attestation.key();
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_revoked(), ErrorCode::AttestationRevoked);
+    a.challenged = 1;
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.challenger.to_account_info(),
+                to: ctx.accounts.challenge.to_account_info(),
+            },
+        ),
+        bond,
+    )?;
+    
+    let c = &mut ctx.accounts.challenge;
+    c.attestation = attestation;
+    c.mint = a.mint;
+    c.ruleset_version = a.ruleset_version;
+    c.oracle = a.attested_by;
+    c.challenger = ctx.accounts.challenger.key();
+    c.bond = bond;
+    c.evidence_hash = evidence_hash;
+    c.response_hash = [0; 32];
+    c.opened_at = now;
+    c.answer_deadline = now + cfg.challenge_window;
+    c.status = ChallengeStatus::Open;
+    c.bump = ctx.bumps.challenge;
+    
+    emit!(AttestationChallenged {
+        challenge: c.key(),
+        attestation,
+        mint: c.mint,
+        ruleset_version: c.ruleset_version,
+        oracle: c.oracle,
+        challenger: c.challenger,
+        bond,
+        evidence_hash,
+        answer_deadline: c.answer_deadline,
+    });
+    
+    Ok(())
+}
+
+pub fn answer_challenge(ctx: Context<AnswerChallenge>, response_hash: [u8; 32]) -> Result<()> {
+    let c = &mut ctx.accounts.challenge;
+    require!(c.status == ChallengeStatus::Open, ErrorCode::ChallengeAlreadyAnswered);
+    require!(Clock::get()?.unix_timestamp <= c.answer_deadline, ErrorCode::ChallengeWindowClosed);
+    c.status = ChallengeStatus::Answered;
+    c.response_hash = response_hash;
+    
+    emit!(ChallengeAnswered {
+        challenge: c.key(),
+        oracle: c.oracle,
+        response_hash,
+    });
+    
+    Ok(())
+}
+
+pub fn resolve_challenge(ctx: Context<ResolveChallenge>, upheld: bool) -> Result<()> {
+    let resolver = ctx.accounts.resolver.key();
+    let c = &ctx.accounts.challenge;
+    let unanswered = c.status == ChallengeStatus::Open && Clock::get()?.unix_timestamp > c.answer_deadline;
+    let decision = council_decision(ctx.accounts.council.as_deref(), ctx.accounts.council_tally.as_deref());
+    let upheld = if unanswered {
+        true
+    } else if let Some(upheld) = decision {
+        upheld
+    } else {
+        require_role(&ctx.accounts.config, &resolver, Role::Revoker, ctx.accounts.role_grant.as_deref(), ctx.accounts.admin_set.as_deref(), ctx.remaining_accounts)?;
+        let audit_log = ctx.accounts.audit_log.as_ref().ok_or(ErrorCode::AuditLogRequired)?;
+        record_admin_action(audit_log, &resolver, instruction::ResolveChallenge::DISCRIMINATOR, &upheld)?;
+        upheld
+    };
+    
+    let bond = c.bond;
+    let evidence_hash = c.evidence_hash;
+    let challenge_info = ctx.accounts.challenge.to_account_info();
+    let mut slashed = 0;
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    a.challenged = 0;
+    if upheld {
+        if !a.is_revoked() {
+            a.revoke(RevocationReason::Other, evidence_hash);
+            update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.revocation_count += 1)?;
+            if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+                registry_stats.record_revocation(a.score_bps, a.normalized_grade());
+            }
+            notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+            flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+            update_grade_index_at(
+                &ctx.accounts.grade_index_entry, 
+                &mut ctx.accounts.grade_index, 
+                a.mint, 
+                ctx.accounts.attestation.key(), 
+                None
+            )?;
+            emit_cpi!(AttestationRevokedV2::new(&a, RevocationSource::Challenge, resolver, RevocationReason::Other)?);
+        }
+        
+        let o = &mut ctx.accounts.oracle;
+        slashed = bond.min(o.stake);
+        o.stake -= slashed;
+        move_lamports(&o.to_account_info(), &ctx.accounts.challenger.to_account_info(), slashed)?;
+    } else {
+        move_lamports(&challenge_info, &ctx.accounts.oracle_key.to_account_info(), bond)?;
+    }
+    
+    emit!(ChallengeResolved {
+        challenge: challenge_info.key(),
+        attestation: ctx.accounts.attestation.key(),
+        mint: a.mint,
+        challenger: ctx.accounts.challenger.key(),
+        upheld,
+        bond,
+        slashed,
+        resolver,
+    });
+    
+    Ok(())
+}
+
+pub fn set_council(ctx: Context<ConfigureCouncil>, members: Vec<Pubkey>, quorum: u8) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetCouncil::DISCRIMINATOR, &(&members, quorum))?;
+    require!(!members.is_empty() && members.len() <= MAX_COUNCIL_MEMBERS, ErrorCode::InvalidCouncil);
+    require!(quorum >= 1 && quorum as usize <= members.len(), ErrorCode::InvalidCouncil);
+    for (i, member) in members.iter().enumerate() {
+        require!(*member != Pubkey::default(), ErrorCode::InvalidCouncil);
+        require!(!members[..i].contains(member), ErrorCode::InvalidCouncil);
+    }
+    
+    let council = &mut ctx.accounts.council;
+    council.members = members;
+    council.quorum = quorum;
+    council.epoch += 1;
+    council.bump = ctx.bumps.council;
+    
+    emit!(CouncilUpdated {
+        members: council.members.clone(),
+        quorum,
+        epoch: council.epoch,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn vote_on_dispute(ctx: Context<VoteOnDispute>, uphold: bool) -> Result<()> {
+    let council = &ctx.accounts.council;
+    let member = ctx.accounts.member.key();
+    require!(council.members.contains(&member), ErrorCode::NotCouncilMember);
+    require_open_case(&ctx.accounts.case)?;
+    
+    let case = ctx.accounts.case.key();
+    let vote = &mut ctx.accounts.vote;
+    require!(vote.epoch != council.epoch, ErrorCode::DuplicateCouncilVote);
+    vote.case = case;
+    vote.member = member;
+    vote.uphold = uphold;
+    vote.epoch = council.epoch;
+    vote.voted_at = Clock::get()?.unix_timestamp;
+    vote.bump = ctx.bumps.vote;
+    
+    let tally = &mut ctx.accounts.tally;
+    if tally.epoch != council.epoch {
+        tally.case = case;
+        tally.epoch = council.epoch;
+        tally.uphold_votes = 0;
+        tally.reject_votes = 0;
+        tally.bump = ctx.bumps.tally;
+    }
+    require!(
+        tally.uphold_votes < council.quorum && tally.reject_votes < council.quorum,
+        ErrorCode::CouncilCaseDecided
+    );
+    if uphold {
+        tally.uphold_votes += 1;
+    } else {
+        tally.reject_votes += 1;
+    }
+    
+    emit!(CouncilVoteCast {
+        case,
+        member,
+        uphold,
+        uphold_votes: tally.uphold_votes,
+        reject_votes: tally.reject_votes,
+        quorum: council.quorum,
+    });
+    
+    Ok(())
+}
+
+/// A Dispute still open, or a Challenge not yet resolved.
+fn require_open_case(case: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*case.owner, crate::ID, ErrorCode::InvalidCouncilCase);
+    let data = case.try_borrow_data()?;
+    if data.starts_with(&Dispute::discriminator()) {
+        let dispute = Dispute::try_deserialize(&mut &data[..])?;
+        require!(dispute.status == DisputeStatus::Open, ErrorCode::DisputeNotOpen);
+        Ok(())
+    } else if data.starts_with(&Challenge::discriminator()) {
+        Ok(())
+    } else {
+        err!(ErrorCode::InvalidCouncilCase)
+    }
+}
+
+/// The council's decision when both accounts were passed and a side has quorum.
+fn council_decision(council: Option<&Council>, tally: Option<&CouncilTally>) -> Option<bool> {
+    let (council, tally) = (council?, tally?);
+    if tally.epoch != council.epoch {
+        None
+    } else if tally.uphold_votes >= council.quorum {
+        Some(true)
+    } else if tally.reject_votes >= council.quorum {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[derive(Accounts)]
+pub struct FileDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init, 
+        seeds = [b"dispute", attestation.key().as_ref(), challenger.key().as_ref()], 
+        bump, 
+        payer = challenger, 
+        space = 8 + 32 + 32 + 2 + 32 + 32 + 8 + 32 + 8 + 1 + 1 // discriminator + attestation + mint + version + oracle + challenger + bond + evidence + filed + status + bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub resolver: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    // Only needed when a Revoker decides the dispute
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    // Both needed to apply a council decision
+    #[account(seeds = [b"council"], bump = council.bump)]
+    pub council: Option<Account<'info, Council>>,
+    #[account(seeds = [b"council_tally", dispute.key().as_ref()], bump = council_tally.bump)]
+    pub council_tally: Option<Account<'info, CouncilTally>>,
+    #[account(
+        mut, 
+        has_one = attestation,
+        has_one = challenger,
+        seeds = [b"dispute", attestation.key().as_ref(), challenger.key().as_ref()], 
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut)]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        mut, 
+        seeds = [b"oracle", dispute.oracle.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: the oracle's OracleStats PDA, absent for oracles that attested before
+    /// stats were tracked; always required so the update cannot be skipped
+    #[account(
+        mut, 
+        seeds = [b"oracle_stats", dispute.oracle.as_ref()], 
+        bump
+    )]
+    pub oracle_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", dispute.mint.as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this revokes the attestation; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    /// CHECK: the mint's GradeIndexEntry PDA, empty when it was never listed; always
+    /// required so the index cannot keep listing a revoked attestation
+    #[account(mut, seeds = [b"grade_index_entry", dispute.mint.as_ref()], bump)]
+    pub grade_index_entry: UncheckedAccount<'info>,
+    pub grade_index: GradeIndexPages<'info>,
+    /// CHECK: challenger recorded on the dispute
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+    // Forfeited bonds go to the fee vault, withdrawable only through withdraw_fees
+    #[account(mut, seeds = [b"fee_vault"], bump = treasury.bump)]
+    pub treasury: Account<'info, FeeVault>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init, 
+        seeds = [b"challenge", attestation.key().as_ref()], 
+        bump, 
+        payer = challenger, 
+        space = 8 + 32 + 32 + 2 + 32 + 32 + 8 + 32 + 32 + 8 + 8 + 1 + 1 // discriminator + attestation + mint + version + oracle + challenger + bond + evidence + response + opened + deadline + status + bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AnswerChallenge<'info> {
+    #[account(
+        mut, 
+        has_one = oracle,
+        seeds = [b"challenge", challenge.attestation.as_ref()], 
+        bump = challenge.bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    pub oracle: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveChallenge<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub resolver: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    // Only needed when a Revoker decides an answered challenge
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    // Both needed to apply a council decision
+    #[account(seeds = [b"council"], bump = council.bump)]
+    pub council: Option<Account<'info, Council>>,
+    #[account(seeds = [b"council_tally", challenge.key().as_ref()], bump = council_tally.bump)]
+    pub council_tally: Option<Account<'info, CouncilTally>>,
+    #[account(
+        mut, 
+        has_one = attestation,
+        has_one = challenger,
+        close = challenger,
+        seeds = [b"challenge", attestation.key().as_ref()], 
+        bump = challenge.bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    #[account(mut)]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        mut, 
+        seeds = [b"oracle", challenge.oracle.as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: the challenged oracle's key, paid the bond when the challenge is rejected
+    #[account(mut, address = challenge.oracle)]
+    pub oracle_key: UncheckedAccount<'info>,
+    /// CHECK: the oracle's OracleStats PDA, absent for oracles that attested before
+    /// stats were tracked; always required so the update cannot be skipped
+    #[account(
+        mut, 
+        seeds = [b"oracle_stats", challenge.oracle.as_ref()], 
+        bump
+    )]
+    pub oracle_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", challenge.mint.as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this revokes the attestation; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    /// CHECK: the mint's GradeIndexEntry PDA, empty when it was never listed; always
+    /// required so the index cannot keep listing a revoked attestation
+    #[account(mut, seeds = [b"grade_index_entry", challenge.mint.as_ref()], bump)]
+    pub grade_index_entry: UncheckedAccount<'info>,
+    pub grade_index: GradeIndexPages<'info>,
+    /// CHECK: challenger recorded on the challenge
+    #[account(mut)]
+    pub challenger: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureCouncil<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"council"], 
+        bump, 
+        payer = payer, 
+        space = 8 + 4 + 32 * MAX_COUNCIL_MEMBERS + 1 + 4 + 1 // discriminator + members + quorum + epoch + bump
+    )]
+    pub council: Account<'info, Council>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoteOnDispute<'info> {
+    #[account(seeds = [b"council"], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    /// CHECK: Dispute or Challenge account, checked by require_open_case
+    pub case: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"council_vote", case.key().as_ref(), member.key().as_ref()], 
+        bump, 
+        payer = member, 
+        space = 8 + 32 + 32 + 1 + 4 + 8 + 1 // discriminator + case + member + uphold + epoch + voted + bump
+    )]
+    pub vote: Account<'info, CouncilVote>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"council_tally", case.key().as_ref()], 
+        bump, 
+        payer = member, 
+        space = 8 + 32 + 4 + 1 + 1 + 1 // discriminator + case + epoch + uphold + reject + bump
+    )]
+    pub tally: Account<'info, CouncilTally>,
+    #[account(mut)]
+    pub member: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
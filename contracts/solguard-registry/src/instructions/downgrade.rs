@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn set_downgrade_cosign_delta(ctx: Context<OnlyAdmin>, delta_bps: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetDowngradeCosignDelta::DISCRIMINATOR, &delta_bps)?;
+    require!(delta_bps <= 10000, ErrorCode::InvalidScore);
+    let old_delta_bps = ctx.accounts.config.downgrade_cosign_delta_bps;
+    ctx.accounts.config.downgrade_cosign_delta_bps = delta_bps;
+    
+    emit!(DowngradeCosignDeltaUpdated {
+        old_delta_bps,
+        new_delta_bps: delta_bps,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn propose_downgrade(ctx: Context<ProposeDowngrade>, ruleset_version: u16, item: BatchAttestation) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    validate_submission(cfg, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), item.score, item.grade)?;
+    item.report.validate()?;
+    validate_subscores(&item.subscores)?;
+    require!(cfg.consensus_threshold <= 1, ErrorCode::ConsensusRequired);
+    
+    let a = ctx.accounts.attestation.load()?;
+    require!(
+        cfg.requires_downgrade_cosign(a.live_score(), item.score, normalize_grade(item.grade, cfg.grade_scale())), 
+        ErrorCode::CosignNotRequired
+    );
+    
+    let signer = ctx.accounts.signer.key();
+    let p = &mut ctx.accounts.pending;
+    require!(
+        p.proposer == Pubkey::default() || p.proposer == signer || p.base_attested_at != a.attested_at, 
+        ErrorCode::DowngradeAlreadyPending
+    );
+    charge_attestation_fee(
+        &ctx.accounts.config, 
+        &mut ctx.accounts.fee, 
+        &ctx.accounts.signer.to_account_info(), 
+        &ctx.accounts.system_program.to_account_info(), 
+        1
+    )?;
+    
+    if p.payer == Pubkey::default() {
+        p.payer = signer;
+    }
+    p.mint = a.mint;
+    p.ruleset_version = ruleset_version;
+    p.proposer = signer;
+    p.base_attested_at = a.attested_at;
+    p.proposed_at = Clock::get()?.unix_timestamp;
+    p.bump = ctx.bumps.pending;
+    
+    emit!(DowngradeProposed {
+        mint: a.mint,
+        ruleset_version,
+        proposer: signer,
+        previous_score: a.score_bps,
+        score: item.score,
+        grade: item.grade,
+    });
+    p.item = item;
+    
+    Ok(())
+}
+
+pub fn cosign_downgrade(ctx: Context<CosignDowngrade>, ruleset_version: u16) -> Result<()> {
+    let cosigner = ctx.accounts.cosigner.key();
+    let p = &ctx.accounts.pending;
+    match &ctx.accounts.cosigner_oracle {
+        Some(oracle) => {
+            require!(oracle.active, ErrorCode::OracleInactive);
+            require_keys_neq!(cosigner, p.proposer, ErrorCode::CosignerIsProposer);
+        }
+        None => {
+            require_role(&ctx.accounts.config, &cosigner, Role::Revoker, ctx.accounts.role_grant.as_deref(), ctx.accounts.admin_set.as_deref(), ctx.remaining_accounts)?;
+            let audit_log = ctx.accounts.audit_log.as_ref().ok_or(ErrorCode::AuditLogRequired)?;
+            record_admin_action(audit_log, &cosigner, instruction::CosignDowngrade::DISCRIMINATOR, &ruleset_version)?;
+        }
+    }
+    let cfg = &ctx.accounts.config;
+    require!(!cfg.paused, ErrorCode::RegistryPaused);
+    
+    let clock = Clock::get()?;
+    let item = p.item.clone();
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(a.attested_at == p.base_attested_at, ErrorCode::StalePendingDowngrade);
+    a.consume_cooldown(cfg.attestation_cooldown, clock.unix_timestamp)?;
+    let previous = a.live_score();
+    let prior = a.prior_score();
+    a.advance_nonce(item.nonce)?;
+    a.score_bps = item.score;
+    a.grade = item.grade;
+    a.grade_levels = cfg.grade_scale();
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.proofs_hash = item.proofs_hash;
+    a.set_report(item.report);
+    a.metadata_hash = [0; 32];
+    a.set_mint_snapshot(&ctx.accounts.mint, mint_risk_flags(&ctx.accounts.mint.to_account_info())?, clock.slot);
+    a.require_extensions_allowed()?;
+    a.subscores = item.subscores;
+    a.flags = item.flags;
+    a.attested_by = p.proposer;
+    a.attested_at = clock.unix_timestamp;
+    a.clear_revocation();
+    a.expired = 0;
+    a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+    a.oracle_count = if ctx.accounts.cosigner_oracle.is_some() { 2 } else { 1 };
+    a.origin_chain = 0;
+    a.zk_verified = 0;
+    a.valid_until = cfg.expiry_from(a.attested_at);
+    if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+        registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+    }
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    if a.normalized_grade() <= GRADE_RED {
+        flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    }
+    update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.record_attestation(a.normalized_grade(), a.attested_at))?;
+    ctx.accounts.history.push(HistoryEntry {
+        score_bps: item.score,
+        grade: a.normalized_grade(),
+        oracle: a.attested_by,
+        slot: clock.slot,
+    });
+    
+    emit_cpi!(TokenAttested {
+        mint: a.mint,
+        ruleset_version,
+        score: item.score,
+        grade: item.grade,
+        attested_by: a.attested_by,
+        attested_at: a.attested_at,
+    });
+    emit_cpi!(TokenAttestedV2::new(&a, prior, ctx.accounts.proposer_oracle.weight as u64));
+    if let Some(changed) = ScoreChanged::new(&a, prior) {
+        emit_cpi!(changed);
+    }
+    emit!(DowngradeCosigned {
+        mint: a.mint,
+        ruleset_version,
+        proposer: a.attested_by,
+        cosigner,
+        score: item.score,
+        grade: item.grade,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct ProposeDowngrade<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"pending_downgrade", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = PENDING_DOWNGRADE_SPACE
+    )]
+    pub pending: Box<Account<'info, PendingDowngrade>>,
+    pub fee: FeePayment<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct CosignDowngrade<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub cosigner: Signer<'info>,
+    // Present when an oracle co-signs; Revokers co-sign without it
+    #[account(seeds = [b"oracle", cosigner.key().as_ref()], bump = cosigner_oracle.bump)]
+    pub cosigner_oracle: Option<Account<'info, Oracle>>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    // Only needed when a Revoker co-signs
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        mut, 
+        close = payer, 
+        seeds = [b"pending_downgrade", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = pending.bump
+    )]
+    pub pending: Box<Account<'info, PendingDowngrade>>,
+    #[account(seeds = [b"oracle", pending.proposer.as_ref()], bump = proposer_oracle.bump)]
+    pub proposer_oracle: Account<'info, Oracle>,
+    /// CHECK: the proposer's OracleStats PDA, absent for oracles that attested before
+    /// stats were tracked; always required so the update cannot be skipped
+    #[account(mut, seeds = [b"oracle_stats", pending.proposer.as_ref()], bump)]
+    pub oracle_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"history", mint.key().as_ref()], bump = history.bump)]
+    pub history: Box<Account<'info, AttestationHistory>>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this write grades the mint red or below; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    /// CHECK: the proposal's rent payer
+    #[account(mut, address = pending.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+
+use crate::auth::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn init_fee_vault(ctx: Context<InitFeeVault>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::InitFeeVault::DISCRIMINATOR, &())?;
+    ctx.accounts.fee_vault.bump = ctx.bumps.fee_vault;
+    Ok(())
+}
+
+pub fn set_attestation_fee(ctx: Context<OnlyAdmin>, fee: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetAttestationFee::DISCRIMINATOR, &fee)?;
+    let old_fee = ctx.accounts.config.attestation_fee;
+    ctx.accounts.config.attestation_fee = fee;
+    
+    emit!(AttestationFeeUpdated {
+        old_fee,
+        new_fee: fee,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64, destination: Pubkey) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::WithdrawFees::DISCRIMINATOR, &(amount, destination))?;
+    
+    let vault_info = ctx.accounts.fee_vault.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+    let available = vault_info.lamports().saturating_sub(rent_floor);
+    require!(amount > 0 && amount <= available, ErrorCode::InsufficientFees);
+    
+    move_lamports(&vault_info, &ctx.accounts.destination.to_account_info(), amount)?;
+    ctx.accounts.fee_vault.total_withdrawn += amount;
+    
+    emit!(FeesWithdrawn {
+        amount,
+        destination,
+        total_withdrawn: ctx.accounts.fee_vault.total_withdrawn,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn init_fee_token_vault(ctx: Context<InitFeeTokenVault>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::InitFeeTokenVault::DISCRIMINATOR, &())?;
+    Ok(())
+}
+
+pub fn set_fee_mint(ctx: Context<OnlyAdmin>, fee_mint: Pubkey) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetFeeMint::DISCRIMINATOR, &fee_mint)?;
+    let old_mint = ctx.accounts.config.fee_mint;
+    ctx.accounts.config.fee_mint = fee_mint;
+    
+    emit!(FeeMintUpdated {
+        old_mint,
+        new_mint: fee_mint,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn withdraw_fee_tokens(ctx: Context<WithdrawFeeTokens>, amount: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::WithdrawFeeTokens::DISCRIMINATOR, &amount)?;
+    require!(amount > 0 && amount <= ctx.accounts.fee_token_vault.amount, ErrorCode::InsufficientFees);
+    
+    let signer_seeds: &[&[u8]] = &[b"fee_vault", &[ctx.accounts.fee_vault.bump]];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.fee_token_vault.to_account_info(),
+                mint: ctx.accounts.fee_mint.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.fee_vault.to_account_info(),
+            },
+            &[signer_seeds]
+        ),
+        amount,
+        ctx.accounts.fee_mint.decimals
+    )?;
+    ctx.accounts.fee_vault.total_token_withdrawn += amount;
+    
+    emit!(FeeTokensWithdrawn {
+        mint: ctx.accounts.fee_mint.key(),
+        amount,
+        destination: ctx.accounts.destination.key(),
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitFeeVault<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"fee_vault"], 
+        bump, 
+        payer = payer, 
+        space = 8 + 8 + 8 + 1 + 8 + 8 // discriminator + collected + withdrawn + bump + token collected + token withdrawn
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, destination: Pubkey)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    /// CHECK: receives the withdrawn lamports
+    #[account(mut, address = destination)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitFeeTokenVault<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init, 
+        payer = payer, 
+        associated_token::mint = fee_mint, 
+        associated_token::authority = fee_vault, 
+        associated_token::token_program = token_program
+    )]
+    pub fee_token_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFeeTokens<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    // Any mint the vault has collected, not just the current Config.fee_mint
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut, 
+        associated_token::mint = fee_mint, 
+        associated_token::authority = fee_vault, 
+        associated_token::token_program = token_program
+    )]
+    pub fee_token_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = fee_mint)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Fee accounts shared by the attestation instructions; lamport fees only need
+// fee_vault, SPL fees (Config.fee_mint set) need the rest as well
+#[derive(Accounts)]
+pub struct FeePayment<'info> {
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub payer_fee_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub fee_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
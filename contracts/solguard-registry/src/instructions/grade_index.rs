@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn init_grade_index_page(ctx: Context<InitGradeIndexPage>, grade: u8, page: u32) -> Result<()> {
+    require!(grade < GRADE_LEVELS_EXTENDED, ErrorCode::InvalidGrade);
+    require!(page == 0 || ctx.accounts.previous.is_some(), ErrorCode::GradeIndexPageOutOfOrder);
+    
+    let p = &mut ctx.accounts.index_page;
+    p.grade = grade;
+    p.page = page;
+    p.bump = ctx.bumps.index_page;
+    
+    Ok(())
+}
+
+pub fn sync_grade_index(ctx: Context<SyncGradeIndex>) -> Result<()> {
+    let mint = ctx.accounts.mint.key();
+    let grade = ctx.accounts.attestation.load()?.live_grade();
+    let entry = &mut ctx.accounts.entry;
+    if entry.mint == Pubkey::default() {
+        entry.mint = mint;
+        entry.bump = ctx.bumps.entry;
+    }
+    update_grade_index(
+        entry, 
+        &mut ctx.accounts.grade_index, 
+        mint, 
+        ctx.accounts.attestation.key(), 
+        true, 
+        grade
+    )
+}
+
+/// Lists `mint` on `pages.to` for `grade`, unlisting it from `pages.from` first,
+/// or only unlists it when `grade` is None. Writes for another attestation than
+/// the one the entry tracks are ignored unless `latest` says it is the mint's
+/// newest.
+fn update_grade_index(
+    entry: &mut GradeIndexEntry, 
+    pages: &mut GradeIndexPages, 
+    mint: Pubkey, 
+    attestation: Pubkey, 
+    latest: bool, 
+    grade: Option<u8>
+) -> Result<()> {
+    if !latest && entry.attestation != attestation {
+        return Ok(());
+    }
+    entry.attestation = attestation;
+    let listed = entry.listed.then_some(entry.grade);
+    if listed == grade {
+        return Ok(());
+    }
+    
+    if entry.listed {
+        let from = pages.from.as_deref_mut().ok_or(ErrorCode::GradeIndexMismatch)?;
+        require!(from.grade == entry.grade && from.page == entry.page, ErrorCode::GradeIndexMismatch);
+        require!(from.remove(&mint), ErrorCode::GradeIndexMismatch);
+        entry.listed = false;
+    }
+    if let Some(grade) = grade {
+        let to = pages.to.as_deref_mut().ok_or(ErrorCode::GradeIndexMismatch)?;
+        require!(to.grade == grade, ErrorCode::GradeIndexMismatch);
+        require!(to.mints.len() < GRADE_INDEX_PAGE_LEN, ErrorCode::GradeIndexPageFull);
+        to.mints.push(mint);
+        entry.grade = grade;
+        entry.page = to.page;
+        entry.listed = true;
+    }
+    
+    emit!(GradeIndexUpdated {
+        mint,
+        from_grade: listed,
+        to_grade: grade,
+        page: entry.page,
+    });
+    Ok(())
+}
+
+/// update_grade_index after a write: lists the attestation at `attestation` when
+/// `latest` points at it, creating the mint's entry (whose PDA bump is `bump`)
+/// on its first write.
+pub(crate) fn index_attestation(
+    entry: &mut GradeIndexEntry, 
+    bump: u8, 
+    pages: &mut GradeIndexPages, 
+    a: &Attestation, 
+    attestation: Pubkey, 
+    latest: &LatestAttestation
+) -> Result<()> {
+    if entry.mint == Pubkey::default() {
+        entry.mint = a.mint;
+        entry.bump = bump;
+    }
+    update_grade_index(entry, pages, a.mint, attestation, latest.attestation == attestation, a.live_grade())
+}
+
+/// update_grade_index for the revocation paths, which take the mint's
+/// GradeIndexEntry PDA unconditionally so a caller cannot leave a revoked
+/// attestation listed. A mint without an entry was never listed.
+pub(crate) fn update_grade_index_at(
+    info: &AccountInfo, 
+    pages: &mut GradeIndexPages, 
+    mint: Pubkey, 
+    attestation: Pubkey, 
+    grade: Option<u8>
+) -> Result<()> {
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    let mut entry = load_program_account::<GradeIndexEntry>(info)?;
+    update_grade_index(&mut entry, pages, mint, attestation, false, grade)?;
+    store_program_account(info, &entry)
+}
+
+// Index pages a grade change moves the mint between; `from` is the page its
+// GradeIndexEntry points at, `to` any page of the new grade with room
+#[derive(Accounts)]
+pub struct GradeIndexPages<'info> {
+    #[account(mut)]
+    pub from: Option<Account<'info, GradeIndexPage>>,
+    #[account(mut)]
+    pub to: Option<Account<'info, GradeIndexPage>>,
+}
+
+#[derive(Accounts)]
+#[instruction(grade: u8, page: u32)]
+pub struct InitGradeIndexPage<'info> {
+    #[account(
+        init, 
+        seeds = [b"grade_index".as_ref(), &[grade], &page.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_INDEX_PAGE_SPACE
+    )]
+    pub index_page: Account<'info, GradeIndexPage>,
+    // Page `page - 1`, required for every page but the first
+    #[account(seeds = [b"grade_index".as_ref(), &[grade], &page.saturating_sub(1).to_le_bytes()], bump = previous.bump)]
+    pub previous: Option<Account<'info, GradeIndexPage>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncGradeIndex<'info> {
+    /// CHECK: only the key is used
+    pub mint: UncheckedAccount<'info>,
+    #[account(seeds = [b"latest", mint.key().as_ref()], bump = latest.bump)]
+    pub latest: Account<'info, LatestAttestation>,
+    #[account(address = latest.attestation)]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_index_entry", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_INDEX_ENTRY_SPACE
+    )]
+    pub entry: Account<'info, GradeIndexEntry>,
+    pub grade_index: GradeIndexPages<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A GradeIndexPage account owned by the program, leaked so it lives as long as the test
+    fn page(grade: u8, page: u32, mints: Vec<Pubkey>) -> Account<'static, GradeIndexPage> {
+        let mut data = Vec::with_capacity(GRADE_INDEX_PAGE_SPACE);
+        GradeIndexPage { grade, page, mints, bump: 255 }.try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())), 
+            false, 
+            true, 
+            Box::leak(Box::new(0)), 
+            data.leak(), 
+            &crate::ID, 
+            false, 
+            0
+        );
+        Account::try_from(&info).unwrap()
+    }
+
+    fn pages(from: Option<Account<'static, GradeIndexPage>>, to: Option<Account<'static, GradeIndexPage>>) -> GradeIndexPages<'static> {
+        GradeIndexPages { from, to }
+    }
+
+    fn listed(mint: Pubkey, attestation: Pubkey, grade: u8, page: u32) -> GradeIndexEntry {
+        GradeIndexEntry { mint, attestation, grade, page, listed: true, bump: 255 }
+    }
+
+    #[test]
+    fn update_grade_index_lists_unlisted_mint() {
+        let (mint, attestation) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = GradeIndexEntry { mint, ..Default::default() };
+        let mut pages = pages(None, Some(page(GRADE_GREEN, 2, vec![])));
+        
+        update_grade_index(&mut entry, &mut pages, mint, attestation, true, Some(GRADE_GREEN)).unwrap();
+        assert_eq!(pages.to.as_ref().unwrap().mints, vec![mint]);
+        assert!(entry.listed);
+        assert_eq!((entry.attestation, entry.grade, entry.page), (attestation, GRADE_GREEN, 2));
+    }
+
+    #[test]
+    fn update_grade_index_moves_between_grades() {
+        let (mint, attestation, other) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = listed(mint, attestation, GRADE_GREEN, 0);
+        let mut pages = pages(Some(page(GRADE_GREEN, 0, vec![other, mint])), Some(page(GRADE_RED, 1, vec![])));
+        
+        update_grade_index(&mut entry, &mut pages, mint, attestation, false, Some(GRADE_RED)).unwrap();
+        assert_eq!(pages.from.as_ref().unwrap().mints, vec![other]);
+        assert_eq!(pages.to.as_ref().unwrap().mints, vec![mint]);
+        assert_eq!((entry.grade, entry.page, entry.listed), (GRADE_RED, 1, true));
+    }
+
+    #[test]
+    fn update_grade_index_unlists_without_grade() {
+        let (mint, attestation) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = listed(mint, attestation, GRADE_YELLOW, 0);
+        let mut pages = pages(Some(page(GRADE_YELLOW, 0, vec![mint])), None);
+        
+        update_grade_index(&mut entry, &mut pages, mint, attestation, false, None).unwrap();
+        assert!(pages.from.as_ref().unwrap().mints.is_empty());
+        assert!(!entry.listed);
+    }
+
+    #[test]
+    fn update_grade_index_same_grade_needs_no_pages() {
+        let (mint, attestation) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = listed(mint, attestation, GRADE_GREEN, 0);
+        
+        update_grade_index(&mut entry, &mut pages(None, None), mint, attestation, false, Some(GRADE_GREEN)).unwrap();
+        assert!(entry.listed);
+    }
+
+    #[test]
+    fn update_grade_index_ignores_other_attestation_unless_latest() {
+        let (mint, attestation, older) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut entry = listed(mint, attestation, GRADE_GREEN, 0);
+        
+        update_grade_index(&mut entry, &mut pages(None, None), mint, older, false, None).unwrap();
+        assert_eq!(entry.attestation, attestation);
+        assert!(entry.listed);
+        
+        let mut pages = pages(Some(page(GRADE_GREEN, 0, vec![mint])), None);
+        update_grade_index(&mut entry, &mut pages, mint, older, true, None).unwrap();
+        assert_eq!(entry.attestation, older);
+        assert!(!entry.listed);
+    }
+
+    #[test]
+    fn update_grade_index_rejects_wrong_pages() {
+        let (mint, attestation) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // `from` is not the page the entry points at
+        let mut entry = listed(mint, attestation, GRADE_GREEN, 0);
+        let mut wrong_from = pages(Some(page(GRADE_GREEN, 1, vec![mint])), Some(page(GRADE_RED, 0, vec![])));
+        let err = update_grade_index(&mut entry, &mut wrong_from, mint, attestation, false, Some(GRADE_RED)).unwrap_err();
+        assert_eq!(err, ErrorCode::GradeIndexMismatch.into());
+        
+        // `to` holds another grade
+        let mut entry = GradeIndexEntry { mint, ..Default::default() };
+        let mut wrong_to = pages(None, Some(page(GRADE_YELLOW, 0, vec![])));
+        let err = update_grade_index(&mut entry, &mut wrong_to, mint, attestation, true, Some(GRADE_RED)).unwrap_err();
+        assert_eq!(err, ErrorCode::GradeIndexMismatch.into());
+        
+        // `to` missing
+        let err = update_grade_index(&mut entry, &mut pages(None, None), mint, attestation, true, Some(GRADE_RED)).unwrap_err();
+        assert_eq!(err, ErrorCode::GradeIndexMismatch.into());
+    }
+
+    #[test]
+    fn update_grade_index_rejects_full_page() {
+        let (mint, attestation) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let full = (0..GRADE_INDEX_PAGE_LEN).map(|_| Pubkey::new_unique()).collect();
+        let mut entry = GradeIndexEntry { mint, ..Default::default() };
+        let mut pages = pages(None, Some(page(GRADE_GREEN, 0, full)));
+        
+        let err = update_grade_index(&mut entry, &mut pages, mint, attestation, true, Some(GRADE_GREEN)).unwrap_err();
+        assert_eq!(err, ErrorCode::GradeIndexPageFull.into());
+    }
+}
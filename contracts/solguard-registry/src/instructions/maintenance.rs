@@ -0,0 +1,412 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn migrate_attestation(ctx: Context<MigrateAttestation>, from_version: u16, to_version: u16) -> Result<()> {
+    let cfg = &ctx.accounts.config;
+    require!(!cfg.paused, ErrorCode::RegistryPaused);
+    require!(cfg.is_active_version(to_version), ErrorCode::InvalidRulesetVersion);
+    
+    let old = *ctx.accounts.old_attestation.load()?;
+    require!(!old.is_revoked(), ErrorCode::AttestationRevoked);
+    
+    let mut a = ctx.accounts.new_attestation.load_init()?;
+    *a = old;
+    a.ruleset_version = to_version;
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.payer = ctx.accounts.signer.key();
+    a.superseded = 0;
+    a.supersedes = ctx.accounts.old_attestation.key();
+    ctx.accounts.latest.bump = ctx.bumps.latest;
+    // The old PDA is closed below, so a pointer at it moves to the new one
+    // whatever the version order
+    if ctx.accounts.latest.attestation == ctx.accounts.old_attestation.key() {
+        ctx.accounts.latest.clear();
+    }
+    if let Some(superseded) = record_latest_attestation(
+        &mut ctx.accounts.latest, 
+        ctx.accounts.superseded.as_deref(), 
+        &mut a, 
+        ctx.accounts.new_attestation.key()
+    )? {
+        emit!(superseded);
+    }
+    
+    emit!(AttestationMigrated {
+        mint: a.mint,
+        from_version,
+        to_version,
+        migrated_by: ctx.accounts.signer.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn migrate_account(ctx: Context<MigrateAccount>, kind: AccountKind) -> Result<()> {
+    let info = ctx.accounts.account.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system = ctx.accounts.system_program.to_account_info();
+    
+    let (from_version, to_version) = match kind {
+        AccountKind::Config => migrate_borsh_account::<Config>(&info, &payer, &system, CONFIG_SPACE)?,
+        AccountKind::Oracle => migrate_borsh_account::<Oracle>(&info, &payer, &system, ORACLE_SPACE)?,
+        AccountKind::Attestation => migrate_attestation_layout(&info, &payer, &system)?,
+        AccountKind::RegistryStats => migrate_borsh_account::<RegistryStats>(&info, &payer, &system, REGISTRY_STATS_SPACE)?,
+    };
+    
+    emit!(AccountMigrated {
+        account: info.key(),
+        kind,
+        from_version,
+        to_version,
+    });
+    
+    Ok(())
+}
+
+pub fn crank_expire_attestations<'info>(ctx: Context<'_, '_, '_, 'info, CrankExpire<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cranker = ctx.accounts.cranker.key();
+    let mut expired = 0u64;
+    for info in ctx.remaining_accounts {
+        let loader = AccountLoader::<Attestation>::try_from(info)?;
+        let mut a = loader.load_mut()?;
+        if a.is_revoked() || a.is_expired_flagged() || !a.is_expired(now) {
+            continue;
+        }
+        a.expired = 1;
+        expired += 1;
+        
+        emit!(AttestationExpired {
+            mint: a.mint,
+            ruleset_version: a.ruleset_version,
+            valid_until: a.valid_until,
+            cranker,
+        });
+    }
+    
+    let reward = match ctx.accounts.fee_vault.as_mut() {
+        Some(vault) => {
+            let vault_info = vault.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+            let available = vault_info.lamports().saturating_sub(rent_floor);
+            let reward = ctx.accounts.config.crank_reward.saturating_mul(expired).min(available);
+            if reward > 0 {
+                move_lamports(&vault_info, &ctx.accounts.cranker.to_account_info(), reward)?;
+                vault.total_withdrawn += reward;
+            }
+            reward
+        }
+        None => 0,
+    };
+    
+    emit!(ExpiryCranked {
+        expired,
+        reward,
+        cranker,
+    });
+    
+    Ok(())
+}
+
+pub fn snapshot_epoch(ctx: Context<SnapshotEpoch>, epoch: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(epoch == clock.epoch, ErrorCode::InvalidSnapshotEpoch);
+    
+    let stats = &ctx.accounts.registry_stats;
+    let snap = &mut ctx.accounts.snapshot;
+    snap.epoch = epoch;
+    snap.live_by_grade = stats.live_by_grade;
+    snap.live_total = stats.live_total();
+    snap.average_score_bps = stats.average_score_bps();
+    snap.attestation_count = stats.attestation_count;
+    snap.revocation_count = stats.revocation_count;
+    snap.active_oracles = stats.active_oracles;
+    snap.slot = clock.slot;
+    snap.taken_at = clock.unix_timestamp;
+    snap.taken_by = ctx.accounts.payer.key();
+    snap.bump = ctx.bumps.snapshot;
+    
+    emit!(EpochSnapshotTaken {
+        epoch,
+        live_by_grade: snap.live_by_grade,
+        live_total: snap.live_total,
+        average_score_bps: snap.average_score_bps,
+        taken_by: snap.taken_by,
+    });
+    
+    Ok(())
+}
+
+pub fn close_attestation(ctx: Context<CloseAttestation>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let a = ctx.accounts.attestation.load()?;
+    if authority != a.payer {
+        require_admin(&ctx.accounts.config, &authority, ctx.accounts.admin_set.as_deref(), ctx.remaining_accounts)?;
+        let audit_log = ctx.accounts.audit_log.as_ref().ok_or(ErrorCode::AuditLogRequired)?;
+        record_admin_action(audit_log, &authority, instruction::CloseAttestation::DISCRIMINATOR, &())?;
+    }
+    
+    let superseded = a.ruleset_version < ctx.accounts.config.ruleset_version;
+    require!(a.is_revoked() || superseded, ErrorCode::AttestationStillLive);
+    require!(!a.is_challenged(), ErrorCode::AttestationUnderChallenge);
+    if let (Some(registry_stats), Some((score, grade))) = (ctx.accounts.registry_stats.as_mut(), a.live_score()) {
+        registry_stats.record_close(score, grade);
+    }
+    clear_latest_attestation(&ctx.accounts.latest, ctx.accounts.attestation.key())?;
+    
+    emit!(AttestationClosed {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        payer: a.payer,
+        closed_by: authority,
+    });
+    
+    Ok(())
+}
+
+pub fn set_crank_reward(ctx: Context<OnlyAdmin>, reward: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetCrankReward::DISCRIMINATOR, &reward)?;
+    let old_reward = ctx.accounts.config.crank_reward;
+    ctx.accounts.config.crank_reward = reward;
+    
+    emit!(CrankRewardUpdated {
+        old_reward,
+        new_reward: reward,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+/// Grows `info` to `space` bytes (zero-filled), topping its rent up from `payer`.
+fn grow_account<'info>(
+    info: &AccountInfo<'info>, 
+    payer: &AccountInfo<'info>, 
+    system_program: &AccountInfo<'info>, 
+    space: usize
+) -> Result<()> {
+    if info.data_len() >= space {
+        return Ok(());
+    }
+    let shortfall = Rent::get()?.minimum_balance(space).saturating_sub(info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(), 
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                }
+            ), 
+            shortfall
+        )?;
+    }
+    info.realloc(space, true)?;
+    Ok(())
+}
+
+/// Upgrades a borsh account whose older layouts are prefixes of the current one:
+/// the zero-filled tail decodes as defaults for the appended fields.
+fn migrate_borsh_account<'info, T>(
+    info: &AccountInfo<'info>, 
+    payer: &AccountInfo<'info>, 
+    system_program: &AccountInfo<'info>, 
+    space: usize
+) -> Result<(u8, u8)>
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator + VersionedLayout,
+{
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidRemainingAccount);
+    require!(info.try_borrow_data()?[..8] == T::discriminator(), ErrorCode::AccountKindMismatch);
+    
+    grow_account(info, payer, system_program, space)?;
+    let mut account: T = load_program_account(info)?;
+    let from = *account.layout_version();
+    *account.layout_version() = T::LAYOUT_VERSION;
+    store_program_account(info, &account)?;
+    
+    Ok((from, T::LAYOUT_VERSION))
+}
+
+/// Attestations written before the zero-copy conversion are borsh-encoded and
+/// at most LEGACY_ATTESTATION_SPACE long; they are re-encoded into the fixed layout.
+/// Zero-copy accounts from before `frozen`, `metadata_hash`, the mint snapshot,
+/// `risk_flags`, the authority snapshot, `category_proofs` or `flags` only need the zero-filled tail.
+fn migrate_attestation_layout<'info>(
+    info: &AccountInfo<'info>, 
+    payer: &AccountInfo<'info>, 
+    system_program: &AccountInfo<'info>
+) -> Result<(u8, u8)> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidRemainingAccount);
+    require!(info.try_borrow_data()?[..8] == Attestation::discriminator(), ErrorCode::AccountKindMismatch);
+    
+    let legacy = if info.data_len() <= LEGACY_ATTESTATION_SPACE {
+        // Even older accounts lack trailing fields; zero-pad them like migrate_borsh_account
+        let mut body = [0u8; LEGACY_ATTESTATION_SPACE - 8];
+        let data = info.try_borrow_data()?;
+        let len = (data.len() - 8).min(body.len());
+        body[..len].copy_from_slice(&data[8..8 + len]);
+        Some(LegacyAttestation::deserialize(&mut &body[..])?)
+    } else {
+        None
+    };
+    
+    grow_account(info, payer, system_program, ATTESTATION_SPACE)?;
+    let loader = AccountLoader::<Attestation>::try_from(info)?;
+    let mut a = loader.load_mut()?;
+    let from = if legacy.is_some() { 0 } else { a.version };
+    if let Some(legacy) = legacy {
+        *a = legacy.into();
+    }
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    
+    Ok((from, ATTESTATION_LAYOUT_VERSION))
+}
+
+/// Clears the LatestAttestation at `info` when it points at `attestation`, which
+/// is being closed. Mints attested before LatestAttestation existed have none.
+fn clear_latest_attestation(info: &AccountInfo, attestation: Pubkey) -> Result<()> {
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    let mut latest = load_program_account::<LatestAttestation>(info)?;
+    if latest.attestation == attestation {
+        latest.clear();
+        store_program_account(info, &latest)?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(from_version: u16, to_version: u16)]
+pub struct MigrateAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"ruleset_compat", &from_version.to_le_bytes(), &to_version.to_le_bytes()], 
+        bump = compatibility.bump
+    )]
+    pub compatibility: Account<'info, RulesetCompatibility>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        has_one = payer,
+        close = payer,
+        seeds = [b"attest", mint.key().as_ref(), &from_version.to_le_bytes()], 
+        bump
+    )]
+    pub old_attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init, 
+        seeds = [b"attest", mint.key().as_ref(), &to_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = ATTESTATION_SPACE
+    )]
+    pub new_attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"latest", mint.key().as_ref()], 
+        bump, 
+        payer = signer, 
+        space = LATEST_ATTESTATION_SPACE
+    )]
+    pub latest: Box<Account<'info, LatestAttestation>>,
+    /// CHECK: the attestation `latest` points at, loaded by record_latest_attestation;
+    /// required when the migration moves the mint to a newer ruleset version
+    #[account(mut, address = latest.attestation @ ErrorCode::SupersededAttestationMismatch)]
+    pub superseded: Option<UncheckedAccount<'info>>,
+    /// CHECK: rent payer of the old attestation, receives its lamports
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: owner and discriminator checked against `kind` in the handler
+    #[account(mut)]
+    pub account: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankExpire<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    // Only needed to collect Config.crank_reward
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Option<Account<'info, FeeVault>>,
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SnapshotEpoch<'info> {
+    #[account(seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Account<'info, RegistryStats>,
+    #[account(
+        init, 
+        seeds = [b"snapshot", &epoch.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = EPOCH_SNAPSHOT_SPACE
+    )]
+    pub snapshot: Account<'info, EpochSnapshot>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseAttestation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        has_one = payer,
+        close = payer,
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    /// CHECK: the mint's LatestAttestation PDA, absent for mints attested before it
+    /// was tracked; always required so a pointer at the closed attestation is cleared
+    #[account(mut, seeds = [b"latest", mint.key().as_ref()], bump)]
+    pub latest: UncheckedAccount<'info>,
+    // Only needed when an admin closes an attestation they did not pay for
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: Option<AccountLoader<'info, AuditLog>>,
+    /// CHECK: original rent payer, receives the lamports
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
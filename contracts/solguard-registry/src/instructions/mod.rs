@@ -0,0 +1,39 @@
+mod admin;
+mod attest;
+mod bridge;
+mod compressed;
+mod consensus;
+mod consumer;
+mod dispute;
+mod downgrade;
+mod fees;
+mod grade_index;
+mod maintenance;
+mod oracle;
+mod report;
+mod revoke;
+mod risk;
+mod ruleset;
+mod subject;
+mod watch;
+mod zk;
+
+pub use admin::*;
+pub use attest::*;
+pub use bridge::*;
+pub use compressed::*;
+pub use consensus::*;
+pub use consumer::*;
+pub use dispute::*;
+pub use downgrade::*;
+pub use fees::*;
+pub use grade_index::*;
+pub use maintenance::*;
+pub use oracle::*;
+pub use report::*;
+pub use revoke::*;
+pub use risk::*;
+pub use ruleset::*;
+pub use subject::*;
+pub use watch::*;
+pub use zk::*;
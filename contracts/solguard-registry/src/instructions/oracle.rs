@@ -0,0 +1,539 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn add_oracle(ctx: Context<UpdateOracle>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::AddOracle::DISCRIMINATOR, &())?;
+    
+    // Registration deposit is only charged when the Oracle PDA is first created
+    if ctx.accounts.oracle.depositor == Pubkey::default() {
+        let deposit = ctx.accounts.config.oracle_deposit;
+        if deposit > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.oracle.to_account_info(),
+                    },
+                ),
+                deposit,
+            )?;
+        }
+        ctx.accounts.oracle.deposit = deposit;
+        ctx.accounts.oracle.depositor = ctx.accounts.payer.key();
+    }
+    
+    let o = &mut ctx.accounts.oracle;
+    let registry_stats = &mut ctx.accounts.registry_stats;
+    registry_stats.bump = ctx.bumps.registry_stats;
+    registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    if !o.active {
+        registry_stats.active_oracles += 1;
+    }
+    o.bump = ctx.bumps.oracle;
+    o.version = ORACLE_LAYOUT_VERSION;
+    o.active = true;
+    o.deactivated_at = 0;
+    if o.weight == 0 {
+        o.weight = 1;
+    }
+    
+    emit!(OracleAdded {
+        oracle: ctx.accounts.oracle_key.key(),
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn remove_oracle(ctx: Context<UpdateOracle>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::RemoveOracle::DISCRIMINATOR, &())?;
+    let o = &mut ctx.accounts.oracle;
+    if o.active {
+        let registry_stats = &mut ctx.accounts.registry_stats;
+        registry_stats.bump = ctx.bumps.registry_stats;
+        registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+        registry_stats.active_oracles = registry_stats.active_oracles.saturating_sub(1);
+    }
+    o.active = false;
+    o.deactivated_at = Clock::get()?.unix_timestamp;
+    
+    emit!(OracleRemoved {
+        oracle: ctx.accounts.oracle_key.key(),
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn close_oracle(ctx: Context<CloseOracle>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::CloseOracle::DISCRIMINATOR, &())?;
+    let o = &ctx.accounts.oracle;
+    require!(!o.active, ErrorCode::OracleStillActive);
+    require!(o.stake == 0 && o.unbonding == 0, ErrorCode::OracleStakeOutstanding);
+    require!(
+        Clock::get()?.unix_timestamp >= o.deactivated_at + ctx.accounts.config.unbonding_period, 
+        ErrorCode::UnbondingNotElapsed
+    );
+    
+    emit!(OracleClosed {
+        oracle: ctx.accounts.oracle_key.key(),
+        deposit: o.deposit,
+        depositor: o.depositor,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_oracle_deposit(ctx: Context<OnlyAdmin>, deposit: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetOracleDeposit::DISCRIMINATOR, &deposit)?;
+    let old_deposit = ctx.accounts.config.oracle_deposit;
+    ctx.accounts.config.oracle_deposit = deposit;
+    
+    emit!(OracleDepositUpdated {
+        old_deposit,
+        new_deposit: deposit,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_unbonding_period(ctx: Context<OnlyAdmin>, period: i64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetUnbondingPeriod::DISCRIMINATOR, &period)?;
+    require!(period >= 0, ErrorCode::InvalidUnbondingPeriod);
+    let old_period = ctx.accounts.config.unbonding_period;
+    ctx.accounts.config.unbonding_period = period;
+    
+    emit!(UnbondingPeriodUpdated {
+        old_period,
+        new_period: period,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn oracle_heartbeat(ctx: Context<OracleHeartbeat>) -> Result<()> {
+    ctx.accounts.oracle.last_seen_slot = Clock::get()?.slot;
+    Ok(())
+}
+
+pub fn stake_oracle(ctx: Context<StakeOracle>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidStakeAmount);
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.oracle_key.to_account_info(),
+                to: ctx.accounts.oracle.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let o = &mut ctx.accounts.oracle;
+    o.stake = o.stake.checked_add(amount).ok_or(ErrorCode::InvalidStakeAmount)?;
+    
+    emit!(OracleStaked {
+        oracle: ctx.accounts.oracle_key.key(),
+        amount,
+        total_stake: o.stake,
+    });
+    
+    Ok(())
+}
+
+pub fn unstake_oracle(ctx: Context<UnstakeOracle>, amount: u64) -> Result<()> {
+    let o = &mut ctx.accounts.oracle;
+    require!(amount > 0 && amount <= o.stake, ErrorCode::InsufficientStake);
+    
+    o.stake -= amount;
+    o.unbonding += amount;
+    o.unbonding_ends_at = Clock::get()?.unix_timestamp + ctx.accounts.config.unbonding_period;
+    
+    emit!(OracleUnstaked {
+        oracle: ctx.accounts.oracle_key.key(),
+        amount,
+        unbonding_ends_at: o.unbonding_ends_at,
+    });
+    
+    Ok(())
+}
+
+pub fn withdraw_unbonded(ctx: Context<UnstakeOracle>) -> Result<()> {
+    let o = &mut ctx.accounts.oracle;
+    require!(o.unbonding > 0, ErrorCode::InsufficientStake);
+    require!(Clock::get()?.unix_timestamp >= o.unbonding_ends_at, ErrorCode::UnbondingNotElapsed);
+    
+    let amount = o.unbonding;
+    o.unbonding = 0;
+    move_lamports(&o.to_account_info(), &ctx.accounts.oracle_key.to_account_info(), amount)?;
+    
+    emit!(OracleStakeWithdrawn {
+        oracle: ctx.accounts.oracle_key.key(),
+        amount,
+    });
+    
+    Ok(())
+}
+
+pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64) -> Result<()> {
+    authorize_full_admin_action(&ctx, instruction::SlashOracle::DISCRIMINATOR, &amount)?;
+    let o = &mut ctx.accounts.oracle;
+    require!(amount > 0 && amount <= o.stake + o.unbonding, ErrorCode::InsufficientStake);
+    
+    // Bonded stake is slashed first, then anything still unbonding
+    let from_stake = amount.min(o.stake);
+    o.stake -= from_stake;
+    o.unbonding -= amount - from_stake;
+    move_lamports(&o.to_account_info(), &ctx.accounts.treasury.to_account_info(), amount)?;
+    
+    emit!(OracleSlashed {
+        oracle: ctx.accounts.oracle_key.key(),
+        amount,
+        treasury: ctx.accounts.treasury.key(),
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_oracle_metadata(
+    ctx: Context<SetOracleMetadata>, 
+    name: String, 
+    operator_uri: String, 
+    contact_hash: [u8; 32]
+) -> Result<()> {
+    require!(name.len() <= MAX_ORACLE_NAME_LEN, ErrorCode::OracleNameTooLong);
+    require!(operator_uri.len() <= MAX_ORACLE_URI_LEN, ErrorCode::OracleUriTooLong);
+    
+    let now = Clock::get()?.unix_timestamp;
+    let p = &mut ctx.accounts.profile;
+    if p.created_at == 0 {
+        p.oracle = ctx.accounts.oracle_key.key();
+        p.created_at = now;
+        p.bump = ctx.bumps.profile;
+    }
+    p.name = name;
+    p.operator_uri = operator_uri;
+    p.contact_hash = contact_hash;
+    p.updated_at = now;
+    
+    emit!(OracleMetadataUpdated {
+        oracle: p.oracle,
+        name: p.name.clone(),
+        operator_uri: p.operator_uri.clone(),
+        contact_hash,
+    });
+    
+    Ok(())
+}
+
+pub fn set_oracle_weight(ctx: Context<ManageOracle>, weight: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::SetOracleWeight::DISCRIMINATOR, &weight)?;
+    require!(weight >= 1, ErrorCode::InvalidOracleWeight);
+    let old_weight = ctx.accounts.oracle.weight;
+    ctx.accounts.oracle.weight = weight;
+    
+    emit!(OracleWeightUpdated {
+        oracle: ctx.accounts.oracle_key.key(),
+        old_weight,
+        new_weight: weight,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_oracle_scoped(ctx: Context<ManageOracle>, scoped: bool) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::SetOracleScoped::DISCRIMINATOR, &scoped)?;
+    ctx.accounts.oracle.scoped = scoped;
+    
+    emit!(OracleScopeUpdated {
+        oracle: ctx.accounts.oracle_key.key(),
+        scoped,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_oracle_evm_address(ctx: Context<ManageOracle>, evm_address: [u8; 20]) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::SetOracleEvmAddress::DISCRIMINATOR, &evm_address)?;
+    ctx.accounts.oracle.evm_address = evm_address;
+    
+    emit!(OracleEvmAddressUpdated {
+        oracle: ctx.accounts.oracle_key.key(),
+        evm_address,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn add_scope_mint(ctx: Context<AddScopeMint>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::AddScopeMint::DISCRIMINATOR, &())?;
+    let e = &mut ctx.accounts.scope_entry;
+    e.oracle = ctx.accounts.oracle_key.key();
+    e.mint = ctx.accounts.mint.key();
+    e.bump = ctx.bumps.scope_entry;
+    
+    emit!(ScopeMintAdded {
+        oracle: e.oracle,
+        mint: e.mint,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn remove_scope_mint(ctx: Context<RemoveScopeMint>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::OracleManager, instruction::RemoveScopeMint::DISCRIMINATOR, &())?;
+    
+    emit!(ScopeMintRemoved {
+        oracle: ctx.accounts.scope_entry.oracle,
+        mint: ctx.accounts.scope_entry.mint,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateOracle<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"oracle", oracle_key.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = ORACLE_SPACE
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"registry_stats"], 
+        bump, 
+        payer = payer, 
+        space = REGISTRY_STATS_SPACE
+    )]
+    pub registry_stats: Box<Account<'info, RegistryStats>>,
+    /// CHECK: Oracle pubkey (not necessarily a signer at creation)
+    pub oracle_key: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOracle<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        has_one = depositor,
+        close = depositor,
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: Oracle pubkey
+    pub oracle_key: UncheckedAccount<'info>,
+    /// CHECK: receives the deposit and rent back
+    #[account(mut)]
+    pub depositor: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOracle<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: Oracle pubkey
+    pub oracle_key: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddScopeMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    /// CHECK: Oracle pubkey
+    pub oracle_key: UncheckedAccount<'info>,
+    /// CHECK: token mint the oracle may attest
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init, 
+        seeds = [b"scope", oracle_key.key().as_ref(), mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 32 + 32 + 1 // discriminator + oracle + mint + bump
+    )]
+    pub scope_entry: Account<'info, ScopeEntry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveScopeMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        close = admin,
+        seeds = [b"scope", scope_entry.oracle.as_ref(), scope_entry.mint.as_ref()], 
+        bump = scope_entry.bump
+    )]
+    pub scope_entry: Account<'info, ScopeEntry>,
+}
+
+#[derive(Accounts)]
+pub struct OracleHeartbeat<'info> {
+    #[account(
+        mut, 
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeOracle<'info> {
+    #[account(
+        mut, 
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(mut)]
+    pub oracle_key: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeOracle<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut, 
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(mut)]
+    pub oracle_key: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashOracle<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: Oracle pubkey
+    pub oracle_key: UncheckedAccount<'info>,
+    /// CHECK: receives slashed lamports
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleMetadata<'info> {
+    #[account(
+        seeds = [b"oracle", oracle_key.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"oracle_profile", oracle_key.key().as_ref()], 
+        bump, 
+        payer = oracle_key, 
+        space = 8 + 32 + 4 + MAX_ORACLE_NAME_LEN + 4 + MAX_ORACLE_URI_LEN + 32 + 8 + 8 + 1 // discriminator + oracle + name + uri + contact + created + updated + bump
+    )]
+    pub profile: Account<'info, OracleProfile>,
+    #[account(mut)]
+    pub oracle_key: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn set_report_bond(ctx: Context<OnlyAdmin>, bond: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetReportBond::DISCRIMINATOR, &bond)?;
+    let old_bond = ctx.accounts.config.report_bond;
+    ctx.accounts.config.report_bond = bond;
+    
+    emit!(ReportBondUpdated {
+        old_bond,
+        new_bond: bond,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn submit_report(ctx: Context<SubmitReport>, evidence_hash: [u8; 32]) -> Result<()> {
+    let attestation = ctx.accounts.attestation.key();
+    let a = ctx.accounts.attestation.load()?;
+    require!(!a.is_revoked(), ErrorCode::AttestationRevoked);
+    
+    let bond = ctx.accounts.config.report_bond;
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.reporter.to_account_info(),
+                    to: ctx.accounts.report.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+    
+    let r = &mut ctx.accounts.report;
+    r.attestation = attestation;
+    r.mint = a.mint;
+    r.reporter = ctx.accounts.reporter.key();
+    r.bond = bond;
+    r.evidence_hash = evidence_hash;
+    r.reported_at = Clock::get()?.unix_timestamp;
+    r.status = ReportStatus::Open;
+    r.bump = ctx.bumps.report;
+    
+    emit!(RugReportSubmitted {
+        report: r.key(),
+        attestation: r.attestation,
+        mint: r.mint,
+        reporter: r.reporter,
+        bond,
+        evidence_hash,
+    });
+    
+    Ok(())
+}
+
+pub fn award_bounty(ctx: Context<AwardBounty>, bounty: u64) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::AwardBounty::DISCRIMINATOR, &bounty)?;
+    require!(ctx.accounts.report.status == ReportStatus::Open, ErrorCode::ReportNotOpen);
+    require!(ctx.accounts.attestation.load()?.is_revoked(), ErrorCode::NotRevoked);
+    
+    let vault_info = ctx.accounts.fee_vault.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(vault_info.data_len());
+    require!(bounty <= vault_info.lamports().saturating_sub(rent_floor), ErrorCode::InsufficientFees);
+    move_lamports(&vault_info, &ctx.accounts.reporter.to_account_info(), bounty)?;
+    ctx.accounts.fee_vault.total_withdrawn += bounty;
+    
+    let bond = ctx.accounts.report.bond;
+    move_lamports(&ctx.accounts.report.to_account_info(), &ctx.accounts.reporter.to_account_info(), bond)?;
+    
+    let r = &mut ctx.accounts.report;
+    r.bond = 0;
+    r.status = ReportStatus::Awarded;
+    
+    emit!(BountyAwarded {
+        report: r.key(),
+        attestation: r.attestation,
+        mint: r.mint,
+        reporter: r.reporter,
+        bond,
+        bounty,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn reject_report(ctx: Context<RejectReport>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RejectReport::DISCRIMINATOR, &())?;
+    require!(ctx.accounts.report.status == ReportStatus::Open, ErrorCode::ReportNotOpen);
+    
+    let bond = ctx.accounts.report.bond;
+    move_lamports(&ctx.accounts.report.to_account_info(), &ctx.accounts.treasury.to_account_info(), bond)?;
+    
+    let r = &mut ctx.accounts.report;
+    r.bond = 0;
+    r.status = ReportStatus::Rejected;
+    
+    emit!(RugReportRejected {
+        report: r.key(),
+        attestation: r.attestation,
+        reporter: r.reporter,
+        bond,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitReport<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init, 
+        seeds = [b"report", attestation.key().as_ref(), reporter.key().as_ref()], 
+        bump, 
+        payer = reporter, 
+        space = 8 + 32 + 32 + 32 + 8 + 32 + 8 + 1 + 1 // discriminator + attestation + mint + reporter + bond + evidence + reported + status + bump
+    )]
+    pub report: Account<'info, RugReport>,
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AwardBounty<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        has_one = attestation,
+        has_one = reporter,
+        seeds = [b"report", attestation.key().as_ref(), reporter.key().as_ref()], 
+        bump = report.bump
+    )]
+    pub report: Account<'info, RugReport>,
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(mut, seeds = [b"fee_vault"], bump = fee_vault.bump)]
+    pub fee_vault: Account<'info, FeeVault>,
+    /// CHECK: reporter recorded on the report
+    #[account(mut)]
+    pub reporter: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RejectReport<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"report", report.attestation.as_ref(), report.reporter.as_ref()], 
+        bump = report.bump
+    )]
+    pub report: Account<'info, RugReport>,
+    // Forfeited bonds go to the fee vault, withdrawable only through withdraw_fees
+    #[account(mut, seeds = [b"fee_vault"], bump = treasury.bump)]
+    pub treasury: Account<'info, FeeVault>,
+}
@@ -0,0 +1,383 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+
+use crate::auth::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn revoke_attestation(
+    ctx: Context<Revoke>, 
+    ruleset_version: u16, 
+    reason: RevocationReason, 
+    evidence_hash: Option<[u8; 32]>
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RevokeAttestation::DISCRIMINATOR, &(ruleset_version, reason, evidence_hash))?;
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_revoked(), ErrorCode::AlreadyRevoked);
+    
+    let evidence_hash = evidence_hash.unwrap_or_default();
+    a.revoke(reason, evidence_hash);
+    update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.revocation_count += 1)?;
+    if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+        registry_stats.record_revocation(a.score_bps, a.normalized_grade());
+    }
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    update_grade_index_at(
+        &ctx.accounts.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        a.mint, 
+        ctx.accounts.attestation.key(), 
+        None
+    )?;
+    
+    emit_cpi!(AttestationRevoked {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        admin: ctx.accounts.admin.key(),
+        reason,
+        evidence_hash,
+    });
+    emit_cpi!(AttestationRevokedV2::new(&a, RevocationSource::Admin, ctx.accounts.admin.key(), reason)?);
+    
+    Ok(())
+}
+
+pub fn set_revocation_quorum(ctx: Context<OnlyAdmin>, quorum: u8) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetRevocationQuorum::DISCRIMINATOR, &quorum)?;
+    let old_quorum = ctx.accounts.config.revocation_quorum;
+    ctx.accounts.config.revocation_quorum = quorum;
+    
+    emit!(RevocationQuorumUpdated {
+        old_quorum,
+        new_quorum: quorum,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn vote_revocation(
+    ctx: Context<VoteRevocation>, 
+    _ruleset_version: u16, 
+    reason: RevocationReason, 
+    evidence_hash: [u8; 32]
+) -> Result<()> {
+    let quorum = ctx.accounts.config.revocation_quorum;
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    require!(quorum > 0, ErrorCode::QuorumRevocationDisabled);
+    require!(ctx.accounts.oracle.active, ErrorCode::OracleInactive);
+    
+    let attestation = ctx.accounts.attestation.key();
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_revoked(), ErrorCode::AlreadyRevoked);
+    
+    let vote = &mut ctx.accounts.vote;
+    require!(vote.attested_at != a.attested_at, ErrorCode::DuplicateRevocationVote);
+    vote.attestation = attestation;
+    vote.oracle = ctx.accounts.signer.key();
+    vote.attested_at = a.attested_at;
+    vote.reason = reason;
+    vote.evidence_hash = evidence_hash;
+    vote.voted_at = Clock::get()?.unix_timestamp;
+    vote.bump = ctx.bumps.vote;
+    
+    let tally = &mut ctx.accounts.tally;
+    if tally.attested_at != a.attested_at {
+        tally.attestation = attestation;
+        tally.attested_at = a.attested_at;
+        tally.votes = 0;
+        tally.bump = ctx.bumps.tally;
+    }
+    tally.votes += 1;
+    
+    emit!(RevocationVoteCast {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        oracle: vote.oracle,
+        reason,
+        votes: tally.votes,
+        quorum,
+    });
+    
+    if tally.votes >= quorum {
+        a.revoke(reason, evidence_hash);
+        update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.revocation_count += 1)?;
+        if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+            registry_stats.record_revocation(a.score_bps, a.normalized_grade());
+        }
+        notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+        flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+        update_grade_index_at(
+            &ctx.accounts.grade_index_entry, 
+            &mut ctx.accounts.grade_index, 
+            a.mint, 
+            ctx.accounts.attestation.key(), 
+            None
+        )?;
+        
+        emit_cpi!(AttestationRevokedByQuorum {
+            mint: a.mint,
+            ruleset_version: a.ruleset_version,
+            votes: tally.votes,
+            reason,
+            evidence_hash,
+        });
+        emit_cpi!(AttestationRevokedV2::new(&a, RevocationSource::Quorum, vote.oracle, reason)?);
+    }
+    
+    Ok(())
+}
+
+pub fn reinstate_attestation(ctx: Context<Revoke>, ruleset_version: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::ReinstateAttestation::DISCRIMINATOR, &ruleset_version)?;
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(a.is_revoked(), ErrorCode::NotRevoked);
+    
+    let reason = a.revocation_reason;
+    a.clear_revocation();
+    update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.revocation_count = stats.revocation_count.saturating_sub(1))?;
+    if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+        registry_stats.record_reinstatement(a.score_bps, a.normalized_grade());
+    }
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    update_grade_index_at(
+        &ctx.accounts.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        a.mint, 
+        ctx.accounts.attestation.key(), 
+        a.live_grade()
+    )?;
+    
+    emit!(AttestationReinstated {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        previous_reason: reason,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn freeze_attestation(ctx: Context<Revoke>, ruleset_version: u16, evidence_hash: [u8; 32]) -> Result<()> {
+    authorize_guardian_action(&ctx, Role::Guardian, instruction::FreezeAttestation::DISCRIMINATOR, &(ruleset_version, evidence_hash))?;
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_frozen(), ErrorCode::AlreadyFrozen);
+    a.frozen = 1;
+    
+    emit!(AttestationFrozen {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        authority: ctx.accounts.admin.key(),
+        evidence_hash,
+    });
+    
+    Ok(())
+}
+
+pub fn unfreeze_attestation(ctx: Context<Revoke>, ruleset_version: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Guardian, instruction::UnfreezeAttestation::DISCRIMINATOR, &ruleset_version)?;
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(a.is_frozen(), ErrorCode::NotFrozen);
+    a.frozen = 0;
+    
+    emit!(AttestationUnfrozen {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        authority: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_own_attestation(ctx: Context<RevokeOwn>) -> Result<()> {
+    require!(!ctx.accounts.config.paused, ErrorCode::RegistryPaused);
+    let mut a = ctx.accounts.attestation.load_mut()?;
+    require!(!a.is_revoked(), ErrorCode::AlreadyRevoked);
+    require!(a.oracle_count <= 1, ErrorCode::ConsensusAttestation);
+    
+    a.revoke(RevocationReason::OracleError, [0; 32]);
+    update_oracle_stats(&ctx.accounts.oracle_stats, |stats| stats.revocation_count += 1)?;
+    if let Some(registry_stats) = ctx.accounts.registry_stats.as_mut() {
+        registry_stats.record_revocation(a.score_bps, a.normalized_grade());
+    }
+    notify_watchers(ctx.accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    flag_in_risk_bloom(&ctx.accounts.risk_bloom, &a.mint)?;
+    update_grade_index_at(
+        &ctx.accounts.grade_index_entry, 
+        &mut ctx.accounts.grade_index, 
+        a.mint, 
+        ctx.accounts.attestation.key(), 
+        None
+    )?;
+    
+    emit_cpi!(OwnAttestationRevoked {
+        mint: a.mint,
+        ruleset_version: a.ruleset_version,
+        oracle: ctx.accounts.signer.key(),
+    });
+    emit_cpi!(AttestationRevokedV2::new(&a, RevocationSource::Oracle, ctx.accounts.signer.key(), RevocationReason::OracleError)?);
+    
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct Revoke<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    /// CHECK: the oracle's OracleStats PDA, absent for oracles that attested before
+    /// stats were tracked; always required so the update cannot be skipped
+    #[account(
+        mut, 
+        seeds = [b"oracle_stats", attestation.load()?.attested_by.as_ref()], 
+        bump
+    )]
+    pub oracle_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged by revoke_attestation; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    /// CHECK: the mint's GradeIndexEntry PDA, empty when it was never listed; always
+    /// required so the index cannot keep listing a revoked attestation
+    #[account(mut, seeds = [b"grade_index_entry", mint.key().as_ref()], bump)]
+    pub grade_index_entry: UncheckedAccount<'info>,
+    pub grade_index: GradeIndexPages<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VoteRevocation<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"revoke_vote", attestation.key().as_ref(), signer.key().as_ref()], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 32 + 8 + 1 + 32 + 8 + 1 // discriminator + attestation + oracle + attested + reason + evidence + voted + bump
+    )]
+    pub vote: Account<'info, RevocationVote>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"revoke_tally", attestation.key().as_ref()], 
+        bump, 
+        payer = signer, 
+        space = 8 + 32 + 8 + 1 + 1 // discriminator + attestation + attested + votes + bump
+    )]
+    pub tally: Account<'info, RevocationTally>,
+    /// CHECK: the oracle's OracleStats PDA, absent for oracles that attested before
+    /// stats were tracked; always required so the update cannot be skipped
+    #[account(
+        mut, 
+        seeds = [b"oracle_stats", attestation.load()?.attested_by.as_ref()], 
+        bump
+    )]
+    pub oracle_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this revokes the attestation; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    /// CHECK: the mint's GradeIndexEntry PDA, empty when it was never listed; always
+    /// required so the index cannot keep listing a revoked attestation
+    #[account(mut, seeds = [b"grade_index_entry", mint.key().as_ref()], bump)]
+    pub grade_index_entry: UncheckedAccount<'info>,
+    pub grade_index: GradeIndexPages<'info>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeOwn<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        mut, 
+        seeds = [b"attest", mint.key().as_ref(), &attestation.load()?.ruleset_version.to_le_bytes()], 
+        bump,
+        constraint = attestation.load()?.attested_by == signer.key() @ ErrorCode::NotAttester
+    )]
+    pub attestation: AccountLoader<'info, Attestation>,
+    /// CHECK: the oracle's OracleStats PDA, absent for oracles that attested before
+    /// stats were tracked; always required so the update cannot be skipped
+    #[account(
+        mut, 
+        seeds = [b"oracle_stats", signer.key().as_ref()], 
+        bump
+    )]
+    pub oracle_stats: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"registry_stats"], bump = registry_stats.bump)]
+    pub registry_stats: Option<Account<'info, RegistryStats>>,
+    // The mint's MintWatch, when anyone watches it
+    #[account(mut, seeds = [b"watch", mint.key().as_ref()], bump = watch.bump)]
+    pub watch: Option<Account<'info, MintWatch>>,
+    /// CHECK: the RiskBloom PDA, flagged when this revokes the attestation; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    /// CHECK: the mint's GradeIndexEntry PDA, empty when it was never listed; always
+    /// required so the index cannot keep listing a revoked attestation
+    #[account(mut, seeds = [b"grade_index_entry", mint.key().as_ref()], bump)]
+    pub grade_index_entry: UncheckedAccount<'info>,
+    pub grade_index: GradeIndexPages<'info>,
+    pub signer: Signer<'info>,
+}
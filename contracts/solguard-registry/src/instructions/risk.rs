@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn blacklist_mint(ctx: Context<BlacklistMint>, evidence_hash: [u8; 32]) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::BlacklistMint::DISCRIMINATOR, &evidence_hash)?;
+    
+    let b = &mut ctx.accounts.blacklist;
+    b.mint = ctx.accounts.mint.key();
+    b.evidence_hash = evidence_hash;
+    b.added_by = ctx.accounts.admin.key();
+    b.added_at = Clock::get()?.unix_timestamp;
+    b.bump = ctx.bumps.blacklist;
+    
+    flag_in_risk_bloom(&ctx.accounts.risk_bloom, &b.mint)?;
+    
+    emit!(MintBlacklisted {
+        mint: b.mint,
+        evidence_hash,
+        admin: b.added_by,
+    });
+    
+    Ok(())
+}
+
+pub fn init_risk_bloom(ctx: Context<InitRiskBloom>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::InitRiskBloom::DISCRIMINATOR, &())?;
+    let mut bloom = ctx.accounts.risk_bloom.load_init()?;
+    bloom.bump = ctx.bumps.risk_bloom;
+    bloom.reset_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+pub fn reset_risk_bloom(ctx: Context<ResetRiskBloom>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::ResetRiskBloom::DISCRIMINATOR, &())?;
+    let mut bloom = ctx.accounts.risk_bloom.load_mut()?;
+    let inserted = bloom.inserted;
+    bloom.bits = [0; RISK_BLOOM_BYTES];
+    bloom.inserted = 0;
+    bloom.reset_at = Clock::get()?.unix_timestamp;
+    
+    emit!(RiskBloomReset {
+        inserted,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn add_to_risk_bloom(ctx: Context<AddToRiskBloom>, _ruleset_version: u16) -> Result<()> {
+    let flagged = ctx.accounts.blacklist.is_some() || match &ctx.accounts.attestation {
+        Some(loader) => {
+            let a = loader.load()?;
+            a.is_revoked() || a.normalized_grade() <= GRADE_RED
+        }
+        None => false,
+    };
+    require!(flagged, ErrorCode::NotRiskBloomCandidate);
+    flag_in_risk_bloom(&ctx.accounts.risk_bloom.to_account_info(), &ctx.accounts.mint.key())
+}
+
+pub fn allowlist_mint(ctx: Context<AllowlistMint>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::AllowlistMint::DISCRIMINATOR, &())?;
+    
+    let entry = &mut ctx.accounts.allowlist;
+    entry.mint = ctx.accounts.mint.key();
+    entry.added_by = ctx.accounts.admin.key();
+    entry.added_at = Clock::get()?.unix_timestamp;
+    entry.bump = ctx.bumps.allowlist;
+    
+    emit!(MintAllowlisted {
+        mint: entry.mint,
+        admin: entry.added_by,
+    });
+    
+    Ok(())
+}
+
+pub fn remove_allowlisted_mint(ctx: Context<RemoveAllowlistedMint>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::RemoveAllowlistedMint::DISCRIMINATOR, &())?;
+    
+    emit!(MintRemovedFromAllowlist {
+        mint: ctx.accounts.allowlist.mint,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_grade_override(ctx: Context<SetGradeOverride>, grade: u8) -> Result<()> {
+    require!(grade < GRADE_LEVELS_EXTENDED, ErrorCode::InvalidGrade);
+    
+    let o = &mut ctx.accounts.grade_override;
+    let old_grade = (o.set_at != 0).then_some(o.grade);
+    o.mint = ctx.accounts.mint.key();
+    o.grade = grade;
+    o.governance = ctx.accounts.governance.key();
+    o.set_at = Clock::get()?.unix_timestamp;
+    if o.payer == Pubkey::default() {
+        o.payer = ctx.accounts.payer.key();
+    }
+    o.bump = ctx.bumps.grade_override;
+    
+    emit!(GradeOverrideSet {
+        mint: o.mint,
+        old_grade,
+        new_grade: grade,
+        governance: o.governance,
+    });
+    
+    Ok(())
+}
+
+pub fn clear_grade_override(ctx: Context<ClearGradeOverride>) -> Result<()> {
+    let o = &ctx.accounts.grade_override;
+    
+    emit!(GradeOverrideCleared {
+        mint: o.mint,
+        grade: o.grade,
+        governance: ctx.accounts.governance.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BlacklistMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init, 
+        seeds = [b"blacklist", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 32 + 32 + 32 + 8 + 1 // discriminator + mint + evidence + added by + added at + bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+    /// CHECK: the RiskBloom PDA, flagged with the blacklisted mint; empty until init_risk_bloom,
+    /// always required so the flag cannot be skipped once it exists
+    #[account(mut, seeds = [b"risk_bloom"], bump)]
+    pub risk_bloom: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitRiskBloom<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"risk_bloom"], 
+        bump, 
+        payer = payer, 
+        space = RISK_BLOOM_SPACE
+    )]
+    pub risk_bloom: AccountLoader<'info, RiskBloom>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResetRiskBloom<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut, seeds = [b"risk_bloom"], bump = risk_bloom.load()?.bump)]
+    pub risk_bloom: AccountLoader<'info, RiskBloom>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AddToRiskBloom<'info> {
+    /// CHECK: only the key is used
+    pub mint: UncheckedAccount<'info>,
+    // Not needed for blacklisted mints
+    #[account(seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], bump)]
+    pub attestation: Option<AccountLoader<'info, Attestation>>,
+    #[account(seeds = [b"blacklist", mint.key().as_ref()], bump = blacklist.bump)]
+    pub blacklist: Option<Account<'info, Blacklist>>,
+    #[account(mut, seeds = [b"risk_bloom"], bump = risk_bloom.load()?.bump)]
+    pub risk_bloom: AccountLoader<'info, RiskBloom>,
+}
+
+#[derive(Accounts)]
+pub struct AllowlistMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init, 
+        seeds = [b"allowlist", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 32 + 32 + 8 + 1 // discriminator + mint + added by + added at + bump
+    )]
+    pub allowlist: Account<'info, AllowlistEntry>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGradeOverride<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = config.governance != Pubkey::default() && governance.key() == config.governance @ ErrorCode::NotGovernance
+    )]
+    pub governance: Signer<'info>,
+    /// CHECK: mint pubkey
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"grade_override", mint.key().as_ref()], 
+        bump, 
+        payer = payer, 
+        space = GRADE_OVERRIDE_SPACE
+    )]
+    pub grade_override: Account<'info, GradeOverride>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClearGradeOverride<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = config.governance != Pubkey::default() && governance.key() == config.governance @ ErrorCode::NotGovernance
+    )]
+    pub governance: Signer<'info>,
+    #[account(
+        mut, 
+        has_one = payer,
+        close = payer,
+        seeds = [b"grade_override", grade_override.mint.as_ref()], 
+        bump = grade_override.bump
+    )]
+    pub grade_override: Account<'info, GradeOverride>,
+    /// CHECK: original rent payer, receives the lamports
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowlistedMint<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        close = admin, 
+        seeds = [b"allowlist", allowlist.mint.as_ref()], 
+        bump = allowlist.bump
+    )]
+    pub allowlist: Account<'info, AllowlistEntry>,
+}
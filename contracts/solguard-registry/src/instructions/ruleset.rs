@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn publish_ruleset(
+    ctx: Context<PublishRuleset>, 
+    version: u16, 
+    definition_hash: [u8; 32], 
+    uri: String, 
+    activation_slot: u64
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::PublishRuleset::DISCRIMINATOR, &(version, definition_hash, &uri, activation_slot))?;
+    require!(uri.len() <= MAX_RULESET_URI_LEN, ErrorCode::RulesetUriTooLong);
+    
+    let r = &mut ctx.accounts.ruleset;
+    r.version = version;
+    r.definition_hash = definition_hash;
+    r.uri = uri;
+    r.activation_slot = activation_slot;
+    r.valid_from = 0;
+    r.valid_until = 0;
+    r.status = RulesetStatus::Published;
+    r.published_by = ctx.accounts.admin.key();
+    r.published_at = Clock::get()?.unix_timestamp;
+    r.bump = ctx.bumps.ruleset;
+    
+    emit!(RulesetPublished {
+        version,
+        definition_hash,
+        uri: r.uri.clone(),
+        activation_slot,
+        admin: r.published_by,
+    });
+    
+    Ok(())
+}
+
+pub fn set_ruleset_validity(
+    ctx: Context<SetRulesetValidity>, 
+    version: u16, 
+    valid_from: i64, 
+    valid_until: i64
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetRulesetValidity::DISCRIMINATOR, &(version, valid_from, valid_until))?;
+    require!(valid_from >= 0 && (valid_until == 0 || valid_until > valid_from), ErrorCode::InvalidValidityWindow);
+    
+    let r = &mut ctx.accounts.ruleset;
+    r.valid_from = valid_from;
+    r.valid_until = valid_until;
+    
+    emit!(RulesetValidityUpdated {
+        version: r.version,
+        valid_from,
+        valid_until,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn bump_ruleset_version(ctx: Context<BumpRulesetVersion>, v: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::BumpRulesetVersion::DISCRIMINATOR, &v)?;
+    require!(ctx.accounts.config.timelock_delay == 0, ErrorCode::TimelockRequired);
+    activate_ruleset(Some(&mut *ctx.accounts.ruleset), ctx.accounts.previous_ruleset.as_deref_mut(), v)?;
+    apply_admin_action(&mut ctx.accounts.config, AdminAction::BumpRulesetVersion { version: v }, ctx.accounts.admin.key())
+}
+
+pub fn set_active_rulesets(ctx: Context<OnlyAdmin>, rulesets: Vec<ActiveRuleset>) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetActiveRulesets::DISCRIMINATOR, &rulesets)?;
+    require!(rulesets.len() <= MAX_ACTIVE_RULESETS, ErrorCode::TooManyRulesetVersions);
+    for (i, r) in rulesets.iter().enumerate() {
+        require!(r.version != 0, ErrorCode::InvalidRulesetVersion);
+        require!(rulesets[..i].iter().all(|o| o.version != r.version), ErrorCode::InvalidRulesetVersion);
+        require!(r.min_grade < GRADE_LEVELS_EXTENDED, ErrorCode::InvalidGrade);
+    }
+    
+    let mut active = [ActiveRuleset::default(); MAX_ACTIVE_RULESETS];
+    active[..rulesets.len()].copy_from_slice(&rulesets);
+    ctx.accounts.config.active_rulesets = active;
+    
+    emit!(ActiveRulesetsUpdated {
+        rulesets,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn declare_ruleset_compatible(ctx: Context<DeclareRulesetCompatible>, from_version: u16, to_version: u16) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::DeclareRulesetCompatible::DISCRIMINATOR, &(from_version, to_version))?;
+    require!(from_version != to_version, ErrorCode::InvalidRulesetVersion);
+    
+    let c = &mut ctx.accounts.compatibility;
+    c.from_version = from_version;
+    c.to_version = to_version;
+    c.declared_by = ctx.accounts.admin.key();
+    c.declared_at = Clock::get()?.unix_timestamp;
+    c.bump = ctx.bumps.compatibility;
+    
+    emit!(RulesetCompatibilityDeclared {
+        from_version,
+        to_version,
+        admin: c.declared_by,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(version: u16)]
+pub struct PublishRuleset<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"ruleset", &version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 2 + 32 + 4 + MAX_RULESET_URI_LEN + 8 + 8 + 8 + 1 + 32 + 8 + 1 // discriminator + version + hash + uri + activation slot + valid from + valid until + status + publisher + published + bump
+    )]
+    pub ruleset: Account<'info, Ruleset>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(version: u16)]
+pub struct SetRulesetValidity<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut, seeds = [b"ruleset", &version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Account<'info, Ruleset>,
+}
+
+#[derive(Accounts)]
+#[instruction(v: u16)]
+pub struct BumpRulesetVersion<'info> {
+    #[account(
+        mut, 
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(mut, seeds = [b"ruleset", &v.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Account<'info, Ruleset>,
+    // Absent for versions that predate Ruleset accounts
+    #[account(
+        mut, 
+        seeds = [b"ruleset", &config.ruleset_version.to_le_bytes()], 
+        bump = previous_ruleset.bump
+    )]
+    pub previous_ruleset: Option<Account<'info, Ruleset>>,
+}
+
+#[derive(Accounts)]
+#[instruction(from_version: u16, to_version: u16)]
+pub struct DeclareRulesetCompatible<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init, 
+        seeds = [b"ruleset_compat", &from_version.to_le_bytes(), &to_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 2 + 2 + 32 + 8 + 1 // discriminator + from + to + admin + declared + bump
+    )]
+    pub compatibility: Account<'info, RulesetCompatibility>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,892 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::metadata::{Metadata, MetadataAccount};
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn attest_collection(
+    ctx: Context<AttestCollection>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32], 
+    inherit: bool, 
+    item_override: bool
+) -> Result<()> {
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), score, grade)?;
+    
+    let c = &mut ctx.accounts.collection_attestation;
+    c.collection_mint = ctx.accounts.collection_mint.key();
+    c.ruleset_version = ruleset_version;
+    c.score_bps = score;
+    c.grade = grade;
+    c.grade_levels = ctx.accounts.config.grade_scale();
+    c.proofs_hash = proofs_hash;
+    c.attested_by = ctx.accounts.signer.key();
+    c.attested_at = Clock::get()?.unix_timestamp;
+    c.valid_until = ctx.accounts.config.expiry_from(c.attested_at);
+    c.inherit = inherit;
+    c.item_override = item_override;
+    c.revoked = false;
+    c.bump = ctx.bumps.collection_attestation;
+    
+    emit!(CollectionAttested {
+        collection_mint: c.collection_mint,
+        ruleset_version,
+        score,
+        grade,
+        inherit,
+        item_override,
+        attested_by: c.attested_by,
+        attested_at: c.attested_at,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_collection_attestation(
+    ctx: Context<RevokeCollection>, 
+    ruleset_version: u16, 
+    reason: RevocationReason
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RevokeCollectionAttestation::DISCRIMINATOR, &(ruleset_version, reason))?;
+    let c = &mut ctx.accounts.collection_attestation;
+    require!(!c.revoked, ErrorCode::AlreadyRevoked);
+    c.revoked = true;
+    
+    emit!(CollectionAttestationRevoked {
+        collection_mint: c.collection_mint,
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+        reason,
+    });
+    
+    Ok(())
+}
+
+pub fn verify_collection_item(
+    ctx: Context<VerifyCollectionItem>, 
+    ruleset_version: u16, 
+    min_grade: u8, 
+    max_age_secs: Option<i64>
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cfg = &ctx.accounts.config;
+    require!(cfg.is_active_version(ruleset_version), ErrorCode::InvalidRulesetVersion);
+    let min_grade = min_grade.max(cfg.ruleset_min_grade(ruleset_version));
+    if cfg.subscription_price > 0 {
+        require_subscription(ctx.accounts.subscription.as_deref(), ctx.accounts.caller.as_ref())?;
+    }
+    
+    let c = &ctx.accounts.collection_attestation;
+    let collection = ctx.accounts.metadata.collection.as_ref().filter(|collection| collection.verified);
+    require!(collection.is_some_and(|collection| collection.key == c.collection_mint), ErrorCode::NotInCollection);
+    
+    let max_age = cfg.max_age_or_default(max_age_secs)?;
+    let info = &ctx.accounts.attestation;
+    let attested = info.owner == &crate::ID && !info.data_is_empty();
+    let mut grade = if attested && (c.item_override || !c.inherit) {
+        let loader = AccountLoader::<Attestation>::try_from(info.as_ref())?;
+        let a = loader.load()?;
+        a.require_valid(now)?;
+        a.require_fresh(now, max_age)?;
+        a.effective_grade(cfg, now)
+    } else {
+        require!(c.inherit, ErrorCode::AttestationMissing);
+        c.require_valid(now)?;
+        c.require_fresh(now, max_age)?;
+        c.effective_grade(cfg, now)
+    };
+    if !ctx.accounts.blacklist.data_is_empty() {
+        grade = grade.min(GRADE_RED);
+    }
+    require!(grade >= min_grade, ErrorCode::InsufficientGrade);
+    Ok(())
+}
+
+pub fn attest_program(
+    ctx: Context<AttestProgram>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32], 
+    build_hash: [u8; 32]
+) -> Result<()> {
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), score, grade)?;
+    let (upgrade_authority, deployed_slot) = program_deployment(&ctx.accounts.program, ctx.accounts.program_data.as_deref())?;
+    
+    let p = &mut ctx.accounts.program_attestation;
+    p.program_id = ctx.accounts.program.key();
+    p.ruleset_version = ruleset_version;
+    p.score_bps = score;
+    p.grade = grade;
+    p.grade_levels = ctx.accounts.config.grade_scale();
+    p.proofs_hash = proofs_hash;
+    p.upgrade_authority = upgrade_authority;
+    p.deployed_slot = deployed_slot;
+    p.build_hash = build_hash;
+    p.attested_by = ctx.accounts.signer.key();
+    p.attested_at = Clock::get()?.unix_timestamp;
+    p.valid_until = ctx.accounts.config.expiry_from(p.attested_at);
+    p.revoked = false;
+    p.bump = ctx.bumps.program_attestation;
+    
+    emit!(ProgramAttested {
+        program_id: p.program_id,
+        ruleset_version,
+        score,
+        grade,
+        upgrade_authority,
+        build_hash,
+        attested_by: p.attested_by,
+        attested_at: p.attested_at,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_program_attestation(
+    ctx: Context<RevokeProgram>, 
+    ruleset_version: u16, 
+    reason: RevocationReason
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RevokeProgramAttestation::DISCRIMINATOR, &(ruleset_version, reason))?;
+    let p = &mut ctx.accounts.program_attestation;
+    require!(!p.revoked, ErrorCode::AlreadyRevoked);
+    p.revoked = true;
+    
+    emit!(ProgramAttestationRevoked {
+        program_id: p.program_id,
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+        reason,
+    });
+    
+    Ok(())
+}
+
+pub fn verify_program(
+    ctx: Context<VerifyProgram>, 
+    ruleset_version: u16, 
+    min_grade: u8, 
+    max_age_secs: Option<i64>, 
+    require_immutable: bool
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cfg = &ctx.accounts.config;
+    require!(cfg.is_active_version(ruleset_version), ErrorCode::InvalidRulesetVersion);
+    let min_grade = min_grade.max(cfg.ruleset_min_grade(ruleset_version));
+    if cfg.subscription_price > 0 {
+        require_subscription(ctx.accounts.subscription.as_deref(), ctx.accounts.caller.as_ref())?;
+    }
+    
+    let p = &ctx.accounts.program_attestation;
+    p.require_valid(now)?;
+    p.require_fresh(now, cfg.max_age_or_default(max_age_secs)?)?;
+    let (upgrade_authority, deployed_slot) = program_deployment(&ctx.accounts.program, ctx.accounts.program_data.as_deref())?;
+    require!(
+        upgrade_authority == p.upgrade_authority && deployed_slot == p.deployed_slot, 
+        ErrorCode::ProgramChangedSinceAttestation
+    );
+    require!(!require_immutable || upgrade_authority.is_none(), ErrorCode::ProgramUpgradeable);
+    require!(p.effective_grade(cfg, now) >= min_grade, ErrorCode::InsufficientGrade);
+    Ok(())
+}
+
+pub fn attest_wallet(
+    ctx: Context<AttestWallet>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32], 
+    tokens_created: u32, 
+    tokens_rugged: u32
+) -> Result<()> {
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), score, grade)?;
+    require!(tokens_rugged <= tokens_created, ErrorCode::InvalidWalletHistory);
+    
+    let w = &mut ctx.accounts.wallet_attestation;
+    w.wallet = ctx.accounts.wallet.key();
+    w.ruleset_version = ruleset_version;
+    w.score_bps = score;
+    w.grade = grade;
+    w.grade_levels = ctx.accounts.config.grade_scale();
+    w.proofs_hash = proofs_hash;
+    w.tokens_created = tokens_created;
+    w.tokens_rugged = tokens_rugged;
+    w.attested_by = ctx.accounts.signer.key();
+    w.attested_at = Clock::get()?.unix_timestamp;
+    w.valid_until = ctx.accounts.config.expiry_from(w.attested_at);
+    w.revoked = false;
+    w.bump = ctx.bumps.wallet_attestation;
+    
+    emit!(WalletAttested {
+        wallet: w.wallet,
+        ruleset_version,
+        score,
+        grade,
+        tokens_created,
+        tokens_rugged,
+        attested_by: w.attested_by,
+        attested_at: w.attested_at,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_wallet_attestation(
+    ctx: Context<RevokeWallet>, 
+    ruleset_version: u16, 
+    reason: RevocationReason
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RevokeWalletAttestation::DISCRIMINATOR, &(ruleset_version, reason))?;
+    let w = &mut ctx.accounts.wallet_attestation;
+    require!(!w.revoked, ErrorCode::AlreadyRevoked);
+    w.revoked = true;
+    
+    emit!(WalletAttestationRevoked {
+        wallet: w.wallet,
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+        reason,
+    });
+    
+    Ok(())
+}
+
+pub fn verify_wallet(
+    ctx: Context<VerifyWallet>, 
+    ruleset_version: u16, 
+    min_grade: u8, 
+    max_age_secs: Option<i64>, 
+    max_rugged: Option<u32>
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cfg = &ctx.accounts.config;
+    require!(cfg.is_active_version(ruleset_version), ErrorCode::InvalidRulesetVersion);
+    let min_grade = min_grade.max(cfg.ruleset_min_grade(ruleset_version));
+    if cfg.subscription_price > 0 {
+        require_subscription(ctx.accounts.subscription.as_deref(), ctx.accounts.caller.as_ref())?;
+    }
+    
+    let w = &ctx.accounts.wallet_attestation;
+    w.require_valid(now)?;
+    w.require_fresh(now, cfg.max_age_or_default(max_age_secs)?)?;
+    require!(max_rugged.map_or(true, |max| w.tokens_rugged <= max), ErrorCode::WalletRugHistory);
+    require!(w.effective_grade(cfg, now) >= min_grade, ErrorCode::InsufficientGrade);
+    Ok(())
+}
+
+pub fn attest_pool(
+    ctx: Context<AttestPool>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32], 
+    base_mint: Pubkey, 
+    quote_mint: Pubkey, 
+    lp_burned_bps: u16, 
+    lp_locked_bps: u16, 
+    lp_unlock_at: i64
+) -> Result<()> {
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), score, grade)?;
+    require!(lp_burned_bps as u32 + lp_locked_bps as u32 <= 10000, ErrorCode::InvalidLpShares);
+    
+    let p = &mut ctx.accounts.pool_attestation;
+    p.pool = ctx.accounts.pool.key();
+    p.dex_program = *ctx.accounts.pool.owner;
+    p.base_mint = base_mint;
+    p.quote_mint = quote_mint;
+    p.ruleset_version = ruleset_version;
+    p.score_bps = score;
+    p.grade = grade;
+    p.grade_levels = ctx.accounts.config.grade_scale();
+    p.proofs_hash = proofs_hash;
+    p.lp_burned_bps = lp_burned_bps;
+    p.lp_locked_bps = lp_locked_bps;
+    p.lp_unlock_at = lp_unlock_at;
+    p.attested_by = ctx.accounts.signer.key();
+    p.attested_at = Clock::get()?.unix_timestamp;
+    p.valid_until = ctx.accounts.config.expiry_from(p.attested_at);
+    p.revoked = false;
+    p.bump = ctx.bumps.pool_attestation;
+    
+    emit!(PoolAttested {
+        pool: p.pool,
+        dex_program: p.dex_program,
+        base_mint,
+        quote_mint,
+        ruleset_version,
+        score,
+        grade,
+        lp_burned_bps,
+        lp_locked_bps,
+        lp_unlock_at,
+        attested_by: p.attested_by,
+        attested_at: p.attested_at,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_pool_attestation(
+    ctx: Context<RevokePool>, 
+    ruleset_version: u16, 
+    reason: RevocationReason
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RevokePoolAttestation::DISCRIMINATOR, &(ruleset_version, reason))?;
+    let p = &mut ctx.accounts.pool_attestation;
+    require!(!p.revoked, ErrorCode::AlreadyRevoked);
+    p.revoked = true;
+    
+    emit!(PoolAttestationRevoked {
+        pool: p.pool,
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+        reason,
+    });
+    
+    Ok(())
+}
+
+pub fn verify_pool(
+    ctx: Context<VerifyPool>, 
+    ruleset_version: u16, 
+    min_grade: u8, 
+    max_age_secs: Option<i64>, 
+    min_lp_secured_bps: u16
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cfg = &ctx.accounts.config;
+    require!(cfg.is_active_version(ruleset_version), ErrorCode::InvalidRulesetVersion);
+    let min_grade = min_grade.max(cfg.ruleset_min_grade(ruleset_version));
+    if cfg.subscription_price > 0 {
+        require_subscription(ctx.accounts.subscription.as_deref(), ctx.accounts.caller.as_ref())?;
+    }
+    
+    let p = &ctx.accounts.pool_attestation;
+    p.require_valid(now)?;
+    p.require_fresh(now, cfg.max_age_or_default(max_age_secs)?)?;
+    require!(p.lp_secured_bps(now) >= min_lp_secured_bps, ErrorCode::LiquidityNotSecured);
+    require!(p.effective_grade(cfg, now) >= min_grade, ErrorCode::InsufficientGrade);
+    Ok(())
+}
+
+pub fn attest_pair(
+    ctx: Context<AttestPair>, 
+    ruleset_version: u16, 
+    score: u16, 
+    grade: u8, 
+    proofs_hash: [u8; 32], 
+    risk_flags: u16
+) -> Result<()> {
+    validate_submission(&ctx.accounts.config, &ctx.accounts.oracle, false, ruleset_version, ctx.accounts.ruleset.as_deref(), score, grade)?;
+    
+    let p = &mut ctx.accounts.pair_attestation;
+    p.mint_a = ctx.accounts.mint_a.key();
+    p.mint_b = ctx.accounts.mint_b.key();
+    p.venue = ctx.accounts.venue.key();
+    p.ruleset_version = ruleset_version;
+    p.score_bps = score;
+    p.grade = grade;
+    p.grade_levels = ctx.accounts.config.grade_scale();
+    p.proofs_hash = proofs_hash;
+    p.risk_flags = risk_flags;
+    p.attested_by = ctx.accounts.signer.key();
+    p.attested_at = Clock::get()?.unix_timestamp;
+    p.valid_until = ctx.accounts.config.expiry_from(p.attested_at);
+    p.revoked = false;
+    p.bump = ctx.bumps.pair_attestation;
+    
+    emit!(PairAttested {
+        mint_a: p.mint_a,
+        mint_b: p.mint_b,
+        venue: p.venue,
+        ruleset_version,
+        score,
+        grade,
+        risk_flags,
+        attested_by: p.attested_by,
+        attested_at: p.attested_at,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_pair_attestation(
+    ctx: Context<RevokePair>, 
+    ruleset_version: u16, 
+    reason: RevocationReason
+) -> Result<()> {
+    authorize_admin_action(&ctx, Role::Revoker, instruction::RevokePairAttestation::DISCRIMINATOR, &(ruleset_version, reason))?;
+    let p = &mut ctx.accounts.pair_attestation;
+    require!(!p.revoked, ErrorCode::AlreadyRevoked);
+    p.revoked = true;
+    
+    emit!(PairAttestationRevoked {
+        mint_a: p.mint_a,
+        mint_b: p.mint_b,
+        venue: p.venue,
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+        reason,
+    });
+    
+    Ok(())
+}
+
+pub fn verify_pair(
+    ctx: Context<VerifyPair>, 
+    ruleset_version: u16, 
+    min_grade: u8, 
+    max_age_secs: Option<i64>, 
+    forbidden_risk_flags: u16
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let cfg = &ctx.accounts.config;
+    require!(cfg.is_active_version(ruleset_version), ErrorCode::InvalidRulesetVersion);
+    let min_grade = min_grade.max(cfg.ruleset_min_grade(ruleset_version));
+    if cfg.subscription_price > 0 {
+        require_subscription(ctx.accounts.subscription.as_deref(), ctx.accounts.caller.as_ref())?;
+    }
+    
+    let p = &ctx.accounts.pair_attestation;
+    p.require_valid(now)?;
+    p.require_fresh(now, cfg.max_age_or_default(max_age_secs)?)?;
+    require!(p.risk_flags & forbidden_risk_flags == 0, ErrorCode::PairRiskFlagged);
+    require!(p.effective_grade(cfg, now) >= min_grade, ErrorCode::InsufficientGrade);
+    Ok(())
+}
+
+/// Upgrade authority and last deploy slot of `program`. Programs owned by the
+/// upgradeable loader need their ProgramData; anything else is immutable.
+fn program_deployment(program: &AccountInfo, program_data: Option<&ProgramData>) -> Result<(Option<Pubkey>, u64)> {
+    if program.owner != &bpf_loader_upgradeable::ID {
+        return Ok((None, 0));
+    }
+    let data = program_data.ok_or(ErrorCode::ProgramDataRequired)?;
+    Ok((data.upgrade_authority_address, data.slot))
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestCollection<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: Metaplex collection mint
+    pub collection_mint: UncheckedAccount<'info>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"collection", collection_mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = COLLECTION_ATTESTATION_SPACE
+    )]
+    pub collection_attestation: Account<'info, CollectionAttestation>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct RevokeCollection<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"collection", collection_attestation.collection_mint.as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = collection_attestation.bump
+    )]
+    pub collection_attestation: Account<'info, CollectionAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VerifyCollectionItem<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: NFT mint
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"metadata", Metadata::id().as_ref(), mint.key().as_ref()], 
+        seeds::program = Metadata::id(), 
+        bump
+    )]
+    pub metadata: Box<Account<'info, MetadataAccount>>,
+    #[account(
+        seeds = [b"collection", collection_attestation.collection_mint.as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = collection_attestation.bump
+    )]
+    pub collection_attestation: Account<'info, CollectionAttestation>,
+    /// CHECK: the item's own attestation, which may not exist; ownership checked in the handler
+    #[account(
+        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump
+    )]
+    pub attestation: UncheckedAccount<'info>,
+    /// CHECK: the item's Blacklist PDA; only its existence is checked
+    #[account(seeds = [b"blacklist", mint.key().as_ref()], bump)]
+    pub blacklist: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"subscription", subscription.consumer_program.as_ref()], 
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    /// The consumer program's `[b"solguard_caller"]` PDA, signed through invoke_signed
+    pub caller: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestProgram<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: the graded program; only its key and owner are used
+    #[account(executable)]
+    pub program: UncheckedAccount<'info>,
+    // Required for programs owned by the upgradeable loader
+    #[account(
+        seeds = [program.key().as_ref()], 
+        bump, 
+        seeds::program = bpf_loader_upgradeable::ID
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"attest_program", program.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = PROGRAM_ATTESTATION_SPACE
+    )]
+    pub program_attestation: Account<'info, ProgramAttestation>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct RevokeProgram<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"attest_program", program_attestation.program_id.as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = program_attestation.bump
+    )]
+    pub program_attestation: Account<'info, ProgramAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VerifyProgram<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: the verified program; only its key and owner are used
+    pub program: UncheckedAccount<'info>,
+    #[account(
+        seeds = [program.key().as_ref()], 
+        bump, 
+        seeds::program = bpf_loader_upgradeable::ID
+    )]
+    pub program_data: Option<Account<'info, ProgramData>>,
+    #[account(
+        seeds = [b"attest_program", program.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = program_attestation.bump
+    )]
+    pub program_attestation: Account<'info, ProgramAttestation>,
+    #[account(
+        seeds = [b"subscription", subscription.consumer_program.as_ref()], 
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    /// The consumer program's `[b"solguard_caller"]` PDA, signed through invoke_signed
+    pub caller: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestWallet<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: the graded wallet; only its key is used
+    pub wallet: UncheckedAccount<'info>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"wallet", wallet.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = WALLET_ATTESTATION_SPACE
+    )]
+    pub wallet_attestation: Account<'info, WalletAttestation>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct RevokeWallet<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"wallet", wallet_attestation.wallet.as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = wallet_attestation.bump
+    )]
+    pub wallet_attestation: Account<'info, WalletAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VerifyWallet<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: wallet pubkey
+    pub wallet: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"wallet", wallet.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = wallet_attestation.bump
+    )]
+    pub wallet_attestation: Account<'info, WalletAttestation>,
+    #[account(
+        seeds = [b"subscription", subscription.consumer_program.as_ref()], 
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    /// The consumer program's `[b"solguard_caller"]` PDA, signed through invoke_signed
+    pub caller: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestPool<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: pool state account; only its key and owner are used
+    #[account(constraint = POOL_PROGRAMS.contains(pool.owner) @ ErrorCode::UnsupportedPoolProgram)]
+    pub pool: UncheckedAccount<'info>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"pool", pool.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = POOL_ATTESTATION_SPACE
+    )]
+    pub pool_attestation: Account<'info, PoolAttestation>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct RevokePool<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [b"pool", pool_attestation.pool.as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = pool_attestation.bump
+    )]
+    pub pool_attestation: Account<'info, PoolAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VerifyPool<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: pool pubkey
+    pub pool: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"pool", pool.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = pool_attestation.bump
+    )]
+    pub pool_attestation: Account<'info, PoolAttestation>,
+    #[account(
+        seeds = [b"subscription", subscription.consumer_program.as_ref()], 
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    /// The consumer program's `[b"solguard_caller"]` PDA, signed through invoke_signed
+    pub caller: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestPair<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"oracle", signer.key().as_ref()],
+        bump = oracle.bump
+    )]
+    pub oracle: Account<'info, Oracle>,
+    /// CHECK: mint pubkey, must sort below mint_b
+    #[account(constraint = mint_a.key() < mint_b.key() @ ErrorCode::InvalidPair)]
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: mint pubkey
+    pub mint_b: UncheckedAccount<'info>,
+    /// CHECK: venue program; only its key is used
+    #[account(executable)]
+    pub venue: UncheckedAccount<'info>,
+    // Only needed when attesting under a version other than the current one
+    #[account(seeds = [b"ruleset", &ruleset_version.to_le_bytes()], bump = ruleset.bump)]
+    pub ruleset: Option<Account<'info, Ruleset>>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"pair", mint_a.key().as_ref(), mint_b.key().as_ref(), venue.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = signer, 
+        space = PAIR_ATTESTATION_SPACE
+    )]
+    pub pair_attestation: Account<'info, PairAttestation>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct RevokePair<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        mut, 
+        seeds = [
+            b"pair", 
+            pair_attestation.mint_a.as_ref(), 
+            pair_attestation.mint_b.as_ref(), 
+            pair_attestation.venue.as_ref(), 
+            &ruleset_version.to_le_bytes()
+        ], 
+        bump = pair_attestation.bump
+    )]
+    pub pair_attestation: Account<'info, PairAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct VerifyPair<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: mint pubkey, the lower-sorting side of the pair
+    pub mint_a: UncheckedAccount<'info>,
+    /// CHECK: mint pubkey
+    pub mint_b: UncheckedAccount<'info>,
+    /// CHECK: venue program pubkey
+    pub venue: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"pair", mint_a.key().as_ref(), mint_b.key().as_ref(), venue.key().as_ref(), &ruleset_version.to_le_bytes()], 
+        bump = pair_attestation.bump
+    )]
+    pub pair_attestation: Account<'info, PairAttestation>,
+    #[account(
+        seeds = [b"subscription", subscription.consumer_program.as_ref()], 
+        bump = subscription.bump
+    )]
+    pub subscription: Option<Account<'info, Subscription>>,
+    /// The consumer program's `[b"solguard_caller"]` PDA, signed through invoke_signed
+    pub caller: Option<Signer<'info>>,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::events::*;
+use crate::state::*;
+
+pub fn register_watcher(ctx: Context<RegisterWatcher>) -> Result<()> {
+    let watch = &mut ctx.accounts.watch;
+    watch.mint = ctx.accounts.mint.key();
+    watch.watchers += 1;
+    watch.bump = ctx.bumps.watch;
+    
+    let w = &mut ctx.accounts.watcher;
+    w.owner = ctx.accounts.owner.key();
+    w.mint = watch.mint;
+    w.seen_changes = watch.changes;
+    w.registered_at = Clock::get()?.unix_timestamp;
+    w.bump = ctx.bumps.watcher;
+    
+    emit!(WatcherRegistered {
+        mint: w.mint,
+        owner: w.owner,
+        watchers: watch.watchers,
+    });
+    
+    Ok(())
+}
+
+pub fn acknowledge_watcher(ctx: Context<AcknowledgeWatcher>) -> Result<()> {
+    ctx.accounts.watcher.seen_changes = ctx.accounts.watch.changes;
+    Ok(())
+}
+
+pub fn unregister_watcher(ctx: Context<UnregisterWatcher>) -> Result<()> {
+    let watch = &mut ctx.accounts.watch;
+    watch.watchers = watch.watchers.saturating_sub(1);
+    
+    emit!(WatcherUnregistered {
+        mint: watch.mint,
+        owner: ctx.accounts.owner.key(),
+        watchers: watch.watchers,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterWatcher<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"watch", mint.key().as_ref()], 
+        bump, 
+        payer = owner, 
+        space = MINT_WATCH_SPACE
+    )]
+    pub watch: Account<'info, MintWatch>,
+    #[account(
+        init, 
+        seeds = [b"watcher", mint.key().as_ref(), owner.key().as_ref()], 
+        bump, 
+        payer = owner, 
+        space = WATCHER_SPACE
+    )]
+    pub watcher: Account<'info, Watcher>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcknowledgeWatcher<'info> {
+    #[account(seeds = [b"watch", watcher.mint.as_ref()], bump = watch.bump)]
+    pub watch: Account<'info, MintWatch>,
+    #[account(
+        mut, 
+        seeds = [b"watcher", watcher.mint.as_ref(), owner.key().as_ref()], 
+        bump = watcher.bump, 
+        has_one = owner
+    )]
+    pub watcher: Account<'info, Watcher>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnregisterWatcher<'info> {
+    #[account(mut, seeds = [b"watch", watcher.mint.as_ref()], bump = watch.bump)]
+    pub watch: Account<'info, MintWatch>,
+    #[account(
+        mut, 
+        seeds = [b"watcher", watcher.mint.as_ref(), owner.key().as_ref()], 
+        bump = watcher.bump, 
+        has_one = owner, 
+        close = owner
+    )]
+    pub watcher: Account<'info, Watcher>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::auth::*;
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instruction;
+use crate::instructions::*;
+use crate::state::*;
+use crate::utils::*;
+
+pub fn attest_with_zk_proof(
+    ctx: Context<AttestWithZkProof>, 
+    ruleset_version: u16, 
+    item: BatchAttestation, 
+    proof: Groth16Proof
+) -> Result<()> {
+    let inputs = zk_public_inputs(&item.proofs_hash, item.score, item.grade);
+    ctx.accounts.circuit.verify(&proof, &inputs)?;
+    
+    write_attestation(&mut ctx.accounts.attest, &ctx.bumps.attest, ruleset_version, item, true)
+}
+
+pub fn set_ruleset_circuit(ctx: Context<SetRulesetCircuit>, ruleset_version: u16, key: Groth16VerifyingKey) -> Result<()> {
+    authorize_admin_action(&ctx, Role::ConfigAdmin, instruction::SetRulesetCircuit::DISCRIMINATOR, &(ruleset_version, &key))?;
+    require!(key.vk_ic.len() == ZK_PUBLIC_INPUTS + 1, ErrorCode::InvalidVerifyingKey);
+    
+    let c = &mut ctx.accounts.circuit;
+    c.ruleset_version = ruleset_version;
+    c.key = key;
+    c.bump = ctx.bumps.circuit;
+    
+    emit!(RulesetCircuitUpdated {
+        ruleset_version,
+        admin: ctx.accounts.admin.key(),
+    });
+    
+    Ok(())
+}
+
+/// Groth16 public inputs for a scored attestation, each a 32-byte big-endian field
+/// element: proofs_hash high half, proofs_hash low half, score bps, grade.
+fn zk_public_inputs(proofs_hash: &[u8; 32], score: u16, grade: u8) -> [[u8; 32]; ZK_PUBLIC_INPUTS] {
+    let mut inputs = [[0u8; 32]; ZK_PUBLIC_INPUTS];
+    inputs[0][16..].copy_from_slice(&proofs_hash[..16]);
+    inputs[1][16..].copy_from_slice(&proofs_hash[16..]);
+    inputs[2][30..].copy_from_slice(&score.to_be_bytes());
+    inputs[3][31] = grade;
+    inputs
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct AttestWithZkProof<'info> {
+    pub attest: AttestToken<'info>,
+    #[account(
+        seeds = [b"circuit", &ruleset_version.to_le_bytes()], 
+        bump = circuit.bump
+    )]
+    pub circuit: Account<'info, RulesetCircuit>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct SetRulesetCircuit<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"admin_set"], bump = admin_set.bump)]
+    pub admin_set: Option<Account<'info, AdminSet>>,
+    pub role_grant: Option<Account<'info, RoleGrant>>,
+    #[account(mut, seeds = [b"audit_log"], bump = audit_log.load()?.bump)]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+    #[account(
+        init_if_needed, 
+        seeds = [b"circuit", &ruleset_version.to_le_bytes()], 
+        bump, 
+        payer = payer, 
+        space = 8 + 2 + 64 + 128 * 3 + 4 + 64 * (ZK_PUBLIC_INPUTS + 1) + 1 // discriminator + version + verifying key + bump
+    )]
+    pub circuit: Box<Account<'info, RulesetCircuit>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,5 +1,20 @@
 use anchor_lang::prelude::*;
 
+mod auth;
+pub mod constants;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+mod utils;
+
+pub use constants::*;
+pub use errors::ErrorCode;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
+pub use utils::{category_proofs_hash, commitment_hash, denormalize_grade, metadata_hash, normalize_grade, risk_bloom_positions};
+
 declare_id!("SoLGuaRdREG1stry11111111111111111111111111111");
 
 #[program]
@@ -7,313 +22,981 @@ pub mod solguard_registry {
     use super::*;
 
     pub fn init_config(ctx: Context<InitConfig>, ruleset_version: u16, min_grade: u8) -> Result<()> {
-        let cfg = &mut ctx.accounts.config;
-        cfg.admin = ctx.accounts.admin.key();
-        cfg.ruleset_version = ruleset_version;
-        cfg.min_grade = min_grade; // 0=red,1=yellow,2=green
-        cfg.bump = ctx.bumps.config;
-        
-        emit!(ConfigInitialized {
-            admin: cfg.admin,
-            ruleset_version,
-            min_grade,
-        });
-        
-        Ok(())
+        instructions::init_config(ctx, ruleset_version, min_grade)
     }
 
     pub fn add_oracle(ctx: Context<UpdateOracle>) -> Result<()> {
-        let o = &mut ctx.accounts.oracle;
-        o.bump = ctx.bumps.oracle;
-        o.active = true;
-        
-        emit!(OracleAdded {
-            oracle: ctx.accounts.oracle_key.key(),
-            admin: ctx.accounts.admin.key(),
-        });
-        
-        Ok(())
+        instructions::add_oracle(ctx)
     }
 
     pub fn remove_oracle(ctx: Context<UpdateOracle>) -> Result<()> {
-        let o = &mut ctx.accounts.oracle;
-        o.active = false;
-        
-        emit!(OracleRemoved {
-            oracle: ctx.accounts.oracle_key.key(),
-            admin: ctx.accounts.admin.key(),
-        });
-        
-        Ok(())
+        instructions::remove_oracle(ctx)
+    }
+
+    pub fn close_oracle(ctx: Context<CloseOracle>) -> Result<()> {
+        instructions::close_oracle(ctx)
+    }
+
+    /// Creates the admin audit log.
+    pub fn init_audit_log(ctx: Context<InitAuditLog>) -> Result<()> {
+        instructions::init_audit_log(ctx)
+    }
+
+    pub fn init_fee_vault(ctx: Context<InitFeeVault>) -> Result<()> {
+        instructions::init_fee_vault(ctx)
+    }
+
+    /// Lamports charged per attestation written by an oracle. A non-zero fee
+    /// requires the fee vault to exist.
+    pub fn set_attestation_fee(ctx: Context<OnlyAdmin>, fee: u64) -> Result<()> {
+        instructions::set_attestation_fee(ctx, fee)
+    }
+
+    /// Moves collected fees out of the vault; the rent-exempt minimum stays behind.
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64, destination: Pubkey) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount, destination)
+    }
+
+    /// Creates the fee vault's associated token account for `fee_mint`.
+    pub fn init_fee_token_vault(ctx: Context<InitFeeTokenVault>) -> Result<()> {
+        instructions::init_fee_token_vault(ctx)
+    }
+
+    /// Denominates the attestation fee in `fee_mint`; Pubkey::default() switches
+    /// back to lamports. Oracle deposits stay in lamports since they are refunded
+    /// from the Oracle PDA itself.
+    pub fn set_fee_mint(ctx: Context<OnlyAdmin>, fee_mint: Pubkey) -> Result<()> {
+        instructions::set_fee_mint(ctx, fee_mint)
+    }
+
+    pub fn withdraw_fee_tokens(ctx: Context<WithdrawFeeTokens>, amount: u64) -> Result<()> {
+        instructions::withdraw_fee_tokens(ctx, amount)
+    }
+
+    pub fn set_stale_after(ctx: Context<OnlyAdmin>, stale_after: i64) -> Result<()> {
+        instructions::set_stale_after(ctx, stale_after)
+    }
+
+    pub fn set_attestation_ttl(ctx: Context<OnlyAdmin>, ttl: i64) -> Result<()> {
+        instructions::set_attestation_ttl(ctx, ttl)
+    }
+
+    pub fn set_oracle_deposit(ctx: Context<OnlyAdmin>, deposit: u64) -> Result<()> {
+        instructions::set_oracle_deposit(ctx, deposit)
+    }
+
+    pub fn set_unbonding_period(ctx: Context<OnlyAdmin>, period: i64) -> Result<()> {
+        instructions::set_unbonding_period(ctx, period)
+    }
+
+    pub fn set_max_heartbeat_age(ctx: Context<OnlyAdmin>, max_age_slots: u64) -> Result<()> {
+        instructions::set_max_heartbeat_age(ctx, max_age_slots)
+    }
+
+    pub fn oracle_heartbeat(ctx: Context<OracleHeartbeat>) -> Result<()> {
+        instructions::oracle_heartbeat(ctx)
+    }
+
+    pub fn stake_oracle(ctx: Context<StakeOracle>, amount: u64) -> Result<()> {
+        instructions::stake_oracle(ctx, amount)
+    }
+
+    pub fn unstake_oracle(ctx: Context<UnstakeOracle>, amount: u64) -> Result<()> {
+        instructions::unstake_oracle(ctx, amount)
+    }
+
+    pub fn withdraw_unbonded(ctx: Context<UnstakeOracle>) -> Result<()> {
+        instructions::withdraw_unbonded(ctx)
+    }
+
+    pub fn slash_oracle(ctx: Context<SlashOracle>, amount: u64) -> Result<()> {
+        instructions::slash_oracle(ctx, amount)
+    }
+
+    pub fn set_oracle_metadata(
+        ctx: Context<SetOracleMetadata>, 
+        name: String, 
+        operator_uri: String, 
+        contact_hash: [u8; 32]
+    ) -> Result<()> {
+        instructions::set_oracle_metadata(ctx, name, operator_uri, contact_hash)
+    }
+
+    pub fn set_oracle_weight(ctx: Context<ManageOracle>, weight: u16) -> Result<()> {
+        instructions::set_oracle_weight(ctx, weight)
+    }
+
+    pub fn set_oracle_scoped(ctx: Context<ManageOracle>, scoped: bool) -> Result<()> {
+        instructions::set_oracle_scoped(ctx, scoped)
+    }
+
+    /// Registers (or clears, with all zeroes) a secp256k1 key for an oracle, letting
+    /// it attest through `attest_with_evm_signature`.
+    pub fn set_oracle_evm_address(ctx: Context<ManageOracle>, evm_address: [u8; 20]) -> Result<()> {
+        instructions::set_oracle_evm_address(ctx, evm_address)
+    }
+
+    pub fn add_scope_mint(ctx: Context<AddScopeMint>) -> Result<()> {
+        instructions::add_scope_mint(ctx)
+    }
+
+    pub fn remove_scope_mint(ctx: Context<RemoveScopeMint>) -> Result<()> {
+        instructions::remove_scope_mint(ctx)
     }
 
     pub fn set_min_grade(ctx: Context<OnlyAdmin>, min_grade: u8) -> Result<()> {
-        require!(min_grade <= 2, ErrorCode::InvalidGrade);
-        let old_grade = ctx.accounts.config.min_grade;
-        ctx.accounts.config.min_grade = min_grade;
-        
-        emit!(MinGradeUpdated {
-            old_grade,
-            new_grade: min_grade,
-            admin: ctx.accounts.admin.key(),
-        });
-        
-        Ok(())
-    }
-
-    pub fn bump_ruleset_version(ctx: Context<OnlyAdmin>, v: u16) -> Result<()> {
-        let old_version = ctx.accounts.config.ruleset_version;
-        ctx.accounts.config.ruleset_version = v;
-        
-        emit!(RulesetVersionBumped {
-            old_version,
-            new_version: v,
-            admin: ctx.accounts.admin.key(),
-        });
-        
-        Ok(())
+        instructions::set_min_grade(ctx, min_grade)
+    }
+
+    /// Records the definition of ruleset `version` on-chain. Published rulesets are
+    /// immutable; `bump_ruleset_version` can only switch to a published version.
+    pub fn publish_ruleset(
+        ctx: Context<PublishRuleset>, 
+        version: u16, 
+        definition_hash: [u8; 32], 
+        uri: String, 
+        activation_slot: u64
+    ) -> Result<()> {
+        instructions::publish_ruleset(ctx, version, definition_hash, uri, activation_slot)
+    }
+
+    /// Opens a window in which oracles may keep (or start) attesting under `version`
+    /// while another version is current, so bumps don't strand in-flight transactions.
+    pub fn set_ruleset_validity(
+        ctx: Context<SetRulesetValidity>, 
+        version: u16, 
+        valid_from: i64, 
+        valid_until: i64
+    ) -> Result<()> {
+        instructions::set_ruleset_validity(ctx, version, valid_from, valid_until)
+    }
+
+    pub fn bump_ruleset_version(ctx: Context<BumpRulesetVersion>, v: u16) -> Result<()> {
+        instructions::bump_ruleset_version(ctx, v)
+    }
+
+    pub fn set_timelock_delay(ctx: Context<OnlyAdmin>, delay: i64) -> Result<()> {
+        instructions::set_timelock_delay(ctx, delay)
+    }
+
+    pub fn set_grade_thresholds(
+        ctx: Context<OnlyAdmin>, 
+        verified_bps: u16, 
+        green_bps: u16, 
+        yellow_bps: u16, 
+        red_bps: u16
+    ) -> Result<()> {
+        instructions::set_grade_thresholds(ctx, verified_bps, green_bps, yellow_bps, red_bps)
+    }
+
+    pub fn set_grade_levels(ctx: Context<OnlyAdmin>, levels: u8) -> Result<()> {
+        instructions::set_grade_levels(ctx, levels)
+    }
+
+    pub fn queue_admin_action(ctx: Context<QueueAdminAction>, action: AdminAction) -> Result<()> {
+        instructions::queue_admin_action(ctx, action)
+    }
+
+    pub fn execute_admin_action(ctx: Context<ResolveAdminAction>) -> Result<()> {
+        instructions::execute_admin_action(ctx)
+    }
+
+    pub fn cancel_admin_action(ctx: Context<ResolveAdminAction>) -> Result<()> {
+        instructions::cancel_admin_action(ctx)
+    }
+
+    pub fn propose_admin(ctx: Context<OnlyAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
+    pub fn set_admin_set(ctx: Context<ConfigureAdminSet>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::set_admin_set(ctx, members, threshold)
+    }
+
+    pub fn clear_admin_set(ctx: Context<OnlyAdmin>) -> Result<()> {
+        instructions::clear_admin_set(ctx)
+    }
+
+    /// Hands the registry's upgrade authority to `new_authority`. The authority
+    /// must first be moved to the `upgrade_authority` PDA with the Solana CLI;
+    /// from then on only the admin (or admin set) can move it.
+    pub fn transfer_upgrade_authority(ctx: Context<ManageUpgradeAuthority>) -> Result<()> {
+        instructions::transfer_upgrade_authority(ctx)
+    }
+
+    /// Makes the registry immutable. Irreversible.
+    pub fn burn_upgrade_authority(ctx: Context<ManageUpgradeAuthority>) -> Result<()> {
+        instructions::burn_upgrade_authority(ctx)
+    }
+
+    /// Also open to Config.guardian. Unpausing is not.
+    pub fn pause(ctx: Context<OnlyAdmin>) -> Result<()> {
+        instructions::pause(ctx)
+    }
+
+    pub fn unpause(ctx: Context<OnlyAdmin>) -> Result<()> {
+        instructions::unpause(ctx)
     }
 
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role) -> Result<()> {
+        instructions::grant_role(ctx, role)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        instructions::revoke_role(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn attest_token(
         ctx: Context<AttestToken>, 
         ruleset_version: u16, 
         score: u16, 
         grade: u8, 
+        proofs_hash: [u8; 32],
+        nonce: u64,
+        report: ReportPointer,
+        subscores: [u16; SUBSCORE_COUNT],
+        flags: u64
+    ) -> Result<()> {
+        instructions::attest_token(ctx, ruleset_version, score, grade, proofs_hash, nonce, report, subscores, flags)
+    }
+
+    /// `attest_token` plus a Groth16 proof that the score and grade were produced
+    /// by the circuit committed for `ruleset_version` from the data behind
+    /// `proofs_hash`. Public inputs are listed on `zk_public_inputs`.
+    pub fn attest_with_zk_proof(
+        ctx: Context<AttestWithZkProof>, 
+        ruleset_version: u16, 
+        item: BatchAttestation, 
+        proof: Groth16Proof
+    ) -> Result<()> {
+        instructions::attest_with_zk_proof(ctx, ruleset_version, item, proof)
+    }
+
+    /// First half of a commit-reveal attestation: only `hash` (see commitment_hash)
+    /// is public until `reveal_attestation`, so the grade can't be front-run.
+    pub fn commit_attestation(ctx: Context<CommitAttestation>, hash: [u8; 32]) -> Result<()> {
+        instructions::commit_attestation(ctx, hash)
+    }
+
+    /// `attest_token` for a result committed in an earlier slot. The commitment
+    /// is closed to the oracle.
+    pub fn reveal_attestation(
+        ctx: Context<RevealAttestation>, 
+        ruleset_version: u16, 
+        item: BatchAttestation, 
+        salt: [u8; 32]
+    ) -> Result<()> {
+        instructions::reveal_attestation(ctx, ruleset_version, item, salt)
+    }
+
+    /// Commits the Groth16 verifying key of the scoring circuit for a ruleset version.
+    pub fn set_ruleset_circuit(ctx: Context<SetRulesetCircuit>, ruleset_version: u16, key: Groth16VerifyingKey) -> Result<()> {
+        instructions::set_ruleset_circuit(ctx, ruleset_version, key)
+    }
+
+    /// Relayed attestation: anyone may submit `payload` as long as the preceding
+    /// instruction is an ed25519 program verification of the oracle key's signature
+    /// over `ATTEST_SIGNATURE_DOMAIN || borsh(payload)`. The relayer pays all fees
+    /// and rent, so oracle keys can stay offline.
+    pub fn attest_with_signature(ctx: Context<AttestWithSignature>, payload: SignedAttestation) -> Result<()> {
+        instructions::attest_with_signature(ctx, payload)
+    }
+
+    /// Same as `attest_with_signature` for oracles whose registered key is an EVM
+    /// address: the preceding instruction must be a secp256k1 program verification
+    /// of an EIP-191 (`personal_sign`) signature over `ATTEST_SIGNATURE_DOMAIN || borsh(payload)`.
+    pub fn attest_with_evm_signature(ctx: Context<AttestWithSignature>, payload: SignedAttestation) -> Result<()> {
+        instructions::attest_with_evm_signature(ctx, payload)
+    }
+
+    /// Attests up to `MAX_BATCH_SIZE` mints in one transaction. Remaining
+    /// accounts are (mint, attestation PDA, history PDA, blacklist PDA, latest
+    /// PDA, superseded attestation, ProofMarker PDA, GradeIndexEntry PDA, from
+    /// index page, to index page) groups in item order; mints must be initialized
+    /// SPL Token or Token-2022 mints. The superseded slot is the attestation the
+    /// mint's LatestAttestation points at and the page slots are as in
+    /// GradeIndexPages, each any account when the item does not need it. Missing
+    /// attestation, history, latest, ProofMarker and GradeIndexEntry PDAs are
+    /// created with the signer as rent payer.
+    pub fn attest_tokens_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, AttestTokensBatch<'info>>, 
+        ruleset_version: u16, 
+        items: Vec<BatchAttestation>
+    ) -> Result<()> {
+        instructions::attest_tokens_batch(ctx, ruleset_version, items)
+    }
+
+    /// Commits a merkle root over `leaf_count` RootLeaf entries; individual
+    /// attestations are written later by `materialize_attestation`. Leaves are
+    /// keccak(0x00 || borsh(leaf)), inner nodes keccak(0x01 || min(l, r) || max(l, r)).
+    pub fn post_attestation_root(
+        ctx: Context<PostAttestationRoot>, 
+        ruleset_version: u16, 
+        root: [u8; 32], 
+        leaf_count: u32
+    ) -> Result<()> {
+        instructions::post_attestation_root(ctx, ruleset_version, root, leaf_count)
+    }
+
+    /// Permissionless: writes the Attestation PDA for one leaf of a posted root
+    /// after checking its inclusion proof. The caller pays rent.
+    pub fn materialize_attestation(ctx: Context<MaterializeAttestation>, leaf: RootLeaf, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::materialize_attestation(ctx, leaf, proof)
+    }
+
+    /// Creates a concurrent merkle tree for compressed attestations. The client
+    /// allocates `merkle_tree` (owned by the account-compression program) beforehand;
+    /// the tree's authority is the TreeConfig PDA.
+    pub fn init_compressed_tree(ctx: Context<InitCompressedTree>, max_depth: u32, max_buffer_size: u32) -> Result<()> {
+        instructions::init_compressed_tree(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Appends a compressed attestation. The full leaf is logged through the noop
+    /// program so indexers can rebuild the tree; only its hash is stored on-chain.
+    /// The mint's Blacklist PDA is passed as the only remaining account.
+    pub fn append_compressed_attestation(
+        ctx: Context<ModifyCompressedTree>, 
+        mint: Pubkey, 
+        score: u16, 
+        grade: u8, 
         proofs_hash: [u8; 32]
     ) -> Result<()> {
-        // Validate inputs
-        require!(ctx.accounts.oracle.active, ErrorCode::OracleInactive);
-        require!(grade <= 2, ErrorCode::InvalidGrade);
-        require!(score <= 10000, ErrorCode::InvalidScore);
-        require!(ruleset_version == ctx.accounts.config.ruleset_version, ErrorCode::InvalidRulesetVersion);
-        
-        // Write/overwrite attestation
-        let a = &mut ctx.accounts.attestation;
-        a.mint = ctx.accounts.mint.key();
-        a.ruleset_version = ruleset_version;
-        a.score_bps = score; // score * 10000 (e.g., 0.9123 => 9123)
-        a.grade = grade;     // 0=red,1=yellow,2=green
-        a.proofs_hash = proofs_hash;
-        a.attested_by = ctx.accounts.signer.key();
-        a.attested_at = Clock::get()?.unix_timestamp;
-        a.revoked = false;
-        
-        emit!(TokenAttested {
-            mint: a.mint,
-            ruleset_version,
-            score,
-            grade,
-            attested_by: a.attested_by,
-            attested_at: a.attested_at,
-        });
-        
-        Ok(())
-    }
-
-    pub fn revoke_attestation(ctx: Context<Revoke>) -> Result<()> {
-        let a = &mut ctx.accounts.attestation;
-        require!(!a.revoked, ErrorCode::AlreadyRevoked);
-        
-        a.revoked = true;
-        
-        emit!(AttestationRevoked {
-            mint: a.mint,
-            ruleset_version: a.ruleset_version,
-            admin: ctx.accounts.admin.key(),
-        });
-        
-        Ok(())
+        instructions::append_compressed_attestation(ctx, mint, score, grade, proofs_hash)
     }
-}
 
-#[account]
-pub struct Config {
-    pub admin: Pubkey,
-    pub ruleset_version: u16,
-    pub min_grade: u8,
-    pub bump: u8,
-}
+    /// Replaces a compressed attestation (re-score or revoke). Only the oracle that
+    /// wrote `previous` may replace it; proof nodes are passed as remaining accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub fn replace_compressed_attestation<'info>(
+        ctx: Context<'_, '_, '_, 'info, ModifyCompressedTree<'info>>, 
+        root: [u8; 32], 
+        leaf_index: u32, 
+        previous: CompressedAttestation, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32], 
+        revoked: bool
+    ) -> Result<()> {
+        instructions::replace_compressed_attestation(ctx, root, leaf_index, previous, score, grade, proofs_hash, revoked)
+    }
 
-#[account]
-pub struct Oracle {
-    pub bump: u8,
-    pub active: bool,
-}
+    pub fn set_consensus_params(
+        ctx: Context<OnlyAdmin>, 
+        threshold: u8, 
+        score_tolerance_bps: u16, 
+        min_weight: u32
+    ) -> Result<()> {
+        instructions::set_consensus_params(ctx, threshold, score_tolerance_bps, min_weight)
+    }
 
-#[account]
-pub struct Attestation {
-    pub mint: Pubkey,
-    pub ruleset_version: u16,
-    pub score_bps: u16,
-    pub grade: u8,           // 0=red,1=yellow,2=green
-    pub proofs_hash: [u8; 32],
-    pub attested_by: Pubkey,
-    pub attested_at: i64,
-    pub revoked: bool,
-}
+    pub fn submit_attestation(
+        ctx: Context<SubmitAttestation>, 
+        ruleset_version: u16, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32],
+        report: ReportPointer,
+        subscores: [u16; SUBSCORE_COUNT],
+        flags: u64
+    ) -> Result<()> {
+        instructions::submit_attestation(ctx, ruleset_version, score, grade, proofs_hash, report, subscores, flags)
+    }
 
-#[derive(Accounts)]
-pub struct InitConfig<'info> {
-    #[account(
-        init, 
-        payer = payer, 
-        space = 8 + 32 + 2 + 1 + 1, // discriminator + admin + version + grade + bump
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, Config>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    /// CHECK: Admin pubkey, doesn't need to be signer for init
-    pub admin: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// Materializes the attestation once enough distinct active oracles agree.
+    /// Remaining accounts are (submission, oracle) pairs.
+    pub fn finalize_consensus(ctx: Context<FinalizeConsensus>, ruleset_version: u16) -> Result<()> {
+        instructions::finalize_consensus(ctx, ruleset_version)
+    }
 
-#[derive(Accounts)]
-pub struct OnlyAdmin<'info> {
-    #[account(
-        mut, 
-        has_one = admin,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    pub admin: Signer<'info>,
-}
+    pub fn revoke_attestation(
+        ctx: Context<Revoke>, 
+        ruleset_version: u16, 
+        reason: RevocationReason, 
+        evidence_hash: Option<[u8; 32]>
+    ) -> Result<()> {
+        instructions::revoke_attestation(ctx, ruleset_version, reason, evidence_hash)
+    }
 
-#[derive(Accounts)]
-pub struct UpdateOracle<'info> {
-    #[account(
-        mut, 
-        has_one = admin,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    pub admin: Signer<'info>,
-    #[account(
-        init_if_needed, 
-        seeds = [b"oracle", oracle_key.key().as_ref()], 
-        bump, 
-        payer = admin, 
-        space = 8 + 1 + 1 // discriminator + bump + active
-    )]
-    pub oracle: Account<'info, Oracle>,
-    /// CHECK: Oracle pubkey (not necessarily a signer at creation)
-    pub oracle_key: UncheckedAccount<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// Permanently blacklists `mint`: no oracle path can attest it again and
+    /// verify_attestation treats it as red. There is no way to lift it.
+    pub fn blacklist_mint(ctx: Context<BlacklistMint>, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::blacklist_mint(ctx, evidence_hash)
+    }
 
-#[derive(Accounts)]
-#[instruction(ruleset_version: u16)]
-pub struct AttestToken<'info> {
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    #[account(
-        seeds = [b"oracle", signer.key().as_ref()],
-        bump = oracle.bump
-    )]
-    pub oracle: Account<'info, Oracle>,
-    /// CHECK: token mint
-    pub mint: UncheckedAccount<'info>,
-    #[account(
-        init_if_needed, 
-        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
-        bump, 
-        payer = signer, 
-        space = 8 + 32 + 2 + 2 + 1 + 32 + 32 + 8 + 1 // discriminator + mint + version + score + grade + hash + attester + time + revoked
-    )]
-    pub attestation: Account<'info, Attestation>,
-    #[account(mut)]
-    pub signer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn init_risk_bloom(ctx: Context<InitRiskBloom>) -> Result<()> {
+        instructions::init_risk_bloom(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(ruleset_version: u16)]
-pub struct Revoke<'info> {
-    #[account(
-        mut, 
-        has_one = admin,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-    pub admin: Signer<'info>,
-    /// CHECK: mint pubkey
-    pub mint: UncheckedAccount<'info>,
-    #[account(
-        mut, 
-        seeds = [b"attest", mint.key().as_ref(), &ruleset_version.to_le_bytes()], 
-        bump
-    )]
-    pub attestation: Account<'info, Attestation>,
-}
+    /// Clears the filter. Bits cannot be unset one mint at a time, so reinstated
+    /// and regraded mints linger until a reset; add_to_risk_bloom then refills it.
+    pub fn reset_risk_bloom(ctx: Context<ResetRiskBloom>) -> Result<()> {
+        instructions::reset_risk_bloom(ctx)
+    }
 
-// Events
-#[event]
-pub struct ConfigInitialized {
-    pub admin: Pubkey,
-    pub ruleset_version: u16,
-    pub min_grade: u8,
-}
+    /// Permissionless: adds a blacklisted mint, or one whose attestation under
+    /// `ruleset_version` is revoked or graded red or below, to the RiskBloom.
+    pub fn add_to_risk_bloom(ctx: Context<AddToRiskBloom>, _ruleset_version: u16) -> Result<()> {
+        instructions::add_to_risk_bloom(ctx, _ruleset_version)
+    }
 
-#[event]
-pub struct OracleAdded {
-    pub oracle: Pubkey,
-    pub admin: Pubkey,
-}
+    /// Allowlists a core asset (wSOL, USDC, ...) so verify_attestation treats it as
+    /// green without an attestation.
+    pub fn allowlist_mint(ctx: Context<AllowlistMint>) -> Result<()> {
+        instructions::allowlist_mint(ctx)
+    }
 
-#[event]
-pub struct OracleRemoved {
-    pub oracle: Pubkey,
-    pub admin: Pubkey,
-}
+    pub fn remove_allowlisted_mint(ctx: Context<RemoveAllowlistedMint>) -> Result<()> {
+        instructions::remove_allowlisted_mint(ctx)
+    }
 
-#[event]
-pub struct MinGradeUpdated {
-    pub old_grade: u8,
-    pub new_grade: u8,
-    pub admin: Pubkey,
-}
+    /// Names the SPL Governance (Realms) PDA whose proposals may override grades;
+    /// Pubkey::default() disables overrides without clearing existing ones.
+    pub fn set_governance(ctx: Context<OnlyAdmin>, governance: Pubkey) -> Result<()> {
+        instructions::set_governance(ctx, governance)
+    }
 
-#[event]
-pub struct RulesetVersionBumped {
-    pub old_version: u16,
-    pub new_version: u16,
-    pub admin: Pubkey,
-}
+    /// Executed by a passed governance proposal: pins the mint's grade (extended
+    /// scale) in verify_attestation regardless of what the oracles attest. The
+    /// governance PDA signs via the proposal's CPI; rent comes from `payer`,
+    /// typically the DAO's native treasury.
+    pub fn set_grade_override(ctx: Context<SetGradeOverride>, grade: u8) -> Result<()> {
+        instructions::set_grade_override(ctx, grade)
+    }
 
-#[event]
-pub struct TokenAttested {
-    pub mint: Pubkey,
-    pub ruleset_version: u16,
-    pub score: u16,
-    pub grade: u8,
-    pub attested_by: Pubkey,
-    pub attested_at: i64,
-}
+    /// Executed by a passed governance proposal: drops the override so the
+    /// attested grade applies again, refunding rent to whoever paid for it.
+    pub fn clear_grade_override(ctx: Context<ClearGradeOverride>) -> Result<()> {
+        instructions::clear_grade_override(ctx)
+    }
 
-#[event]
-pub struct AttestationRevoked {
-    pub mint: Pubkey,
-    pub ruleset_version: u16,
-    pub admin: Pubkey,
-}
+    pub fn set_revocation_quorum(ctx: Context<OnlyAdmin>, quorum: u8) -> Result<()> {
+        instructions::set_revocation_quorum(ctx, quorum)
+    }
+
+    pub fn set_downgrade_cosign_delta(ctx: Context<OnlyAdmin>, delta_bps: u16) -> Result<()> {
+        instructions::set_downgrade_cosign_delta(ctx, delta_bps)
+    }
+
+    pub fn set_attestation_cooldown(ctx: Context<OnlyAdmin>, cooldown: i64) -> Result<()> {
+        instructions::set_attestation_cooldown(ctx, cooldown)
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Oracle inactive or not authorized")]
-    OracleInactive,
-    #[msg("Invalid grade value (must be 0-2)")]
-    InvalidGrade,
-    #[msg("Invalid score value (must be 0-10000)")]
-    InvalidScore,
-    #[msg("Invalid ruleset version")]
-    InvalidRulesetVersion,
-    #[msg("Attestation already revoked")]
-    AlreadyRevoked,
+    /// Lets the next write to this attestation skip Config.attestation_cooldown,
+    /// e.g. to correct a grade right after it landed.
+    pub fn waive_attestation_cooldown(ctx: Context<Revoke>, ruleset_version: u16) -> Result<()> {
+        instructions::waive_attestation_cooldown(ctx, ruleset_version)
+    }
+
+    /// Lets the next attest_token, relayed or finalize_consensus write for `mint`
+    /// under `ruleset_version` carry a grade outside the band its score falls in,
+    /// for exceptional cases the thresholds misjudge. The waiver is its own PDA,
+    /// so it also covers a mint's first attestation; the write that passes it
+    /// closes it and refunds `payer`.
+    /// Full admin only: no role grant stands in for it.
+    pub fn waive_grade_consistency(ctx: Context<WaiveGradeConsistency>, ruleset_version: u16) -> Result<()> {
+        instructions::waive_grade_consistency(ctx, ruleset_version)
+    }
+
+    /// Records an active oracle's vote to revoke; the vote reaching
+    /// Config.revocation_quorum revokes the attestation with its reason and evidence.
+    /// Votes only count toward the attestation as currently written, so a
+    /// re-attestation starts a fresh tally.
+    pub fn vote_revocation(
+        ctx: Context<VoteRevocation>, 
+        _ruleset_version: u16, 
+        reason: RevocationReason, 
+        evidence_hash: [u8; 32]
+    ) -> Result<()> {
+        instructions::vote_revocation(ctx, _ruleset_version, reason, evidence_hash)
+    }
+
+    pub fn reinstate_attestation(ctx: Context<Revoke>, ruleset_version: u16) -> Result<()> {
+        instructions::reinstate_attestation(ctx, ruleset_version)
+    }
+
+    /// Suspends an attestation while an investigation runs. Unlike revocation the
+    /// score and grade are kept, and unfreezing restores the attestation as it was.
+    /// Also open to Config.guardian. Unfreezing is not.
+    pub fn freeze_attestation(ctx: Context<Revoke>, ruleset_version: u16, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::freeze_attestation(ctx, ruleset_version, evidence_hash)
+    }
+
+    pub fn unfreeze_attestation(ctx: Context<Revoke>, ruleset_version: u16) -> Result<()> {
+        instructions::unfreeze_attestation(ctx, ruleset_version)
+    }
+
+    /// Allowlists a partner registry's Wormhole emitter on `chain`. Setting the
+    /// address to all zeroes disables the chain.
+    pub fn set_foreign_emitter(ctx: Context<SetForeignEmitter>, chain: u16, address: [u8; 32]) -> Result<()> {
+        instructions::set_foreign_emitter(ctx, chain, address)
+    }
+
+    /// Mirrors an attestation published by an allowlisted partner registry. The VAA
+    /// must already be posted to the Wormhole core bridge; its payload uses the same
+    /// layout as `Attestation::wormhole_payload`. Each VAA can be applied once, only
+    /// under an active ruleset version, and never replaces a newer attestation or
+    /// one written on this chain.
+    pub fn receive_foreign_attestation(ctx: Context<ReceiveForeignAttestation>, _vaa_hash: [u8; 32]) -> Result<()> {
+        instructions::receive_foreign_attestation(ctx, _vaa_hash)
+    }
+
+    /// Posts the attestation to Wormhole so it can be consumed on other chains.
+    /// Permissionless: the caller pays the bridge fee and the message account rent.
+    /// The payload layout is documented on `Attestation::wormhole_payload`.
+    pub fn publish_attestation(ctx: Context<PublishAttestation>, _ruleset_version: u16, batch_id: u32) -> Result<()> {
+        instructions::publish_attestation(ctx, _ruleset_version, batch_id)
+    }
+
+    /// Returns (as return data) the attestation's grade on the extended scale with
+    /// the registry's staleness policy applied. Fails for revoked or expired attestations.
+    pub fn get_effective_grade(ctx: Context<ReadAttestation>, _ruleset_version: u16) -> Result<u8> {
+        instructions::get_effective_grade(ctx, _ruleset_version)
+    }
+
+    /// Returns (as return data) the attestation's AttestationStatus. Unlike
+    /// get_effective_grade this never fails on the attestation's state.
+    pub fn get_attestation_status(ctx: Context<ReadAttestation>, _ruleset_version: u16) -> Result<AttestationStatus> {
+        instructions::get_attestation_status(ctx, _ruleset_version)
+    }
+
+    /// CPI gate for integrating programs: succeeds only if the mint's attestation
+    /// is unrevoked, unexpired, no older than `max_age_secs` and its effective grade
+    /// (extended scale) is at least `min_grade`. Each failure has its own error code.
+    ///
+    /// With a ConsumerConfig the consumer's accepted ruleset versions and minimum
+    /// grade apply on top, and its max age fills in when the caller passes none.
+    /// The consumer program must sign for its `[b"solguard_caller"]` PDA as
+    /// `caller`, so no other program can borrow its policy. Otherwise the registry's active versions are accepted and its default age applies.
+    /// A version's `ActiveRuleset::min_grade` always raises the minimum.
+    ///
+    /// An attestation superseded by a newer ruleset version's is refused unless
+    /// the calling program's ConsumerConfig lists its version explicitly.
+    ///
+    /// Allowlisted mints count as green without an attestation. A governance
+    /// GradeOverride replaces both the attestation and the allowlist grade. A
+    /// blacklisted mint counts as red whatever any of them say.
+    ///
+    /// While Config.subscription_price is set, a calling program must pass its
+    /// Subscription and sign as `caller`, and the subscription must cover
+    /// the current epoch.
+    pub fn verify_attestation(
+        ctx: Context<VerifyAttestation>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>
+    ) -> Result<()> {
+        instructions::verify_attestation(ctx, ruleset_version, min_grade, max_age_secs)
+    }
+
+    /// Grades a Metaplex collection as a whole. With `inherit` set, NFTs verified
+    /// into the collection pass verify_collection_item on this grade; with
+    /// `item_override` set, an item's own attestation takes precedence.
+    pub fn attest_collection(
+        ctx: Context<AttestCollection>, 
+        ruleset_version: u16, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32], 
+        inherit: bool, 
+        item_override: bool
+    ) -> Result<()> {
+        instructions::attest_collection(ctx, ruleset_version, score, grade, proofs_hash, inherit, item_override)
+    }
+
+    pub fn revoke_collection_attestation(
+        ctx: Context<RevokeCollection>, 
+        ruleset_version: u16, 
+        reason: RevocationReason
+    ) -> Result<()> {
+        instructions::revoke_collection_attestation(ctx, ruleset_version, reason)
+    }
+
+    /// verify_attestation for an NFT through its verified Metaplex collection.
+    /// The item's own attestation is used when the collection allows overrides
+    /// or does not pass its grade down; otherwise the collection's grade applies.
+    /// A blacklisted item counts as red either way.
+    pub fn verify_collection_item(
+        ctx: Context<VerifyCollectionItem>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>
+    ) -> Result<()> {
+        instructions::verify_collection_item(ctx, ruleset_version, min_grade, max_age_secs)
+    }
+
+    /// Grades a deployed program. The upgrade authority and last deploy slot are
+    /// read from its ProgramData (none for immutable programs) and recorded next to
+    /// `build_hash`, the hash of the verified build the oracle matched on-chain.
+    pub fn attest_program(
+        ctx: Context<AttestProgram>, 
+        ruleset_version: u16, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32], 
+        build_hash: [u8; 32]
+    ) -> Result<()> {
+        instructions::attest_program(ctx, ruleset_version, score, grade, proofs_hash, build_hash)
+    }
+
+    pub fn revoke_program_attestation(
+        ctx: Context<RevokeProgram>, 
+        ruleset_version: u16, 
+        reason: RevocationReason
+    ) -> Result<()> {
+        instructions::revoke_program_attestation(ctx, ruleset_version, reason)
+    }
+
+    /// CPI gate for programs: like verify_attestation, and additionally fails if
+    /// the program was redeployed or changed upgrade authority since it was graded.
+    /// With `require_immutable` an upgradeable program fails regardless of grade.
+    pub fn verify_program(
+        ctx: Context<VerifyProgram>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>, 
+        require_immutable: bool
+    ) -> Result<()> {
+        instructions::verify_program(ctx, ruleset_version, min_grade, max_age_secs, require_immutable)
+    }
+
+    /// Grades a deployer or creator wallet so consumers can refuse tokens from
+    /// serial ruggers whatever the individual mint looks like.
+    pub fn attest_wallet(
+        ctx: Context<AttestWallet>, 
+        ruleset_version: u16, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32], 
+        tokens_created: u32, 
+        tokens_rugged: u32
+    ) -> Result<()> {
+        instructions::attest_wallet(ctx, ruleset_version, score, grade, proofs_hash, tokens_created, tokens_rugged)
+    }
+
+    pub fn revoke_wallet_attestation(
+        ctx: Context<RevokeWallet>, 
+        ruleset_version: u16, 
+        reason: RevocationReason
+    ) -> Result<()> {
+        instructions::revoke_wallet_attestation(ctx, ruleset_version, reason)
+    }
+
+    /// CPI gate for wallets, e.g. a launchpad checking a token's creator. Fails
+    /// below `min_grade` or when the wallet rugged more than `max_rugged` tokens.
+    pub fn verify_wallet(
+        ctx: Context<VerifyWallet>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>, 
+        max_rugged: Option<u32>
+    ) -> Result<()> {
+        instructions::verify_wallet(ctx, ruleset_version, min_grade, max_age_secs, max_rugged)
+    }
+
+    /// Grades a liquidity pool next to its tokens' attestations. `lp_burned_bps`
+    /// and `lp_locked_bps` are the shares of LP supply burned and locked until
+    /// `lp_unlock_at` (0 = no lock) as observed by the oracle.
+    pub fn attest_pool(
+        ctx: Context<AttestPool>, 
+        ruleset_version: u16, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32], 
+        base_mint: Pubkey, 
+        quote_mint: Pubkey, 
+        lp_burned_bps: u16, 
+        lp_locked_bps: u16, 
+        lp_unlock_at: i64
+    ) -> Result<()> {
+        instructions::attest_pool(ctx, ruleset_version, score, grade, proofs_hash, base_mint, quote_mint, lp_burned_bps, lp_locked_bps, lp_unlock_at)
+    }
+
+    pub fn revoke_pool_attestation(
+        ctx: Context<RevokePool>, 
+        ruleset_version: u16, 
+        reason: RevocationReason
+    ) -> Result<()> {
+        instructions::revoke_pool_attestation(ctx, ruleset_version, reason)
+    }
+
+    /// CPI gate for pools: fails below `min_grade` or when less than
+    /// `min_lp_secured_bps` of LP supply is burned or still locked.
+    pub fn verify_pool(
+        ctx: Context<VerifyPool>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>, 
+        min_lp_secured_bps: u16
+    ) -> Result<()> {
+        instructions::verify_pool(ctx, ruleset_version, min_grade, max_age_secs, min_lp_secured_bps)
+    }
+
+    /// Grades a trading pair on a venue, for risks single-mint grades miss such
+    /// as an impersonated quote token or a spoofed market. `mint_a` must sort
+    /// below `mint_b` so each pair has one PDA per venue.
+    pub fn attest_pair(
+        ctx: Context<AttestPair>, 
+        ruleset_version: u16, 
+        score: u16, 
+        grade: u8, 
+        proofs_hash: [u8; 32], 
+        risk_flags: u16
+    ) -> Result<()> {
+        instructions::attest_pair(ctx, ruleset_version, score, grade, proofs_hash, risk_flags)
+    }
+
+    pub fn revoke_pair_attestation(
+        ctx: Context<RevokePair>, 
+        ruleset_version: u16, 
+        reason: RevocationReason
+    ) -> Result<()> {
+        instructions::revoke_pair_attestation(ctx, ruleset_version, reason)
+    }
+
+    /// CPI gate for routing through a pair: fails below `min_grade` or when the
+    /// pair carries any of `forbidden_risk_flags` (PAIR_RISK_*).
+    pub fn verify_pair(
+        ctx: Context<VerifyPair>, 
+        ruleset_version: u16, 
+        min_grade: u8, 
+        max_age_secs: Option<i64>, 
+        forbidden_risk_flags: u16
+    ) -> Result<()> {
+        instructions::verify_pair(ctx, ruleset_version, min_grade, max_age_secs, forbidden_risk_flags)
+    }
+
+    /// First half of a downgrade Config::requires_downgrade_cosign keeps away from
+    /// a single oracle: parks the result until `cosign_downgrade`. The fee is
+    /// charged here. A pending proposal can only be replaced by its proposer or
+    /// once the attestation has been rewritten under it.
+    pub fn propose_downgrade(ctx: Context<ProposeDowngrade>, ruleset_version: u16, item: BatchAttestation) -> Result<()> {
+        instructions::propose_downgrade(ctx, ruleset_version, item)
+    }
+
+    /// Applies a pending downgrade once a second active oracle or a Revoker
+    /// signs off. The proposer stays `attested_by`; the rent goes back to the
+    /// proposal's payer.
+    pub fn cosign_downgrade(ctx: Context<CosignDowngrade>, ruleset_version: u16) -> Result<()> {
+        instructions::cosign_downgrade(ctx, ruleset_version)
+    }
+
+    /// Starts watching a mint. The watcher turns dirty whenever one of the mint's
+    /// attestations is written or revoked, until `acknowledge_watcher`.
+    pub fn register_watcher(ctx: Context<RegisterWatcher>) -> Result<()> {
+        instructions::register_watcher(ctx)
+    }
+
+    /// Marks every change so far as seen, clearing the watcher's dirty state.
+    pub fn acknowledge_watcher(ctx: Context<AcknowledgeWatcher>) -> Result<()> {
+        instructions::acknowledge_watcher(ctx)
+    }
+
+    /// Stops watching and returns the watcher's rent to its owner.
+    pub fn unregister_watcher(ctx: Context<UnregisterWatcher>) -> Result<()> {
+        instructions::unregister_watcher(ctx)
+    }
+
+    /// Registers or updates a consumer program's verification policy. Only the
+    /// consumer program's upgrade authority may do this.
+    ///
+    /// `accepted_versions` and the inclusive `min_version..=max_version` range pin
+    /// the ruleset versions verify_attestation accepts for this consumer; with
+    /// neither set it follows the registry's active versions. `max_version = 0`
+    /// leaves the range unset.
+    pub fn set_consumer_config(
+        ctx: Context<SetConsumerConfig>, 
+        min_grade: u8, 
+        max_age: Option<i64>, 
+        accepted_versions: Vec<u16>, 
+        min_version: u16, 
+        max_version: u16
+    ) -> Result<()> {
+        instructions::set_consumer_config(ctx, min_grade, max_age, accepted_versions, min_version, max_version)
+    }
+
+    /// Pays for `epochs` further epochs of `consumer_program`'s subscription, starting
+    /// after the paid-through epoch or at the current one if it has lapsed. Anyone
+    /// may fund a subscription; payment goes to the fee vault.
+    pub fn fund_subscription(ctx: Context<FundSubscription>, epochs: u64) -> Result<()> {
+        instructions::fund_subscription(ctx, epochs)
+    }
+
+    pub fn set_subscription_price(ctx: Context<OnlyAdmin>, price: u64) -> Result<()> {
+        instructions::set_subscription_price(ctx, price)
+    }
+
+    /// Replaces the set of non-current ruleset versions that stay verifiable, each
+    /// with an optional grade floor. The current version may be listed to give it a floor.
+    pub fn set_active_rulesets(ctx: Context<OnlyAdmin>, rulesets: Vec<ActiveRuleset>) -> Result<()> {
+        instructions::set_active_rulesets(ctx, rulesets)
+    }
+
+    pub fn set_default_max_age(ctx: Context<OnlyAdmin>, max_age_secs: i64) -> Result<()> {
+        instructions::set_default_max_age(ctx, max_age_secs)
+    }
+
+    pub fn renew_attestation(ctx: Context<RenewAttestation>, proofs_hash: [u8; 32]) -> Result<()> {
+        instructions::renew_attestation(ctx, proofs_hash)
+    }
+
+    /// Publishes the per-category evidence hashes behind the attestation's
+    /// `proofs_hash`, which must equal category_proofs_hash(category_proofs).
+    /// Categories without evidence carry a zero hash. Overwrites clear them.
+    pub fn attach_category_proofs(ctx: Context<AttachCategoryProofs>, category_proofs: [[u8; 32]; SUBSCORE_COUNT]) -> Result<()> {
+        instructions::attach_category_proofs(ctx, category_proofs)
+    }
+
+    /// Declares that scores under `from_version` mean the same under `to_version`,
+    /// allowing `migrate_attestation` between them.
+    pub fn declare_ruleset_compatible(ctx: Context<DeclareRulesetCompatible>, from_version: u16, to_version: u16) -> Result<()> {
+        instructions::declare_ruleset_compatible(ctx, from_version, to_version)
+    }
+
+    /// Permissionless: re-tags an unrevoked attestation under a compatible newer
+    /// version instead of waiting for a full re-attestation. The old PDA is closed
+    /// to its rent payer; the caller pays for the new one. Fails if the mint already
+    /// has an attestation under `to_version`.
+    pub fn migrate_attestation(ctx: Context<MigrateAttestation>, from_version: u16, to_version: u16) -> Result<()> {
+        instructions::migrate_attestation(ctx, from_version, to_version)
+    }
+
+    /// Permissionless: upgrades a Config, Oracle, RegistryStats or Attestation written under an
+    /// older layout in place, growing it (the caller tops up rent) when fields were
+    /// added. Accounts already at the current layout version are left untouched.
+    pub fn migrate_account(ctx: Context<MigrateAccount>, kind: AccountKind) -> Result<()> {
+        instructions::migrate_account(ctx, kind)
+    }
+
+    /// Permissionless: marks the attestations passed as remaining accounts expired
+    /// once their `valid_until` has passed, paying `Config.crank_reward` per newly
+    /// marked attestation from whatever the fee vault holds above rent. Accounts
+    /// that are revoked, already marked or not yet expired are skipped, so
+    /// racing crankers don't fail each other's transactions.
+    pub fn crank_expire_attestations<'info>(ctx: Context<'_, '_, '_, 'info, CrankExpire<'info>>) -> Result<()> {
+        instructions::crank_expire_attestations(ctx)
+    }
+
+    /// Permissionless: freezes RegistryStats into the current epoch's snapshot.
+    /// The first call in an epoch wins; snapshots are never rewritten.
+    pub fn snapshot_epoch(ctx: Context<SnapshotEpoch>, epoch: u64) -> Result<()> {
+        instructions::snapshot_epoch(ctx, epoch)
+    }
+
+    /// Permissionless: creates page `page` of `grade`'s index. Pages are created
+    /// in order so readers can walk them until the first missing one.
+    pub fn init_grade_index_page(ctx: Context<InitGradeIndexPage>, grade: u8, page: u32) -> Result<()> {
+        instructions::init_grade_index_page(ctx, grade, page)
+    }
+
+    /// Permissionless: brings the mint's GradeIndexEntry in line with its latest
+    /// attestation. Lists mints attested before the index existed, and catches up
+    /// after paths that do not carry the index accounts (cosign_downgrade,
+    /// migrate_attestation, or reinstating a mint that was never listed).
+    pub fn sync_grade_index(ctx: Context<SyncGradeIndex>) -> Result<()> {
+        instructions::sync_grade_index(ctx)
+    }
+
+    pub fn close_attestation(ctx: Context<CloseAttestation>) -> Result<()> {
+        instructions::close_attestation(ctx)
+    }
+
+    pub fn set_dispute_bond(ctx: Context<OnlyAdmin>, bond: u64) -> Result<()> {
+        instructions::set_dispute_bond(ctx, bond)
+    }
+
+    pub fn file_dispute(ctx: Context<FileDispute>, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::file_dispute(ctx, evidence_hash)
+    }
+
+    /// Settles an open dispute. A sustained dispute revokes the attestation and
+    /// pays the challenger their bond plus `reward` lamports slashed from the
+    /// oracle's stake; a dismissed dispute forfeits the bond to the fee vault.
+    /// Once the dispute council has reached quorum on it, anyone may call this
+    /// to apply the council's decision; `sustain` is then ignored and the reward
+    /// is capped at the challenger's bond.
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, sustain: bool, reward: u64) -> Result<()> {
+        instructions::resolve_dispute(ctx, sustain, reward)
+    }
+
+    pub fn set_crank_reward(ctx: Context<OnlyAdmin>, reward: u64) -> Result<()> {
+        instructions::set_crank_reward(ctx, reward)
+    }
+
+    /// Sets the incident-response key. Pubkey::default() removes it.
+    pub fn set_guardian(ctx: Context<OnlyAdmin>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian(ctx, guardian)
+    }
+
+    pub fn set_challenge_params(ctx: Context<OnlyAdmin>, bond: u64, window: i64) -> Result<()> {
+        instructions::set_challenge_params(ctx, bond, window)
+    }
+
+    /// Posts Config.challenge_bond against an attestation, which fails verification
+    /// until `resolve_challenge`. Unlike `file_dispute` the outcome is settled
+    /// economically: the oracle must answer within Config.challenge_window or the
+    /// challenge is upheld by default.
+    pub fn challenge_attestation(ctx: Context<ChallengeAttestation>, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::challenge_attestation(ctx, evidence_hash)
+    }
+
+    /// The challenged oracle stands by its attestation, leaving the outcome to
+    /// `resolve_challenge`.
+    pub fn answer_challenge(ctx: Context<AnswerChallenge>, response_hash: [u8; 32]) -> Result<()> {
+        instructions::answer_challenge(ctx, response_hash)
+    }
+
+    /// Settles a challenge. Unanswered past the deadline, anyone may call it and
+    /// the challenge is upheld; likewise once the dispute council has reached
+    /// quorum on it. Otherwise a Revoker decides via `upheld`. Upheld
+    /// revokes the attestation and returns the bond to the challenger with up to
+    /// the same amount slashed from the oracle's stake; rejected pays the bond
+    /// to the oracle. The challenge account's rent goes back to the challenger.
+    pub fn resolve_challenge(ctx: Context<ResolveChallenge>, upheld: bool) -> Result<()> {
+        instructions::resolve_challenge(ctx, upheld)
+    }
+
+    /// Replaces the dispute council. Votes cast under the previous membership
+    /// stop counting.
+    pub fn set_council(ctx: Context<ConfigureCouncil>, members: Vec<Pubkey>, quorum: u8) -> Result<()> {
+        instructions::set_council(ctx, members, quorum)
+    }
+
+    /// A council member's vote on an open Dispute or Challenge (`case`). The
+    /// first side to reach the council's quorum decides it; apply the decision
+    /// with `resolve_dispute` or `resolve_challenge`.
+    pub fn vote_on_dispute(ctx: Context<VoteOnDispute>, uphold: bool) -> Result<()> {
+        instructions::vote_on_dispute(ctx, uphold)
+    }
+
+    pub fn set_report_bond(ctx: Context<OnlyAdmin>, bond: u64) -> Result<()> {
+        instructions::set_report_bond(ctx, bond)
+    }
+
+    /// Flags a token with an unrevoked attestation as a suspected rug, posting
+    /// Config.report_bond. If the attestation is later revoked the reporter can
+    /// be awarded a bounty from the fee vault.
+    pub fn submit_report(ctx: Context<SubmitReport>, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::submit_report(ctx, evidence_hash)
+    }
+
+    /// Pays an open report whose attestation has since been revoked: the bond is
+    /// returned and `bounty` lamports are paid from the fee vault, which keeps its
+    /// rent-exempt minimum.
+    pub fn award_bounty(ctx: Context<AwardBounty>, bounty: u64) -> Result<()> {
+        instructions::award_bounty(ctx, bounty)
+    }
+
+    /// Closes out an unfounded report, forfeiting its bond to the fee vault.
+    pub fn reject_report(ctx: Context<RejectReport>) -> Result<()> {
+        instructions::reject_report(ctx)
+    }
+
+    pub fn revoke_own_attestation(ctx: Context<RevokeOwn>) -> Result<()> {
+        instructions::revoke_own_attestation(ctx)
+    }
 }
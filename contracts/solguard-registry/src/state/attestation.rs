@@ -0,0 +1,512 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::*;
+
+/// Zero-copy so CPI consumers can read it without a borsh pass. Fields are ordered
+/// by alignment so the `repr(C)` layout has no implicit padding; offsets below
+/// include the 8-byte discriminator and are stable for memcmp filters:
+///
+/// | offset | field               | offset | field             |
+/// |--------|---------------------|--------|-------------------|
+/// | 8      | mint                | 226    | score_bps         |
+/// | 40     | attested_by         | 228    | origin_chain      |
+/// | 72     | payer               | 230    | subscores         |
+/// | 104    | proofs_hash         | 240    | grade             |
+/// | 136    | revocation_evidence | 241    | grade_levels      |
+/// | 168    | report_content_id   | 242    | revoked           |
+/// | 200    | attested_at         | 243    | oracle_count      |
+/// | 208    | valid_until         | 244    | revocation_reason |
+/// | 216    | nonce               | 245    | report_scheme     |
+/// | 224    | ruleset_version     | 246    | zk_verified       |
+/// |        |                     | 247    | version           |
+/// |        |                     | 248    | frozen            |
+/// |        |                     | 249    | decimals          |
+/// |        |                     | 250    | risk_flags        |
+/// |        |                     | 252    | cooldown_waived   |
+/// |        |                     | 253    | challenged        |
+/// |        |                     | 254    | expired           |
+/// |        |                     | 255    | superseded        |
+/// |        |                     | 256    | metadata_hash     |
+/// |        |                     | 288    | supply            |
+/// |        |                     | 296    | snapshot_slot     |
+/// |        |                     | 304    | mint_authority    |
+/// |        |                     | 336    | freeze_authority  |
+/// |        |                     | 368    | category_proofs   |
+/// |        |                     | 528    | flags             |
+/// |        |                     | 536    | supersedes        |
+#[account(zero_copy)]
+pub struct Attestation {
+    pub mint: Pubkey,
+    pub attested_by: Pubkey,
+    pub payer: Pubkey,       // rent payer, refunded by close_attestation
+    pub proofs_hash: [u8; 32],
+    pub revocation_evidence: [u8; 32],
+    pub report_content_id: [u8; 32], // see report()
+    pub attested_at: i64,
+    pub valid_until: i64,    // 0 = never expires
+    pub nonce: u64,          // bumped on every overwrite
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub origin_chain: u16,   // Wormhole chain id of a mirrored attestation, 0 when attested here
+    pub subscores: [u16; SUBSCORE_COUNT], // basis points per category, see SUBSCORE_*
+    pub grade: u8,           // on the `grade_levels` scale
+    pub grade_levels: u8,    // scale `grade` was issued on, 0 for attestations predating the field
+    pub revoked: u8,         // bool, see is_revoked()
+    pub oracle_count: u8,    // distinct oracles behind this attestation (1 outside consensus mode)
+    pub revocation_reason: u8,        // RevocationReason, 0 while not revoked
+    pub report_scheme: u8,   // REPORT_SCHEME_*
+    pub zk_verified: u8,     // bool: score proven against the ruleset circuit
+    pub version: u8,         // ATTESTATION_LAYOUT_VERSION
+    pub frozen: u8,          // bool: suspended pending investigation, see is_frozen()
+    pub decimals: u8,        // mint decimals at snapshot_slot
+    pub risk_flags: u16,     // RISK_* extensions on the mint at snapshot_slot
+    pub cooldown_waived: u8, // bool: next write skips Config.attestation_cooldown, see waive_attestation_cooldown
+    pub challenged: u8,      // bool: a Challenge is open against this attestation, see is_challenged()
+    pub expired: u8,         // bool: marked by crank_expire_attestations once valid_until passed
+    pub superseded: u8,      // bool: a newer ruleset version's attestation replaced this one, see is_superseded()
+    pub metadata_hash: [u8; 32], // see metadata_hash(), zero when attested without the Metaplex metadata
+    pub supply: u64,         // mint supply at snapshot_slot
+    pub snapshot_slot: u64,  // slot the mint was read at, 0 on attestations written before every path read it
+    pub mint_authority: Pubkey,   // at snapshot_slot, default when unset
+    pub freeze_authority: Pubkey, // at snapshot_slot, default when unset
+    pub category_proofs: [[u8; 32]; SUBSCORE_COUNT], // per-subscore evidence hashes, see attach_category_proofs; zero until attached
+    pub flags: u64,          // FLAG_* reasons reported by the oracle
+    pub supersedes: Pubkey,  // attestation under the older ruleset version this one replaced, default when none
+}
+
+const _: () = assert!(std::mem::size_of::<Attestation>() == 560);
+
+impl Attestation {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked != 0
+    }
+
+    pub fn is_zk_verified(&self) -> bool {
+        self.zk_verified != 0
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen != 0
+    }
+
+    pub fn is_expired_flagged(&self) -> bool {
+        self.expired != 0
+    }
+
+    pub fn is_challenged(&self) -> bool {
+        self.challenged != 0
+    }
+
+    pub fn is_superseded(&self) -> bool {
+        self.superseded != 0
+    }
+
+    /// Normalized grade while this holds an unrevoked attestation, for RegistryStats.
+    pub fn live_grade(&self) -> Option<u8> {
+        (self.attested_at != 0 && !self.is_revoked()).then(|| self.normalized_grade())
+    }
+
+    /// Score and normalized grade while unrevoked, see Config::requires_downgrade_cosign.
+    pub fn live_score(&self) -> Option<(u16, u8)> {
+        self.live_grade().map(|grade| (self.score_bps, grade))
+    }
+
+    /// Score and normalized grade before an overwrite, None for a fresh account.
+    pub fn prior_score(&self) -> Option<(u16, u8)> {
+        (self.attested_at != 0).then(|| (self.score_bps, self.normalized_grade()))
+    }
+
+    pub fn report(&self) -> ReportPointer {
+        ReportPointer { scheme: self.report_scheme, content_id: self.report_content_id }
+    }
+
+    pub fn set_report(&mut self, report: ReportPointer) {
+        self.report_scheme = report.scheme;
+        self.report_content_id = report.content_id;
+    }
+
+    pub fn set_mint_snapshot(&mut self, mint: &Mint, risk_flags: u16, slot: u64) {
+        self.decimals = mint.decimals;
+        self.supply = mint.supply;
+        self.risk_flags = risk_flags;
+        self.snapshot_slot = slot;
+        self.mint_authority = Option::<Pubkey>::from(mint.mint_authority).unwrap_or_default();
+        self.freeze_authority = Option::<Pubkey>::from(mint.freeze_authority).unwrap_or_default();
+    }
+
+    /// Whether `mint` still has the authorities seen when it was graded. Always
+    /// false without a snapshot, so consumers that require it fail closed.
+    pub fn authorities_unchanged(&self, mint: &Mint) -> bool {
+        self.snapshot_slot != 0
+            && Option::<Pubkey>::from(mint.mint_authority).unwrap_or_default() == self.mint_authority
+            && Option::<Pubkey>::from(mint.freeze_authority).unwrap_or_default() == self.freeze_authority
+    }
+
+    /// Call after the grade and snapshot are written.
+    pub fn require_extensions_allowed(&self) -> Result<()> {
+        require!(
+            self.normalized_grade() < GRADE_GREEN || self.risk_flags & HIGH_GRADE_FORBIDDEN_RISK_FLAGS == 0, 
+            ErrorCode::ForbiddenMintExtension
+        );
+        Ok(())
+    }
+
+    /// `grade` on the extended five-level scale, regardless of the scale it was issued on.
+    /// Consumers comparing grades across attestations should use this.
+    pub fn normalized_grade(&self) -> u8 {
+        let levels = if self.grade_levels == 0 { GRADE_LEVELS_LEGACY } else { self.grade_levels };
+        normalize_grade(self.grade, levels)
+    }
+
+    pub fn revoke(&mut self, reason: RevocationReason, evidence_hash: [u8; 32]) {
+        self.revoked = 1;
+        self.revocation_reason = reason as u8;
+        self.revocation_evidence = evidence_hash;
+    }
+
+    pub fn clear_revocation(&mut self) {
+        self.revoked = 0;
+        self.revocation_reason = 0;
+        self.revocation_evidence = [0; 32];
+    }
+
+    /// A write stamped `attested_at` must come at least `cooldown` seconds after
+    /// the previous one unless an admin waived it; consumes the waiver.
+    pub fn consume_cooldown(&mut self, cooldown: i64, attested_at: i64) -> Result<()> {
+        require!(
+            self.attested_at == 0 || self.cooldown_waived != 0 || attested_at - self.attested_at >= cooldown, 
+            ErrorCode::AttestationCooldown
+        );
+        self.cooldown_waived = 0;
+        Ok(())
+    }
+
+    /// Overwrites must carry exactly the next nonce so a delayed or re-landed
+    /// transaction cannot clobber a newer result.
+    pub fn advance_nonce(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce == self.nonce + 1, ErrorCode::StaleNonce);
+        self.nonce = nonce;
+        Ok(())
+    }
+
+    /// Normalized grade with the registry's staleness policy applied: once stale,
+    /// anything above yellow reads as yellow until the attestation is refreshed.
+    pub fn effective_grade(&self, config: &Config, now: i64) -> u8 {
+        let grade = self.normalized_grade();
+        if config.is_stale(self.attested_at, now) {
+            grade.min(GRADE_YELLOW)
+        } else {
+            grade
+        }
+    }
+
+    /// Fails with `AttestationStale` once older than `max_age` seconds (0 = any age).
+    pub fn require_fresh(&self, now: i64, max_age: i64) -> Result<()> {
+        require!(
+            max_age == 0 || now.saturating_sub(self.attested_at) <= max_age, 
+            ErrorCode::AttestationStale
+        );
+        Ok(())
+    }
+
+    /// Cross-chain message body, fixed-width and big-endian for EVM decoding:
+    /// payload id (1) | mint (32) | ruleset version (2) | score bps (2) |
+    /// grade on the extended scale (1) | proofs hash (32) | attested_at (8)
+    pub fn wormhole_payload(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(WORMHOLE_PAYLOAD_LEN);
+        payload.push(WORMHOLE_PAYLOAD_ATTESTATION);
+        payload.extend_from_slice(self.mint.as_ref());
+        payload.extend_from_slice(&self.ruleset_version.to_be_bytes());
+        payload.extend_from_slice(&self.score_bps.to_be_bytes());
+        payload.push(self.normalized_grade());
+        payload.extend_from_slice(&self.proofs_hash);
+        payload.extend_from_slice(&self.attested_at.to_be_bytes());
+        payload
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.valid_until != 0 && now > self.valid_until
+    }
+
+    /// Lifecycle state at `now`. Revocation outranks review, which outranks expiry.
+    pub fn status(&self, now: i64) -> AttestationStatus {
+        if self.is_revoked() {
+            AttestationStatus::Revoked
+        } else if self.is_frozen() || self.is_challenged() {
+            AttestationStatus::UnderReview
+        } else if self.is_expired(now) {
+            AttestationStatus::Expired
+        } else {
+            AttestationStatus::Active
+        }
+    }
+
+    /// Verification paths must treat anything but an Active attestation as unusable.
+    pub fn require_valid(&self, now: i64) -> Result<()> {
+        match self.status(now) {
+            AttestationStatus::Active => Ok(()),
+            AttestationStatus::Revoked => err!(ErrorCode::AttestationRevoked),
+            AttestationStatus::UnderReview if self.is_frozen() => err!(ErrorCode::AttestationFrozen),
+            AttestationStatus::UnderReview => err!(ErrorCode::AttestationUnderChallenge),
+            AttestationStatus::Expired => err!(ErrorCode::AttestationExpired),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    Config,
+    Oracle,
+    Attestation,
+    RegistryStats,
+}
+
+/// Path that revoked an attestation, reported by AttestationRevokedV2.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RevocationSource {
+    Admin,   // revoke_attestation
+    Quorum,  // vote_revocation
+    Dispute, // resolve_dispute, sustained
+    Oracle,  // revoke_own_attestation
+    Challenge, // resolve_challenge, upheld or unanswered
+}
+
+/// Derived from the `revoked`, `frozen`, `challenged` and `expired` bytes, which
+/// stay separate on the account so existing memcmp filters keep working. Each
+/// transition has its own gate:
+/// - UnderReview: freeze_attestation (Guardian or Config.guardian), or a bonded
+///   challenge_attestation. Left by unfreeze_attestation (Guardian) or by
+///   resolving the challenge.
+/// - Revoked: revoke_attestation (Revoker), a revocation quorum, a dispute or
+///   challenge upheld, or the attester itself. Left by reinstate_attestation or
+///   a fresh attestation.
+/// - Expired: valid_until passing; crank_expire_attestations only records it.
+///   Left by renew_attestation or a fresh attestation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttestationStatus {
+    Active,
+    UnderReview,
+    Revoked,
+    Expired,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RevocationReason {
+    Rug = 1,
+    Honeypot = 2,
+    AuthorityChange = 3,
+    OracleError = 4,
+    Other = 5,
+}
+
+#[account]
+pub struct AttestationCommitment {
+    pub oracle: Pubkey,
+    pub hash: [u8; 32],       // see commitment_hash
+    pub committed_slot: u64,  // reveals must land in a later slot
+    pub bump: u8,
+}
+
+/// A downgrade waiting for `cosign_downgrade`, one per attestation.
+#[account]
+pub struct PendingDowngrade {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub item: BatchAttestation,
+    pub proposer: Pubkey,
+    pub payer: Pubkey,         // rent payer, refunded when the downgrade is applied
+    pub base_attested_at: i64, // attestation write the downgrade replaces
+    pub proposed_at: i64,
+    pub bump: u8,
+}
+
+/// Payload an offline oracle key signs for `attest_with_signature`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignedAttestation {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8,
+    pub proofs_hash: [u8; 32],
+    pub nonce: u64, // also stops relayers from replaying old signatures
+    pub report: ReportPointer,
+    pub subscores: [u16; SUBSCORE_COUNT],
+    pub flags: u64, // FLAG_*
+}
+
+/// Where the full off-chain report behind `proofs_hash` can be fetched.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReportPointer {
+    pub scheme: u8, // REPORT_SCHEME_*
+    pub content_id: [u8; 32],
+}
+
+impl ReportPointer {
+    pub(crate) fn validate(&self) -> Result<()> {
+        require!(self.scheme <= REPORT_SCHEME_ARWEAVE, ErrorCode::InvalidReportPointer);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct HistoryEntry {
+    pub score_bps: u16,
+    pub grade: u8, // extended scale
+    pub oracle: Pubkey,
+    pub slot: u64,
+}
+
+/// Ring buffer of the last `HISTORY_LEN` attestations written for a mint.
+#[account]
+#[derive(Default)]
+pub struct AttestationHistory {
+    pub mint: Pubkey,
+    pub head: u8, // next slot to write
+    pub len: u8,
+    pub entries: [HistoryEntry; HISTORY_LEN],
+    pub bump: u8,
+}
+
+impl AttestationHistory {
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = ((self.head as usize + 1) % HISTORY_LEN) as u8;
+        if (self.len as usize) < HISTORY_LEN {
+            self.len += 1;
+        }
+    }
+}
+
+/// Points at a mint's newest attestation so clients need not guess which
+/// ruleset version to derive. Written by every attestation write path.
+#[account]
+#[derive(Default)]
+pub struct LatestAttestation {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub attestation: Pubkey,
+    pub attested_at: i64,
+    pub bump: u8,
+}
+
+impl LatestAttestation {
+    /// Never moves back to an older ruleset version, e.g. while the previous
+    /// version is still active and being re-attested.
+    pub fn record(&mut self, mint: Pubkey, ruleset_version: u16, attestation: Pubkey, attested_at: i64) {
+        if self.attestation != Pubkey::default() && ruleset_version < self.ruleset_version {
+            return;
+        }
+        self.mint = mint;
+        self.ruleset_version = ruleset_version;
+        self.attestation = attestation;
+        self.attested_at = attested_at;
+    }
+    
+    /// Forgets the attestation pointed at, e.g. because it is being closed; the
+    /// next write records whatever version it carries.
+    pub fn clear(&mut self) {
+        self.ruleset_version = 0;
+        self.attestation = Pubkey::default();
+        self.attested_at = 0;
+    }
+}
+
+/// Admin waiver of the score/grade band check for the next oracle write of
+/// `mint` under `ruleset_version` that passes it; that write closes it. Batch
+/// and materialized writes cannot carry one.
+#[account]
+pub struct GradeWaiver {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub admin: Pubkey,  // who granted it
+    pub payer: Pubkey,  // funded the PDA, refunded when the waiver is consumed
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+/// First mint attested with `proofs_hash` under `ruleset_version`. Any other mint
+/// submitting the same hash under that version is rejected as recycled evidence.
+#[account]
+#[derive(Default)]
+pub struct ProofMarker {
+    pub proofs_hash: [u8; 32],
+    pub ruleset_version: u16,
+    pub mint: Pubkey,
+    pub oracle: Pubkey,       // oracle that first submitted the hash
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Submission {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,
+    pub score_bps: u16,
+    pub grade: u8,
+    pub proofs_hash: [u8; 32],
+    pub report: ReportPointer,
+    pub subscores: [u16; SUBSCORE_COUNT],
+    pub flags: u64,            // FLAG_*; finalize_consensus keeps the union across agreeing oracles
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_attestation_record_first_write() {
+        let (mint, attestation) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut latest = LatestAttestation::default();
+        latest.record(mint, 3, attestation, 100);
+        assert_eq!(latest.mint, mint);
+        assert_eq!(latest.ruleset_version, 3);
+        assert_eq!(latest.attestation, attestation);
+        assert_eq!(latest.attested_at, 100);
+    }
+
+    #[test]
+    fn latest_attestation_record_same_or_newer_version() {
+        let mint = Pubkey::new_unique();
+        let mut latest = LatestAttestation::default();
+        latest.record(mint, 3, Pubkey::new_unique(), 100);
+        
+        let same = Pubkey::new_unique();
+        latest.record(mint, 3, same, 200);
+        assert_eq!((latest.attestation, latest.attested_at), (same, 200));
+        
+        let newer = Pubkey::new_unique();
+        latest.record(mint, 4, newer, 300);
+        assert_eq!((latest.ruleset_version, latest.attestation), (4, newer));
+    }
+
+    #[test]
+    fn latest_attestation_record_ignores_older_version() {
+        let (mint, current) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut latest = LatestAttestation::default();
+        latest.record(mint, 4, current, 100);
+        latest.record(mint, 3, Pubkey::new_unique(), 200);
+        assert_eq!(latest.ruleset_version, 4);
+        assert_eq!(latest.attestation, current);
+        assert_eq!(latest.attested_at, 100);
+    }
+
+    #[test]
+    fn latest_attestation_record_after_clear() {
+        let mint = Pubkey::new_unique();
+        let mut latest = LatestAttestation::default();
+        latest.record(mint, 4, Pubkey::new_unique(), 100);
+        latest.clear();
+        
+        let older = Pubkey::new_unique();
+        latest.record(mint, 3, older, 200);
+        assert_eq!((latest.ruleset_version, latest.attestation), (3, older));
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+
+#[account]
+pub struct ForeignEmitter {
+    pub chain: u16,         // Wormhole chain id
+    pub address: [u8; 32],  // partner registry's emitter address
+    pub bump: u8,
+}
+
+/// Marks a VAA as applied; its existence is the replay guard.
+#[account]
+pub struct ReceivedVaa {
+    pub bump: u8,
+}
+
+/// Attestation message as published by `publish_attestation` (here or on a partner
+/// chain). Big-endian, so it gets a hand-written codec rather than borsh.
+#[derive(Clone)]
+pub struct ForeignAttestationMessage {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score: u16,
+    pub grade: u8, // extended scale
+    pub proofs_hash: [u8; 32],
+    pub attested_at: i64,
+}
+
+impl AnchorDeserialize for ForeignAttestationMessage {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut buf = [0u8; WORMHOLE_PAYLOAD_LEN];
+        reader.read_exact(&mut buf)?;
+        if buf[0] != WORMHOLE_PAYLOAD_ATTESTATION {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "unknown payload id"));
+        }
+        let mut mint = [0u8; 32];
+        mint.copy_from_slice(&buf[1..33]);
+        let mut proofs_hash = [0u8; 32];
+        proofs_hash.copy_from_slice(&buf[38..70]);
+        let mut attested_at = [0u8; 8];
+        attested_at.copy_from_slice(&buf[70..78]);
+        Ok(Self {
+            mint: Pubkey::new_from_array(mint),
+            ruleset_version: u16::from_be_bytes([buf[33], buf[34]]),
+            score: u16::from_be_bytes([buf[35], buf[36]]),
+            grade: buf[37],
+            proofs_hash,
+            attested_at: i64::from_be_bytes(attested_at),
+        })
+    }
+}
+
+impl AnchorSerialize for ForeignAttestationMessage {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&[WORMHOLE_PAYLOAD_ATTESTATION])?;
+        writer.write_all(self.mint.as_ref())?;
+        writer.write_all(&self.ruleset_version.to_be_bytes())?;
+        writer.write_all(&self.score.to_be_bytes())?;
+        writer.write_all(&[self.grade])?;
+        writer.write_all(&self.proofs_hash)?;
+        writer.write_all(&self.attested_at.to_be_bytes())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::constants::*;
+use crate::state::*;
+
+#[account]
+pub struct TreeConfig {
+    pub merkle_tree: Pubkey,
+    pub leaf_count: u64, // appended leaves, i.e. the next leaf index
+    pub bump: u8,
+}
+
+#[account]
+pub struct AttestationRoot {
+    pub oracle: Pubkey,
+    pub ruleset_version: u16,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub posted_at: i64,
+    pub bump: u8,
+}
+
+/// Leaf of a compressed attestation tree; the tree stores keccak(borsh(leaf)).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedAttestation {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub grade: u8,
+    pub grade_levels: u8,
+    pub proofs_hash: [u8; 32],
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub revoked: bool,
+}
+
+impl CompressedAttestation {
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(keccak::hash(&self.try_to_vec()?).to_bytes())
+    }
+}
+
+/// One entry of a posted attestation root.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RootLeaf {
+    pub mint: Pubkey,
+    pub score: u16,
+    pub grade: u8,
+    pub proofs_hash: [u8; 32],
+    pub subscores: [u16; SUBSCORE_COUNT],
+}
+
+impl RootLeaf {
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(keccak::hashv(&[&[0u8], &self.try_to_vec()?]).to_bytes())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchAttestation {
+    pub score: u16,
+    pub grade: u8,
+    pub proofs_hash: [u8; 32],
+    pub nonce: u64,
+    pub report: ReportPointer,
+    pub subscores: [u16; SUBSCORE_COUNT],
+    pub flags: u64, // FLAG_*
+}
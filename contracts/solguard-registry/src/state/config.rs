@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::instructions::*;
+use crate::utils::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAction {
+    SetMinGrade { min_grade: u8 },
+    BumpRulesetVersion { version: u16 },
+    SetTimelockDelay { delay: i64 },
+    SetGradeThresholds { verified_bps: u16, green_bps: u16, yellow_bps: u16, red_bps: u16 },
+    SetGradeLevels { levels: u8 },
+}
+
+impl AdminAction {
+    /// One pending slot per action kind.
+    pub fn kind(&self) -> u8 {
+        match self {
+            AdminAction::SetMinGrade { .. } => 0,
+            AdminAction::BumpRulesetVersion { .. } => 1,
+            AdminAction::SetTimelockDelay { .. } => 2,
+            AdminAction::SetGradeThresholds { .. } => 3,
+            AdminAction::SetGradeLevels { .. } => 4,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        match *self {
+            // Checked against the scale in force when the action is applied
+            AdminAction::SetMinGrade { min_grade } => require!(min_grade < GRADE_LEVELS_EXTENDED, ErrorCode::InvalidGrade),
+            AdminAction::BumpRulesetVersion { .. } => {}
+            AdminAction::SetTimelockDelay { delay } => require!(delay >= 0, ErrorCode::InvalidTimelockDelay),
+            AdminAction::SetGradeThresholds { verified_bps, green_bps, yellow_bps, red_bps } => require!(
+                verified_bps <= 10000 && green_bps <= verified_bps && yellow_bps <= green_bps && red_bps <= yellow_bps, 
+                ErrorCode::InvalidGradeThresholds
+            ),
+            AdminAction::SetGradeLevels { levels } => require!(
+                levels == GRADE_LEVELS_LEGACY || levels == GRADE_LEVELS_EXTENDED, 
+                ErrorCode::InvalidGradeLevels
+            ),
+        }
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    OracleManager, // add/remove oracles
+    Revoker,       // revoke attestations
+    ConfigAdmin,   // grading rules, timelock, pause
+    Guardian,      // freeze attestations under investigation
+}
+
+#[account]
+pub struct Config {
+    pub admin: Pubkey,
+    pub ruleset_version: u16,
+    pub min_grade: u8,
+    pub bump: u8,
+    pub pending_admin: Pubkey, // Pubkey::default() when no transfer is in flight
+    pub multisig: bool,        // admin-gated instructions require AdminSet quorum
+    pub paused: bool,          // blocks attestation and revocation
+    pub timelock_delay: i64,   // seconds; 0 lets rule changes apply immediately
+    pub unbonding_period: i64, // seconds before unstaked oracle lamports can be withdrawn
+    pub consensus_threshold: u8,  // >1 requires that many matching oracle submissions
+    pub score_tolerance_bps: u16, // max score spread between agreeing submissions
+    pub max_heartbeat_age: u64,   // slots; verifiers treat quieter oracles as dead (0 = disabled)
+    pub consensus_min_weight: u32, // summed oracle weight needed to finalize consensus
+    pub oracle_deposit: u64,       // lamports locked when an Oracle PDA is created
+    pub attestation_ttl: i64,      // seconds an attestation stays valid (0 = no expiry)
+    pub dispute_bond: u64,         // lamports a challenger locks when filing a dispute
+    pub green_threshold_bps: u16,  // scores at or above are green (all thresholds 0 = unchecked)
+    pub yellow_threshold_bps: u16, // scores at or above are yellow
+    pub grade_levels: u8,          // GRADE_LEVELS_LEGACY or GRADE_LEVELS_EXTENDED
+    pub red_threshold_bps: u16,    // extended scale: scores below are critical
+    pub verified_threshold_bps: u16, // extended scale: scores at or above are verified
+    pub stale_after: i64,          // seconds; older attestations read at most yellow (0 = never)
+    pub default_max_age: i64,      // seconds; verification age limit when the caller passes none (0 = any age)
+    pub active_rulesets: [ActiveRuleset; MAX_ACTIVE_RULESETS], // still-verifiable versions, see set_active_rulesets
+    pub version: u8,               // CONFIG_LAYOUT_VERSION
+    pub attestation_fee: u64,      // per attestation, in lamports or fee_mint base units
+    pub fee_mint: Pubkey,          // Pubkey::default() charges the fee in lamports
+    pub subscription_price: u64,   // lamports per epoch; 0 lets any program verify
+    pub report_bond: u64,          // lamports posted with a rug report
+    pub revocation_quorum: u8,     // distinct oracle votes that revoke without an admin; 0 disables
+    pub downgrade_cosign_delta_bps: u16, // larger score drops, and green-to-red flips, need a co-signer; 0 disables
+    pub attestation_cooldown: i64, // seconds between writes to the same attestation; 0 disables
+    pub challenge_bond: u64,       // lamports posted by challenge_attestation; 0 disables challenges
+    pub challenge_window: i64,     // seconds the oracle has to answer a challenge before it settles against them
+    pub guardian: Pubkey,          // incident-response key; Pubkey::default() when unset
+    pub crank_reward: u64,         // lamports from the fee vault per attestation crank_expire_attestations marks; 0 = unpaid
+    pub governance: Pubkey,        // SPL Governance PDA that may override grades; Pubkey::default() when unset
+}
+
+/// A ruleset version consumers may keep verifying against, with its own grade floor.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ActiveRuleset {
+    pub version: u16,  // 0 = empty slot
+    pub min_grade: u8, // extended scale, raises the caller's minimum on verification
+}
+
+impl Config {
+    pub(crate) fn active_ruleset(&self, version: u16) -> Option<&ActiveRuleset> {
+        self.active_rulesets.iter().find(|r| r.version != 0 && r.version == version)
+    }
+
+    /// Whether replacing a live attestation's `previous` (score, normalized grade)
+    /// with `score` and normalized `grade` must go through `propose_downgrade`.
+    pub fn requires_downgrade_cosign(&self, previous: Option<(u16, u8)>, score: u16, grade: u8) -> bool {
+        match previous {
+            Some((old_score, old_grade)) if self.downgrade_cosign_delta_bps > 0 => {
+                old_score.saturating_sub(score) > self.downgrade_cosign_delta_bps
+                    || (old_grade >= GRADE_GREEN && grade <= GRADE_RED)
+            }
+            _ => false,
+        }
+    }
+
+    /// The current version, or one listed in `active_rulesets`.
+    pub fn is_active_version(&self, version: u16) -> bool {
+        version == self.ruleset_version || self.active_ruleset(version).is_some()
+    }
+
+    /// Grade floor (extended scale) configured for `version`, 0 if none.
+    pub fn ruleset_min_grade(&self, version: u16) -> u8 {
+        self.active_ruleset(version).map_or(0, |r| r.min_grade)
+    }
+
+    /// Number of grades oracles submit on. Configs written before the field
+    /// existed read 0 and stay on the legacy scale.
+    pub fn grade_scale(&self) -> u8 {
+        if self.grade_levels == 0 {
+            GRADE_LEVELS_LEGACY
+        } else {
+            self.grade_levels
+        }
+    }
+
+    /// Grade implied by `score` under the configured thresholds, if any are set.
+    pub fn grade_for_score(&self, score: u16) -> Option<u8> {
+        if self.verified_threshold_bps == 0
+            && self.green_threshold_bps == 0
+            && self.yellow_threshold_bps == 0
+            && self.red_threshold_bps == 0
+        {
+            return None;
+        }
+        let grade = if score >= self.verified_threshold_bps {
+            GRADE_VERIFIED
+        } else if score >= self.green_threshold_bps {
+            GRADE_GREEN
+        } else if score >= self.yellow_threshold_bps {
+            GRADE_YELLOW
+        } else if score >= self.red_threshold_bps {
+            GRADE_RED
+        } else {
+            GRADE_CRITICAL
+        };
+        Some(denormalize_grade(grade, self.grade_scale()))
+    }
+
+    /// Whether an attestation written at `attested_at` has aged past the staleness window.
+    pub fn is_stale(&self, attested_at: i64, now: i64) -> bool {
+        self.stale_after > 0 && now.saturating_sub(attested_at) > self.stale_after
+    }
+
+    /// Age limit for a verification call: the caller's own, else the registry default.
+    pub fn max_age_or_default(&self, max_age_secs: Option<i64>) -> Result<i64> {
+        let max_age = max_age_secs.unwrap_or(self.default_max_age);
+        require!(max_age >= 0, ErrorCode::InvalidMaxAge);
+        Ok(max_age)
+    }
+
+    /// `valid_until` for an attestation written at `attested_at`.
+    pub fn expiry_from(&self, attested_at: i64) -> i64 {
+        if self.attestation_ttl > 0 {
+            attested_at + self.attestation_ttl
+        } else {
+            0
+        }
+    }
+}
+
+#[account]
+pub struct PendingAction {
+    pub action: AdminAction,
+    pub eta: i64,
+    pub queued_by: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+pub struct AdminSet {
+    pub threshold: u8,
+    pub members: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+#[account]
+pub struct RoleGrant {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // All-zero Config: no thresholds set, legacy grade scale
+    fn config() -> Config {
+        Config::deserialize(&mut &[0u8; CONFIG_SPACE][8..]).unwrap()
+    }
+
+    fn with_thresholds(levels: u8) -> Config {
+        let mut config = config();
+        config.grade_levels = levels;
+        config.verified_threshold_bps = 9000;
+        config.green_threshold_bps = 7000;
+        config.yellow_threshold_bps = 4000;
+        config.red_threshold_bps = 2000;
+        config
+    }
+
+    #[test]
+    fn grade_for_score_unset_thresholds() {
+        assert_eq!(config().grade_for_score(0), None);
+        assert_eq!(config().grade_for_score(10000), None);
+    }
+
+    #[test]
+    fn grade_for_score_extended_scale() {
+        let config = with_thresholds(GRADE_LEVELS_EXTENDED);
+        assert_eq!(config.grade_for_score(10000), Some(GRADE_VERIFIED));
+        assert_eq!(config.grade_for_score(9000), Some(GRADE_VERIFIED));
+        assert_eq!(config.grade_for_score(8999), Some(GRADE_GREEN));
+        assert_eq!(config.grade_for_score(7000), Some(GRADE_GREEN));
+        assert_eq!(config.grade_for_score(4000), Some(GRADE_YELLOW));
+        assert_eq!(config.grade_for_score(2000), Some(GRADE_RED));
+        assert_eq!(config.grade_for_score(1999), Some(GRADE_CRITICAL));
+        assert_eq!(config.grade_for_score(0), Some(GRADE_CRITICAL));
+    }
+
+    #[test]
+    fn grade_for_score_legacy_scale() {
+        // grade_levels 0 is a pre-field Config and reads as legacy
+        for levels in [0, GRADE_LEVELS_LEGACY] {
+            let config = with_thresholds(levels);
+            assert_eq!(config.grade_for_score(9500), Some(GRADE_GREEN - GRADE_RED));
+            assert_eq!(config.grade_for_score(7000), Some(GRADE_GREEN - GRADE_RED));
+            assert_eq!(config.grade_for_score(4000), Some(GRADE_YELLOW - GRADE_RED));
+            assert_eq!(config.grade_for_score(2000), Some(0));
+            assert_eq!(config.grade_for_score(0), Some(0));
+        }
+    }
+}
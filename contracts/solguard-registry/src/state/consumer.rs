@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[account]
+pub struct ConsumerConfig {
+    pub consumer_program: Pubkey,
+    pub authority: Pubkey,            // upgrade authority that last updated the policy
+    pub min_grade: u8,                // extended scale
+    pub max_age: Option<i64>,         // seconds; None defers to the registry default
+    pub accepted_versions: Vec<u16>,  // empty = current ruleset only
+    pub updated_at: i64,
+    pub bump: u8,
+    pub min_version: u16,             // inclusive range pinned alongside accepted_versions
+    pub max_version: u16,             // 0 = no range
+}
+
+impl ConsumerConfig {
+    /// Without an explicit list or range the registry's active versions apply, so
+    /// a consumer that pins versions never trusts a new ruleset until it opts in.
+    pub fn accepts_version(&self, version: u16, config: &Config) -> bool {
+        if self.accepted_versions.is_empty() && self.max_version == 0 {
+            config.is_active_version(version)
+        } else {
+            self.pins_version(version)
+        }
+    }
+
+    /// Whether the consumer listed `version` itself rather than deferring to the registry.
+    pub fn pins_version(&self, version: u16) -> bool {
+        self.accepted_versions.contains(&version) || 
+            (self.max_version != 0 && (self.min_version..=self.max_version).contains(&version))
+    }
+}
+
+#[account]
+pub struct Subscription {
+    pub consumer_program: Pubkey,
+    pub paid_through_epoch: u64, // last covered epoch, inclusive
+    pub total_paid: u64,         // lamports
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub fn is_active(&self, epoch: u64) -> bool {
+        self.total_paid > 0 && epoch <= self.paid_through_epoch
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Council {
+    pub members: Vec<Pubkey>,
+    pub quorum: u8,          // matching votes that decide a case
+    pub epoch: u32,          // bumped by set_council; older votes no longer count
+    pub bump: u8,
+}
+
+#[account]
+pub struct CouncilVote {
+    pub case: Pubkey,        // Dispute or Challenge
+    pub member: Pubkey,
+    pub uphold: bool,
+    pub epoch: u32,          // council epoch the vote was cast in
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct CouncilTally {
+    pub case: Pubkey,
+    pub epoch: u32,          // counts reset when the council changes
+    pub uphold_votes: u8,
+    pub reject_votes: u8,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeStatus {
+    Open,
+    Sustained, // attestation revoked, challenger rewarded
+    Dismissed, // attestation upheld, bond forfeited
+}
+
+#[account]
+pub struct Dispute {
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,      // attester at filing time
+    pub challenger: Pubkey,
+    pub bond: u64,           // lamports held by this account on top of rent
+    pub evidence_hash: [u8; 32],
+    pub filed_at: i64,
+    pub status: DisputeStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeStatus {
+    Open,     // waiting for the oracle's answer
+    Answered, // oracle stands by the attestation, awaiting a Revoker
+}
+
+/// Open challenge against an attestation, closed by `resolve_challenge`.
+#[account]
+pub struct Challenge {
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub oracle: Pubkey,      // attester at challenge time
+    pub challenger: Pubkey,
+    pub bond: u64,           // lamports held by this account on top of rent
+    pub evidence_hash: [u8; 32],
+    pub response_hash: [u8; 32], // oracle's counter-evidence, zero until answered
+    pub opened_at: i64,
+    pub answer_deadline: i64,
+    pub status: ChallengeStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ReportStatus {
+    Open,
+    Awarded,  // attestation revoked, bounty paid
+    Rejected, // bond forfeited
+}
+
+#[account]
+pub struct RugReport {
+    pub attestation: Pubkey,
+    pub mint: Pubkey,
+    pub reporter: Pubkey,
+    pub bond: u64,           // lamports held by this account on top of rent
+    pub evidence_hash: [u8; 32],
+    pub reported_at: i64,
+    pub status: ReportStatus,
+    pub bump: u8,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// One page of the mints whose latest attestation currently holds `grade`
+/// (extended scale), so clients can enumerate a grade without scanning every
+/// attestation. Order within a page is not stable.
+#[account]
+pub struct GradeIndexPage {
+    pub grade: u8,
+    pub page: u32,
+    pub mints: Vec<Pubkey>,   // at most GRADE_INDEX_PAGE_LEN
+    pub bump: u8,
+}
+
+impl GradeIndexPage {
+    pub fn remove(&mut self, mint: &Pubkey) -> bool {
+        match self.mints.iter().position(|m| m == mint) {
+            Some(i) => {
+                self.mints.swap_remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Where a mint is listed in the grade index.
+#[account]
+#[derive(Default)]
+pub struct GradeIndexEntry {
+    pub mint: Pubkey,
+    pub attestation: Pubkey,  // attestation the listing follows
+    pub grade: u8,
+    pub page: u32,
+    pub listed: bool,         // false while the attestation is revoked
+    pub bump: u8,
+}
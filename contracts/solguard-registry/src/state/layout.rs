@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+
+/// Borsh layout of Attestation before it became zero-copy; only read by migrate_account.
+#[derive(AnchorDeserialize)]
+pub(crate) struct LegacyAttestation {
+    mint: Pubkey,
+    ruleset_version: u16,
+    score_bps: u16,
+    grade: u8,
+    proofs_hash: [u8; 32],
+    attested_by: Pubkey,
+    attested_at: i64,
+    revoked: bool,
+    oracle_count: u8,
+    valid_until: i64,
+    payer: Pubkey,
+    nonce: u64,
+    revocation_reason: u8,
+    revocation_evidence: [u8; 32],
+    report: ReportPointer,
+    subscores: [u16; SUBSCORE_COUNT],
+    grade_levels: u8,
+    origin_chain: u16,
+    zk_verified: bool,
+}
+
+impl From<LegacyAttestation> for Attestation {
+    fn from(l: LegacyAttestation) -> Self {
+        Attestation {
+            mint: l.mint,
+            attested_by: l.attested_by,
+            payer: l.payer,
+            proofs_hash: l.proofs_hash,
+            revocation_evidence: l.revocation_evidence,
+            report_content_id: l.report.content_id,
+            attested_at: l.attested_at,
+            valid_until: l.valid_until,
+            nonce: l.nonce,
+            ruleset_version: l.ruleset_version,
+            score_bps: l.score_bps,
+            origin_chain: l.origin_chain,
+            subscores: l.subscores,
+            grade: l.grade,
+            grade_levels: l.grade_levels,
+            revoked: l.revoked as u8,
+            oracle_count: l.oracle_count,
+            revocation_reason: l.revocation_reason,
+            report_scheme: l.report.scheme,
+            zk_verified: l.zk_verified as u8,
+            version: ATTESTATION_LAYOUT_VERSION,
+            frozen: 0,
+            decimals: 0,
+            risk_flags: 0,
+            cooldown_waived: 0,
+            challenged: 0,
+            expired: 0,
+            superseded: 0,
+            metadata_hash: [0; 32],
+            supply: 0,
+            snapshot_slot: 0,
+            mint_authority: Pubkey::default(),
+            freeze_authority: Pubkey::default(),
+            category_proofs: [[0; 32]; SUBSCORE_COUNT],
+            flags: 0,
+            supersedes: Pubkey::default(),
+        }
+    }
+}
+
+/// Borsh accounts carrying a layout version, upgradable by migrate_account.
+pub(crate) trait VersionedLayout {
+    const LAYOUT_VERSION: u8;
+    fn layout_version(&mut self) -> &mut u8;
+}
+
+impl VersionedLayout for Config {
+    const LAYOUT_VERSION: u8 = CONFIG_LAYOUT_VERSION;
+    fn layout_version(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl VersionedLayout for RegistryStats {
+    const LAYOUT_VERSION: u8 = REGISTRY_STATS_LAYOUT_VERSION;
+    fn layout_version(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl VersionedLayout for Oracle {
+    const LAYOUT_VERSION: u8 = ORACLE_LAYOUT_VERSION;
+    fn layout_version(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
@@ -0,0 +1,29 @@
+mod attestation;
+mod bridge;
+mod compressed;
+mod config;
+mod consumer;
+mod dispute;
+mod grade_index;
+mod layout;
+mod oracle;
+mod registry;
+mod risk;
+mod ruleset;
+mod subject;
+mod watch;
+
+pub use attestation::*;
+pub use bridge::*;
+pub use compressed::*;
+pub use config::*;
+pub use consumer::*;
+pub use dispute::*;
+pub use grade_index::*;
+pub use layout::*;
+pub use oracle::*;
+pub use registry::*;
+pub use risk::*;
+pub use ruleset::*;
+pub use subject::*;
+pub use watch::*;
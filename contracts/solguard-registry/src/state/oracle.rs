@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::*;
+
+#[account]
+pub struct Oracle {
+    pub bump: u8,
+    pub active: bool,
+    pub stake: u64,             // bonded lamports, slashable
+    pub unbonding: u64,         // lamports waiting out the unbonding period
+    pub unbonding_ends_at: i64,
+    pub last_seen_slot: u64,    // updated by oracle_heartbeat
+    pub weight: u16,            // consensus voting weight, defaults to 1
+    pub deposit: u64,           // registration deposit, refunded by close_oracle
+    pub depositor: Pubkey,
+    pub deactivated_at: i64,
+    pub scoped: bool,           // may only attest mints with a ScopeEntry
+    pub evm_address: [u8; 20],  // secp256k1 signing address, zero if none
+    pub version: u8,            // ORACLE_LAYOUT_VERSION
+}
+
+impl Oracle {
+    /// Whether the oracle has sent a heartbeat within `config.max_heartbeat_age` slots.
+    /// A zero max age disables liveness tracking.
+    pub fn is_live(&self, config: &Config, current_slot: u64) -> bool {
+        config.max_heartbeat_age == 0
+            || current_slot.saturating_sub(self.last_seen_slot) <= config.max_heartbeat_age
+    }
+}
+
+#[account]
+pub struct ScopeEntry {
+    pub oracle: Pubkey,
+    pub mint: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+pub struct OracleProfile {
+    pub oracle: Pubkey,
+    pub name: String,
+    pub operator_uri: String,
+    pub contact_hash: [u8; 32], // hash of off-chain contact details
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct OracleStats {
+    pub oracle: Pubkey,
+    pub attestation_count: u64,
+    pub revocation_count: u64,
+    pub last_attested_at: i64,
+    pub grade_histogram: [u64; GRADE_LEVELS_EXTENDED as usize], // attestations submitted per grade, on the extended scale
+    pub bump: u8,
+}
+
+impl OracleStats {
+    /// `grade` is on the extended scale so buckets stay comparable across scale changes.
+    pub fn record_attestation(&mut self, grade: u8, attested_at: i64) {
+        self.attestation_count += 1;
+        self.last_attested_at = attested_at;
+        self.grade_histogram[grade as usize] += 1;
+    }
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::utils::*;
+
+/// Holds collected protocol fees as lamports above its rent-exempt minimum.
+#[account]
+pub struct FeeVault {
+    pub total_collected: u64,       // lamports
+    pub total_withdrawn: u64,
+    pub bump: u8,
+    pub total_token_collected: u64, // fee_mint base units, summed across mint changes
+    pub total_token_withdrawn: u64,
+}
+
+/// Registry-wide counters for dashboards. `live_by_grade` counts unrevoked
+/// attestations by normalized grade; expiry and staleness are not tracked.
+#[account]
+pub struct RegistryStats {
+    pub attestation_count: u64,  // attestation writes, overwrites included
+    pub revocation_count: u64,
+    pub live_by_grade: [u64; GRADE_LEVELS_EXTENDED as usize],
+    pub active_oracles: u32,
+    pub bump: u8,
+    pub live_score_sum: u64,     // score_bps summed over the attestations in live_by_grade
+    pub version: u8,             // REGISTRY_STATS_LAYOUT_VERSION
+}
+
+impl RegistryStats {
+    /// `previous` is the attestation's live score and grade before it was overwritten.
+    pub fn record_attestation(&mut self, previous: Option<(u16, u8)>, score: u16, grade: u8) {
+        self.attestation_count += 1;
+        if let Some((previous_score, previous_grade)) = previous {
+            self.remove_live(previous_score, previous_grade);
+        }
+        self.add_live(score, grade);
+    }
+
+    pub fn record_revocation(&mut self, score: u16, grade: u8) {
+        self.revocation_count += 1;
+        self.remove_live(score, grade);
+    }
+
+    pub fn record_reinstatement(&mut self, score: u16, grade: u8) {
+        self.revocation_count = self.revocation_count.saturating_sub(1);
+        self.add_live(score, grade);
+    }
+
+    pub fn record_close(&mut self, score: u16, grade: u8) {
+        self.remove_live(score, grade);
+    }
+
+    pub fn live_total(&self) -> u64 {
+        self.live_by_grade.iter().sum()
+    }
+
+    /// Mean score of live attestations, 0 when there are none. Understated for
+    /// attestations written before live_score_sum existed, see remove_live.
+    pub fn average_score_bps(&self) -> u16 {
+        match self.live_total() {
+            0 => 0,
+            total => (self.live_score_sum / total).min(10000) as u16,
+        }
+    }
+
+    pub(crate) fn add_live(&mut self, score: u16, grade: u8) {
+        self.live_by_grade[grade as usize] += 1;
+        self.live_score_sum += score as u64;
+    }
+
+    // Saturating: attestations written before the stats (or the score sum) existed were never counted
+    pub(crate) fn remove_live(&mut self, score: u16, grade: u8) {
+        let count = &mut self.live_by_grade[grade as usize];
+        *count = count.saturating_sub(1);
+        self.live_score_sum = self.live_score_sum.saturating_sub(score as u64);
+    }
+}
+
+/// Registry-wide aggregates frozen once per Solana epoch by `snapshot_epoch`.
+#[account]
+pub struct EpochSnapshot {
+    pub epoch: u64,
+    pub live_by_grade: [u64; GRADE_LEVELS_EXTENDED as usize],
+    pub live_total: u64,
+    pub average_score_bps: u16,
+    pub attestation_count: u64,  // cumulative, as in RegistryStats
+    pub revocation_count: u64,
+    pub active_oracles: u32,
+    pub slot: u64,
+    pub taken_at: i64,
+    pub taken_by: Pubkey,
+    pub bump: u8,
+}
+
+#[zero_copy]
+pub struct AuditEntry {
+    pub action: [u8; 8],       // instruction discriminator
+    pub actor: Pubkey,         // signing admin or role holder
+    pub payload_hash: [u8; 32], // keccak of the instruction arguments, see audit_entry
+    pub slot: u64,
+}
+
+/// Ring buffer of the last `AUDIT_LOG_LEN` admin actions, written by every
+/// admin-gated instruction. Entry `count % AUDIT_LOG_LEN` is overwritten next.
+#[account(zero_copy)]
+pub struct AuditLog {
+    pub count: u64,            // actions recorded since creation
+    pub entries: [AuditEntry; AUDIT_LOG_LEN],
+    pub bump: u8,
+    pub reserved: [u8; 7],     // keeps the size a multiple of 8; zero
+}
+
+const _: () = assert!(std::mem::size_of::<AuditLog>() == 8 + 80 * AUDIT_LOG_LEN + 8);
+
+impl AuditLog {
+    pub fn push(&mut self, entry: AuditEntry) {
+        self.entries[(self.count % AUDIT_LOG_LEN as u64) as usize] = entry;
+        self.count += 1;
+    }
+}
+
+/// Bloom filter over mints that were graded red or below, revoked or blacklisted,
+/// for a single-read negative check: a miss means the mint was never flagged
+/// since the last reset, a hit only that it may have been. See risk_bloom_positions.
+#[account(zero_copy)]
+pub struct RiskBloom {
+    pub inserted: u64,         // insertions since reset_at, repeats included
+    pub reset_at: i64,
+    pub bits: [u8; RISK_BLOOM_BYTES],
+    pub bump: u8,
+    pub reserved: [u8; 7],     // keeps the size a multiple of 8; zero
+}
+
+const _: () = assert!(std::mem::size_of::<RiskBloom>() == 8 + 8 + RISK_BLOOM_BYTES + 8);
+
+impl RiskBloom {
+    pub fn insert(&mut self, mint: &Pubkey) {
+        for bit in risk_bloom_positions(mint) {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+        self.inserted += 1;
+    }
+
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        risk_bloom_positions(mint).iter().all(|&bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+}
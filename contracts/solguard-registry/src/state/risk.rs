@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[account]
+pub struct Blacklist {
+    pub mint: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub added_by: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct AllowlistEntry {
+    pub mint: Pubkey,
+    pub added_by: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+/// Grade pinned by a governance proposal, see set_grade_override.
+#[account]
+pub struct GradeOverride {
+    pub mint: Pubkey,
+    pub grade: u8,          // extended scale
+    pub governance: Pubkey, // Config.governance at the time it was set
+    pub set_at: i64,
+    pub payer: Pubkey,      // refunded on clear_grade_override
+    pub bump: u8,
+}
+
+#[account]
+pub struct RevocationVote {
+    pub attestation: Pubkey,
+    pub oracle: Pubkey,
+    pub attested_at: i64,    // attestation write the vote applies to
+    pub reason: RevocationReason,
+    pub evidence_hash: [u8; 32],
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct RevocationTally {
+    pub attestation: Pubkey,
+    pub attested_at: i64,    // votes are reset when the attestation is rewritten
+    pub votes: u8,
+    pub bump: u8,
+}
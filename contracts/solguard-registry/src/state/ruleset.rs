@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use groth16_solana::groth16::{Groth16Verifier, Groth16Verifyingkey};
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+
+#[account]
+pub struct RulesetCircuit {
+    pub ruleset_version: u16,
+    pub key: Groth16VerifyingKey,
+    pub bump: u8,
+}
+
+impl RulesetCircuit {
+    pub fn verify(&self, proof: &Groth16Proof, inputs: &[[u8; 32]; ZK_PUBLIC_INPUTS]) -> Result<()> {
+        let vk = Groth16Verifyingkey {
+            nr_pubinputs: ZK_PUBLIC_INPUTS,
+            vk_alpha_g1: self.key.vk_alpha_g1,
+            vk_beta_g2: self.key.vk_beta_g2,
+            vk_gamme_g2: self.key.vk_gamma_g2,
+            vk_delta_g2: self.key.vk_delta_g2,
+            vk_ic: &self.key.vk_ic,
+        };
+        let mut verifier = Groth16Verifier::new(&proof.a, &proof.b, &proof.c, inputs, &vk)
+            .map_err(|_| error!(ErrorCode::InvalidZkProof))?;
+        verifier.verify().map_err(|_| error!(ErrorCode::InvalidZkProof))?;
+        Ok(())
+    }
+}
+
+/// Groth16 verifying key over alt_bn128, points uncompressed big-endian.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct Groth16VerifyingKey {
+    pub vk_alpha_g1: [u8; 64],
+    pub vk_beta_g2: [u8; 128],
+    pub vk_gamma_g2: [u8; 128],
+    pub vk_delta_g2: [u8; 128],
+    pub vk_ic: Vec<[u8; 64]>, // ZK_PUBLIC_INPUTS + 1 points
+}
+
+/// Groth16 proof; `a` must already be negated by the prover.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Groth16Proof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+/// On-chain provenance for a ruleset version.
+#[account]
+pub struct Ruleset {
+    pub version: u16,
+    pub definition_hash: [u8; 32], // hash of the canonical ruleset definition
+    pub uri: String,               // where the definition can be fetched
+    pub activation_slot: u64,      // earliest slot it can become the current version
+    pub valid_from: i64,           // 0 = accepted only while current
+    pub valid_until: i64,          // 0 = no end
+    pub status: RulesetStatus,
+    pub published_by: Pubkey,
+    pub published_at: i64,
+    pub bump: u8,
+}
+
+impl Ruleset {
+    /// Whether oracles may attest under this version while it is not the current one.
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        self.valid_from != 0 && now >= self.valid_from && (self.valid_until == 0 || now <= self.valid_until)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RulesetStatus {
+    Published, // recorded, not yet current
+    Active,    // config.ruleset_version points here
+    Retired,   // superseded by a later bump
+}
+
+/// Admin declaration that attestations may move from one version to another.
+#[account]
+pub struct RulesetCompatibility {
+    pub from_version: u16,
+    pub to_version: u16,
+    pub declared_by: Pubkey,
+    pub declared_at: i64,
+    pub bump: u8,
+}
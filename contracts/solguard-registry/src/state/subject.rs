@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::state::*;
+use crate::utils::*;
+
+/// Verification rules shared by attestations of subjects other than mints.
+pub trait SubjectAttestation {
+    fn is_revoked(&self) -> bool;
+    fn attested_at(&self) -> i64;
+    fn valid_until(&self) -> i64; // 0 = never expires
+    fn normalized_grade(&self) -> u8;
+
+    fn require_valid(&self, now: i64) -> Result<()> {
+        require!(!self.is_revoked(), ErrorCode::AttestationRevoked);
+        require!(self.valid_until() == 0 || now <= self.valid_until(), ErrorCode::AttestationExpired);
+        Ok(())
+    }
+
+    fn require_fresh(&self, now: i64, max_age: i64) -> Result<()> {
+        require!(
+            max_age == 0 || now.saturating_sub(self.attested_at()) <= max_age, 
+            ErrorCode::AttestationStale
+        );
+        Ok(())
+    }
+
+    /// Same staleness rule as Attestation::effective_grade.
+    fn effective_grade(&self, config: &Config, now: i64) -> u8 {
+        let grade = self.normalized_grade();
+        if config.is_stale(self.attested_at(), now) {
+            grade.min(GRADE_YELLOW)
+        } else {
+            grade
+        }
+    }
+}
+
+/// Grade for a whole Metaplex collection, keyed by the collection mint.
+#[account]
+pub struct CollectionAttestation {
+    pub collection_mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub grade: u8,           // on the `grade_levels` scale
+    pub grade_levels: u8,
+    pub proofs_hash: [u8; 32],
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub valid_until: i64,    // 0 = never expires
+    pub inherit: bool,       // verified items take this grade
+    pub item_override: bool, // an item's own attestation wins over the inherited grade
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl SubjectAttestation for CollectionAttestation {
+    fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    fn attested_at(&self) -> i64 {
+        self.attested_at
+    }
+
+    fn valid_until(&self) -> i64 {
+        self.valid_until
+    }
+
+    fn normalized_grade(&self) -> u8 {
+        normalize_grade(self.grade, self.grade_levels)
+    }
+}
+
+/// Grade for a deployed program, in the `attest_program` seed namespace.
+#[account]
+pub struct ProgramAttestation {
+    pub program_id: Pubkey,
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub grade: u8,           // on the `grade_levels` scale
+    pub grade_levels: u8,
+    pub proofs_hash: [u8; 32],
+    pub upgrade_authority: Option<Pubkey>, // as observed when graded, None = immutable
+    pub deployed_slot: u64,  // ProgramData slot when graded, 0 for immutable programs
+    pub build_hash: [u8; 32], // verified build the deployed bytecode matched
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub valid_until: i64,    // 0 = never expires
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl SubjectAttestation for ProgramAttestation {
+    fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    fn attested_at(&self) -> i64 {
+        self.attested_at
+    }
+
+    fn valid_until(&self) -> i64 {
+        self.valid_until
+    }
+
+    fn normalized_grade(&self) -> u8 {
+        normalize_grade(self.grade, self.grade_levels)
+    }
+}
+
+/// Grade for a deployer or creator wallet, with the oracle's count of the
+/// tokens it launched and how many of those rugged.
+#[account]
+pub struct WalletAttestation {
+    pub wallet: Pubkey,
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub grade: u8,           // on the `grade_levels` scale
+    pub grade_levels: u8,
+    pub proofs_hash: [u8; 32],
+    pub tokens_created: u32,
+    pub tokens_rugged: u32,  // created tokens the oracle attributes a rug or honeypot to
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub valid_until: i64,    // 0 = never expires
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl SubjectAttestation for WalletAttestation {
+    fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    fn attested_at(&self) -> i64 {
+        self.attested_at
+    }
+
+    fn valid_until(&self) -> i64 {
+        self.valid_until
+    }
+
+    fn normalized_grade(&self) -> u8 {
+        normalize_grade(self.grade, self.grade_levels)
+    }
+}
+
+/// Grade and LP security of an AMM pool account owned by one of `POOL_PROGRAMS`.
+#[account]
+pub struct PoolAttestation {
+    pub pool: Pubkey,
+    pub dex_program: Pubkey, // owner of `pool`
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub grade: u8,           // on the `grade_levels` scale
+    pub grade_levels: u8,
+    pub proofs_hash: [u8; 32],
+    pub lp_burned_bps: u16,  // share of LP supply burned
+    pub lp_locked_bps: u16,  // share of LP supply locked until lp_unlock_at
+    pub lp_unlock_at: i64,
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub valid_until: i64,    // 0 = never expires
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl PoolAttestation {
+    /// LP share that cannot be pulled at `now`: burned, plus locked until unlock.
+    pub fn lp_secured_bps(&self, now: i64) -> u16 {
+        if now < self.lp_unlock_at {
+            self.lp_burned_bps + self.lp_locked_bps
+        } else {
+            self.lp_burned_bps
+        }
+    }
+}
+
+impl SubjectAttestation for PoolAttestation {
+    fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    fn attested_at(&self) -> i64 {
+        self.attested_at
+    }
+
+    fn valid_until(&self) -> i64 {
+        self.valid_until
+    }
+
+    fn normalized_grade(&self) -> u8 {
+        normalize_grade(self.grade, self.grade_levels)
+    }
+}
+
+/// Grade for a (mint A, mint B, venue) trading pair, with mint A sorting first.
+#[account]
+pub struct PairAttestation {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub venue: Pubkey,       // DEX or orderbook program the pair trades on
+    pub ruleset_version: u16,
+    pub score_bps: u16,
+    pub grade: u8,           // on the `grade_levels` scale
+    pub grade_levels: u8,
+    pub proofs_hash: [u8; 32],
+    pub risk_flags: u16,     // PAIR_RISK_* bits
+    pub attested_by: Pubkey,
+    pub attested_at: i64,
+    pub valid_until: i64,    // 0 = never expires
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+impl SubjectAttestation for PairAttestation {
+    fn is_revoked(&self) -> bool {
+        self.revoked
+    }
+
+    fn attested_at(&self) -> i64 {
+        self.attested_at
+    }
+
+    fn valid_until(&self) -> i64 {
+        self.valid_until
+    }
+
+    fn normalized_grade(&self) -> u8 {
+        normalize_grade(self.grade, self.grade_levels)
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Per-mint change counter shared by everyone watching the mint. Attestation
+/// writes and revocations bump it when the account is passed.
+#[account]
+pub struct MintWatch {
+    pub mint: Pubkey,
+    pub watchers: u32,
+    pub changes: u64,
+    pub last_changed_slot: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Watcher {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub seen_changes: u64,   // MintWatch.changes as of the last acknowledgement
+    pub registered_at: i64,
+    pub bump: u8,
+}
+
+impl Watcher {
+    pub fn is_dirty(&self, watch: &MintWatch) -> bool {
+        watch.changes != self.seen_changes
+    }
+}
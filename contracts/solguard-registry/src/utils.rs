@@ -0,0 +1,770 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::ZeroCopy;
+use anchor_lang::solana_program::instruction::{get_stack_height, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token_interface;
+use anchor_spl::token_2022::spl_token_2022::{
+    self, 
+    extension::{
+        default_account_state::DefaultAccountState, permanent_delegate::PermanentDelegate, transfer_hook::TransferHook, 
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::{AccountState, Mint as MintState},
+};
+use std::cell::RefMut;
+
+use crate::constants::*;
+use crate::errors::ErrorCode;
+use crate::events::*;
+use crate::instructions::*;
+use crate::state::*;
+
+/// Shared input checks for anything an oracle submits. Besides the active
+/// versions, `ruleset_version` may be any version whose Ruleset (passed as
+/// `ruleset`) is inside its validity window.
+pub(crate) fn validate_submission(
+    config: &Config, 
+    oracle: &Oracle, 
+    in_scope: bool, 
+    ruleset_version: u16, 
+    ruleset: Option<&Ruleset>, 
+    score: u16, 
+    grade: u8
+) -> Result<()> {
+    validate_oracle_submission(config, oracle, in_scope, ruleset_version, ruleset, score, grade)?;
+    require_grade_in_band(config, score, grade)
+}
+
+/// validate_submission without the grade band check, for write paths that
+/// honour an admin waiver (see GradeWaiver).
+pub(crate) fn validate_oracle_submission(
+    config: &Config, 
+    oracle: &Oracle, 
+    in_scope: bool, 
+    ruleset_version: u16, 
+    ruleset: Option<&Ruleset>, 
+    score: u16, 
+    grade: u8
+) -> Result<()> {
+    require!(!config.paused, ErrorCode::RegistryPaused);
+    require!(oracle.active, ErrorCode::OracleInactive);
+    require!(!oracle.scoped || in_scope, ErrorCode::MintOutOfScope);
+    require!(grade < config.grade_scale(), ErrorCode::InvalidGrade);
+    require!(score <= 10000, ErrorCode::InvalidScore);
+    if !config.is_active_version(ruleset_version) {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ruleset.is_some_and(|r| r.version == ruleset_version && r.is_valid_at(now)), 
+            ErrorCode::InvalidRulesetVersion
+        );
+    }
+    Ok(())
+}
+
+/// Once Config's grade thresholds are set, `grade` must be the band `score` falls in.
+pub(crate) fn require_grade_in_band(config: &Config, score: u16, grade: u8) -> Result<()> {
+    if let Some(expected) = config.grade_for_score(score) {
+        require!(grade == expected, ErrorCode::InconsistentGrade);
+    }
+    Ok(())
+}
+
+/// require_grade_in_band unless the write passes the mint's GradeWaiver. A passed
+/// waiver is consumed whether or not it was needed, refunding the payer that funded it.
+pub(crate) fn require_grade_in_band_or_waived<'info>(
+    config: &Config, 
+    score: u16, 
+    grade: u8, 
+    waiver: Option<&Account<'info, GradeWaiver>>, 
+    waiver_payer: Option<&UncheckedAccount<'info>>
+) -> Result<()> {
+    match waiver {
+        Some(waiver) => {
+            let refund = waiver_payer.filter(|payer| payer.key() == waiver.payer).ok_or(ErrorCode::GradeWaiverPayerMismatch)?;
+            waiver.close(refund.to_account_info())
+        }
+        None => require_grade_in_band(config, score, grade),
+    }
+}
+
+/// Maps a grade on a `levels`-point scale onto the extended five-level scale.
+pub fn normalize_grade(grade: u8, levels: u8) -> u8 {
+    if levels == GRADE_LEVELS_EXTENDED {
+        grade
+    } else {
+        grade + GRADE_RED
+    }
+}
+
+/// Maps an extended-scale grade onto a `levels`-point scale. The legacy scale
+/// has no critical or verified grade, so those collapse into red and green.
+pub fn denormalize_grade(grade: u8, levels: u8) -> u8 {
+    if levels == GRADE_LEVELS_EXTENDED {
+        grade
+    } else {
+        grade.clamp(GRADE_RED, GRADE_GREEN) - GRADE_RED
+    }
+}
+
+pub(crate) fn validate_subscores(subscores: &[u16; SUBSCORE_COUNT]) -> Result<()> {
+    require!(subscores.iter().all(|s| *s <= 10000), ErrorCode::InvalidScore);
+    Ok(())
+}
+
+/// Deserializes a registry-owned account passed via remaining accounts.
+pub(crate) fn load_program_account<T: AccountDeserialize>(info: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidRemainingAccount);
+    let data = info.try_borrow_data()?;
+    let account = T::try_deserialize(&mut &data[..])?;
+    Ok(account)
+}
+
+/// Loads a registry PDA passed via remaining accounts, creating it with
+/// `space` bytes (paid by `payer`) when it does not exist yet.
+pub(crate) fn load_or_init_pda<'info, T: AccountDeserialize + Default>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<(T, u8)> {
+    let (created, bump) = create_pda_if_needed(info, payer, system_program, seeds, space)?;
+    if created {
+        Ok((T::default(), bump))
+    } else {
+        Ok((load_program_account(info)?, bump))
+    }
+}
+
+/// Creates a registry PDA passed via remaining accounts unless it already
+/// exists. Returns whether it was created, and its bump.
+pub(crate) fn create_pda_if_needed<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<(bool, u8)> {
+    let (address, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require_keys_eq!(address, info.key(), ErrorCode::InvalidRemainingAccount);
+    if *info.owner == crate::ID && info.data_len() > 0 {
+        return Ok((false, bump));
+    }
+    
+    let bump_seed = [bump];
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&bump_seed);
+    let rent = Rent::get()?.minimum_balance(space);
+    let current = info.lamports();
+    if current == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+                &[&signer_seeds[..]],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+        return Ok((true, bump));
+    }
+    
+    // create_account rejects an address that already holds lamports, so anyone
+    // could block the PDA by pre-funding it; take it over like Anchor's init does
+    if current < rent {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            rent - current,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: info.clone(),
+            },
+            &[&signer_seeds[..]],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: info.clone(),
+            },
+            &[&signer_seeds[..]],
+        ),
+        &crate::ID,
+    )?;
+    
+    Ok((true, bump))
+}
+
+/// Moves `count` attestation fees from `payer` into the fee vault (or its token
+/// account when Config.fee_mint is set). The fee accounts may be omitted while
+/// the fee is zero.
+pub(crate) fn charge_attestation_fee<'info>(
+    config: &Config, 
+    fee: &mut FeePayment<'info>, 
+    payer: &AccountInfo<'info>, 
+    system_program: &AccountInfo<'info>, 
+    count: u64
+) -> Result<()> {
+    let amount = config.attestation_fee.saturating_mul(count);
+    if amount == 0 {
+        return Ok(());
+    }
+    let vault = fee.fee_vault.as_mut().ok_or(ErrorCode::FeeVaultRequired)?;
+    
+    if config.fee_mint != Pubkey::default() {
+        let (Some(mint), Some(from), Some(to), Some(token_program)) =
+            (&fee.fee_mint, &fee.payer_fee_token, &fee.fee_token_vault, &fee.token_program)
+        else {
+            return err!(ErrorCode::FeeTokenAccountsRequired);
+        };
+        require_keys_eq!(mint.key(), config.fee_mint, ErrorCode::WrongFeeMint);
+        require!(to.mint == config.fee_mint && to.owner == vault.key(), ErrorCode::WrongFeeMint);
+        
+        token_interface::transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(), 
+                token_interface::TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: payer.clone(),
+                }
+            ), 
+            amount, 
+            mint.decimals
+        )?;
+        vault.total_token_collected += amount;
+        return Ok(());
+    }
+    
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(), 
+            system_program::Transfer {
+                from: payer.clone(),
+                to: vault.to_account_info(),
+            }
+        ), 
+        amount
+    )?;
+    vault.total_collected += amount;
+    Ok(())
+}
+
+/// Mutable view of a zero-copy account that `init_if_needed` may have just
+/// created: `load_init` while the discriminator is unset, `load_mut` after.
+pub(crate) fn load_or_init_mut<'a, T: ZeroCopy + Owner>(loader: &'a AccountLoader<T>) -> Result<RefMut<'a, T>> {
+    let fresh = loader.as_ref().try_borrow_data()?[..8] == [0u8; 8];
+    if fresh {
+        loader.load_init()
+    } else {
+        loader.load_mut()
+    }
+}
+
+/// Writes a registry account (discriminator included) back into its data.
+pub(crate) fn store_program_account<T: AccountSerialize>(info: &AccountInfo, account: &T) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Applies `update` to the OracleStats at `info` when the oracle has one. Handlers
+/// take the PDA unconditionally, so a caller cannot skip the update by omitting it.
+pub(crate) fn update_oracle_stats(info: &AccountInfo, update: impl FnOnce(&mut OracleStats)) -> Result<()> {
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    let mut stats = load_program_account::<OracleStats>(info)?;
+    update(&mut stats);
+    store_program_account(info, &stats)
+}
+
+/// Commitment over an attestation's per-category evidence hashes, in SUBSCORE_*
+/// order. Oracles that publish category proofs use it as `proofs_hash`, so each
+/// category's report can be disclosed and checked on its own.
+pub fn category_proofs_hash(category_proofs: &[[u8; 32]; SUBSCORE_COUNT]) -> [u8; 32] {
+    let parts: Vec<&[u8]> = category_proofs.iter().map(|p| p.as_slice()).collect();
+    keccak::hashv(&parts).to_bytes()
+}
+
+/// Bits `mint` sets in RiskBloom: consecutive little-endian u32s of keccak(mint),
+/// each modulo the filter size. Off-chain readers test membership the same way.
+pub fn risk_bloom_positions(mint: &Pubkey) -> [usize; RISK_BLOOM_HASHES] {
+    let hash = keccak::hash(mint.as_ref()).to_bytes();
+    let mut positions = [0; RISK_BLOOM_HASHES];
+    for (position, chunk) in positions.iter_mut().zip(hash.chunks_exact(4)) {
+        *position = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as usize % (RISK_BLOOM_BYTES * 8);
+    }
+    positions
+}
+
+/// Claims `proofs_hash` for `mint` under `ruleset_version` through its ProofMarker,
+/// failing when another mint was already attested with the same evidence.
+pub(crate) fn claim_proofs_hash<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mint: Pubkey,
+    ruleset_version: u16,
+    proofs_hash: [u8; 32],
+    oracle: Pubkey,
+) -> Result<()> {
+    let version_bytes = ruleset_version.to_le_bytes();
+    let (marker, bump) = load_or_init_pda::<ProofMarker>(
+        info, 
+        payer, 
+        system_program, 
+        &[b"proof", &version_bytes, &proofs_hash], 
+        PROOF_MARKER_SPACE
+    )?;
+    if marker.mint != Pubkey::default() {
+        require_keys_eq!(marker.mint, mint, ErrorCode::ProofsHashReused);
+        return Ok(());
+    }
+    
+    store_program_account(info, &ProofMarker {
+        proofs_hash,
+        ruleset_version,
+        mint,
+        oracle,
+        claimed_at: Clock::get()?.unix_timestamp,
+        bump,
+    })
+}
+
+/// Inserts `mint` into the RiskBloom at `info` once init_risk_bloom has created it.
+/// Handlers take the PDA unconditionally, so a caller cannot skip the flag by omitting it.
+pub(crate) fn flag_in_risk_bloom<'info>(info: &AccountInfo<'info>, mint: &Pubkey) -> Result<()> {
+    if info.data_is_empty() {
+        return Ok(());
+    }
+    AccountLoader::<RiskBloom>::try_from(info)?.load_mut()?.insert(mint);
+    Ok(())
+}
+
+/// Hash of the Metaplex fields a copycat would swap to impersonate another token.
+/// Verifiers recompute it from the live metadata account and compare it with
+/// `Attestation::metadata_hash`.
+pub fn metadata_hash(metadata: &MetadataAccount) -> Result<[u8; 32]> {
+    let fields = (
+        metadata.name.trim_end_matches('\0'),
+        metadata.symbol.trim_end_matches('\0'),
+        metadata.uri.trim_end_matches('\0'),
+        metadata.update_authority,
+    );
+    Ok(keccak::hash(&fields.try_to_vec()?).to_bytes())
+}
+
+/// RISK_* bits for the mint's Token-2022 extensions; SPL Token mints have none.
+/// Extensions whose authority or target was cleared are not flagged.
+pub(crate) fn mint_risk_flags(mint: &AccountInfo) -> Result<u16> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let mut flags = 0;
+    for extension in state.get_extension_types()? {
+        flags |= match extension {
+            ExtensionType::TransferFeeConfig => RISK_TRANSFER_FEE,
+            ExtensionType::PermanentDelegate => {
+                let delegate: Option<Pubkey> = state.get_extension::<PermanentDelegate>()?.delegate.into();
+                if delegate.is_some() { RISK_PERMANENT_DELEGATE } else { 0 }
+            }
+            ExtensionType::TransferHook => {
+                let hook: Option<Pubkey> = state.get_extension::<TransferHook>()?.program_id.into();
+                match hook {
+                    Some(program_id) if program_id != SOLGUARD_TRANSFER_HOOK_ID => RISK_TRANSFER_HOOK,
+                    _ => 0,
+                }
+            }
+            ExtensionType::ConfidentialTransferMint => RISK_CONFIDENTIAL_TRANSFER,
+            ExtensionType::NonTransferable => RISK_NON_TRANSFERABLE,
+            ExtensionType::DefaultAccountState => {
+                let default_state = state.get_extension::<DefaultAccountState>()?.state;
+                if default_state == AccountState::Frozen as u8 { RISK_DEFAULT_FROZEN } else { 0 }
+            }
+            _ => 0,
+        };
+    }
+    Ok(flags)
+}
+
+/// Points the mint's LatestAttestation at the attestation just written; every
+/// attestation write path ends with it. When the write moves the mint to a newer
+/// ruleset version, `superseded` must be the attestation `latest` pointed at. It
+/// is marked superseded and the returned event is for the caller to emit.
+pub(crate) fn record_latest_attestation<'info>(
+    latest: &mut LatestAttestation, 
+    superseded: Option<&AccountInfo<'info>>, 
+    a: &mut Attestation, 
+    attestation: Pubkey
+) -> Result<Option<AttestationSuperseded>> {
+    let mut event = None;
+    if latest.attestation != Pubkey::default() && latest.ruleset_version < a.ruleset_version {
+        let superseded = superseded.ok_or(ErrorCode::SupersededAttestationMismatch)?;
+        require_keys_eq!(superseded.key(), latest.attestation, ErrorCode::SupersededAttestationMismatch);
+        // One closed since (or never loadable) has nothing left to mark; refusing the
+        // write instead would lock the mint out of every newer ruleset version
+        if let Ok(loader) = AccountLoader::<Attestation>::try_from(superseded) {
+            loader.load_mut()?.superseded = 1;
+            a.supersedes = superseded.key();
+            event = Some(AttestationSuperseded {
+                mint: a.mint,
+                old_attestation: superseded.key(),
+                old_version: latest.ruleset_version,
+                new_attestation: attestation,
+                new_version: a.ruleset_version,
+            });
+        }
+    }
+    latest.record(a.mint, a.ruleset_version, attestation, a.attested_at);
+    Ok(event)
+}
+
+/// Single-oracle attestation write shared by `attest_token` and `attest_with_zk_proof`.
+pub(crate) fn write_attestation(
+    accounts: &mut AttestToken, 
+    bumps: &AttestTokenBumps, 
+    ruleset_version: u16, 
+    item: BatchAttestation, 
+    zk_verified: bool
+) -> Result<()> {
+    let BatchAttestation { score, grade, proofs_hash, nonce, report, subscores, flags } = item;
+    
+    // Validate inputs
+    let in_scope = accounts.scope_entry.is_some();
+    validate_oracle_submission(&accounts.config, &accounts.oracle, in_scope, ruleset_version, accounts.ruleset.as_deref(), score, grade)?;
+    report.validate()?;
+    validate_subscores(&subscores)?;
+    require!(accounts.config.consensus_threshold <= 1, ErrorCode::ConsensusRequired);
+    charge_attestation_fee(
+        &accounts.config, 
+        &mut accounts.fee, 
+        &accounts.payer.to_account_info(), 
+        &accounts.system_program.to_account_info(), 
+        1
+    )?;
+    claim_proofs_hash(
+        &accounts.proof_marker, 
+        &accounts.payer.to_account_info(), 
+        &accounts.system_program.to_account_info(), 
+        accounts.mint.key(), 
+        ruleset_version, 
+        proofs_hash, 
+        accounts.signer.key()
+    )?;
+    
+    // Write/overwrite attestation
+    let mut a = load_or_init_mut(&accounts.attestation)?;
+    let cfg = &accounts.config;
+    require!(
+        !cfg.requires_downgrade_cosign(a.live_score(), score, normalize_grade(grade, cfg.grade_scale())), 
+        ErrorCode::DowngradeRequiresCosign
+    );
+    a.consume_cooldown(cfg.attestation_cooldown, Clock::get()?.unix_timestamp)?;
+    require_grade_in_band_or_waived(cfg, score, grade, accounts.grade_waiver.as_ref(), accounts.grade_waiver_payer.as_ref())?;
+    let previous = a.live_score();
+    let prior = a.prior_score();
+    a.advance_nonce(nonce)?;
+    a.mint = accounts.mint.key();
+    a.ruleset_version = ruleset_version;
+    a.score_bps = score; // score * 10000 (e.g., 0.9123 => 9123)
+    a.grade = grade;     // on the config's grade scale, see grade_levels
+    a.grade_levels = accounts.config.grade_scale();
+    a.version = ATTESTATION_LAYOUT_VERSION;
+    a.proofs_hash = proofs_hash;
+    a.set_report(report);
+    a.metadata_hash = match &accounts.metadata {
+        Some(metadata) => metadata_hash(metadata)?,
+        None => [0; 32],
+    };
+    a.set_mint_snapshot(&accounts.mint, mint_risk_flags(&accounts.mint.to_account_info())?, Clock::get()?.slot);
+    a.require_extensions_allowed()?;
+    a.subscores = subscores;
+    a.flags = flags;
+    a.attested_by = accounts.signer.key();
+    if a.payer == Pubkey::default() {
+        a.payer = accounts.payer.key();
+    }
+    a.attested_at = Clock::get()?.unix_timestamp;
+    a.clear_revocation();
+    a.expired = 0;
+    a.category_proofs = [[0; 32]; SUBSCORE_COUNT];
+    a.oracle_count = 1;
+    a.origin_chain = 0;
+    a.zk_verified = zk_verified as u8;
+    a.valid_until = accounts.config.expiry_from(a.attested_at);
+    accounts.registry_stats.bump = bumps.registry_stats;
+    accounts.registry_stats.version = REGISTRY_STATS_LAYOUT_VERSION;
+    accounts.registry_stats.record_attestation(previous, a.score_bps, a.normalized_grade());
+    notify_watchers(accounts.watch.as_deref_mut(), a.ruleset_version)?;
+    if a.normalized_grade() <= GRADE_RED {
+        flag_in_risk_bloom(&accounts.risk_bloom, &a.mint)?;
+    }
+    
+    let stats = &mut accounts.oracle_stats;
+    if stats.oracle == Pubkey::default() {
+        stats.oracle = a.attested_by;
+        stats.bump = bumps.oracle_stats;
+    }
+    stats.record_attestation(a.normalized_grade(), a.attested_at);
+    
+    let history = &mut accounts.history;
+    history.mint = a.mint;
+    history.bump = bumps.history;
+    history.push(HistoryEntry {
+        score_bps: score,
+        grade: a.normalized_grade(),
+        oracle: a.attested_by,
+        slot: Clock::get()?.slot,
+    });
+    
+    accounts.latest.bump = bumps.latest;
+    if let Some(superseded) = record_latest_attestation(
+        &mut accounts.latest, 
+        accounts.superseded.as_deref(), 
+        &mut a, 
+        accounts.attestation.key()
+    )? {
+        emit_cpi_event(&accounts.event_authority, bumps.event_authority, superseded)?;
+    }
+    index_attestation(
+        &mut accounts.grade_index_entry, 
+        bumps.grade_index_entry, 
+        &mut accounts.grade_index, 
+        &a, 
+        accounts.attestation.key(), 
+        &accounts.latest
+    )?;
+    
+    emit_cpi_event(&accounts.event_authority, bumps.event_authority, TokenAttested {
+        mint: a.mint,
+        ruleset_version,
+        score,
+        grade,
+        attested_by: a.attested_by,
+        attested_at: a.attested_at,
+    })?;
+    emit_cpi_event(&accounts.event_authority, bumps.event_authority, TokenAttestedV2::new(&a, prior, accounts.oracle.weight as u64))?;
+    if let Some(changed) = ScoreChanged::new(&a, prior) {
+        emit_cpi_event(&accounts.event_authority, bumps.event_authority, changed)?;
+    }
+    
+    Ok(())
+}
+
+/// `emit_cpi!` for shared helpers that hold the accounts struct rather than a
+/// `ctx`: self-invokes with the event as instruction data, signed by the event
+/// authority, so it is recorded as an inner instruction instead of a log line.
+pub(crate) fn emit_cpi_event<E: Event>(event_authority: &AccountInfo, bump: u8, event: E) -> Result<()> {
+    let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE.iter().copied().chain(event.data()).collect();
+    let ix = Instruction::new_with_bytes(crate::ID, &data, vec![AccountMeta::new_readonly(event_authority.key(), true)]);
+    invoke_signed(&ix, &[event_authority.clone()], &[&[b"__event_authority", &[bump]]])?;
+    Ok(())
+}
+
+/// Hash an oracle commits with `commit_attestation`. The salt keeps the few
+/// possible (score, grade) pairs from being brute-forced out of the hash.
+pub fn commitment_hash(mint: &Pubkey, ruleset_version: u16, item: &BatchAttestation, salt: &[u8; 32]) -> Result<[u8; 32]> {
+    let preimage = (mint, ruleset_version, item, salt).try_to_vec()?;
+    Ok(keccak::hash(&preimage).to_bytes())
+}
+
+/// Checks that `caller` signed as `program`'s `[b"solguard_caller"]` PDA, which
+/// only `program` can sign for.
+pub(crate) fn require_caller(caller: Option<&Signer>, program: &Pubkey) -> Result<()> {
+    let caller = caller.ok_or(ErrorCode::CallerMismatch)?;
+    let (pda, _) = Pubkey::find_program_address(&[b"solguard_caller"], program);
+    require_keys_eq!(pda, caller.key(), ErrorCode::CallerMismatch);
+    Ok(())
+}
+
+/// Checks that the calling program holds an active subscription. The caller is
+/// identified by its signer PDA rather than the transaction's top-level
+/// instruction, which names the outermost program of a nested CPI. Only
+/// top-level calls to the registry itself (off-chain checks) are exempt.
+pub(crate) fn require_subscription(subscription: Option<&Subscription>, caller: Option<&Signer>) -> Result<()> {
+    if get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+    
+    let sub = subscription.ok_or(ErrorCode::SubscriptionRequired)?;
+    require_caller(caller, &sub.consumer_program)?;
+    require!(sub.is_active(Clock::get()?.epoch), ErrorCode::SubscriptionInactive);
+    Ok(())
+}
+
+/// Checks that `info` is `mint`'s Blacklist PDA and that it does not exist.
+pub(crate) fn require_not_blacklisted(mint: &Pubkey, info: &AccountInfo) -> Result<()> {
+    let (pda, _) = Pubkey::find_program_address(&[b"blacklist", mint.as_ref()], &crate::ID);
+    require_keys_eq!(pda, info.key(), ErrorCode::InvalidRemainingAccount);
+    require!(info.data_is_empty(), ErrorCode::MintBlacklisted);
+    Ok(())
+}
+
+/// Bumps the mint's change counter so its watchers read as dirty, and emits the
+/// event watchers subscribe to instead of the full attestation stream.
+pub(crate) fn notify_watchers(watch: Option<&mut MintWatch>, ruleset_version: u16) -> Result<()> {
+    let Some(watch) = watch else {
+        return Ok(());
+    };
+    watch.changes += 1;
+    watch.last_changed_slot = Clock::get()?.slot;
+    emit!(WatchedMintChanged {
+        mint: watch.mint,
+        ruleset_version,
+        changes: watch.changes,
+        watchers: watch.watchers,
+    });
+    Ok(())
+}
+
+/// Moves lamports out of a program-owned account.
+pub(crate) fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+/// Marks the published ruleset `version` active once its activation slot has
+/// passed, retiring `previous` (the outgoing version) when it has a Ruleset.
+pub(crate) fn activate_ruleset(ruleset: Option<&mut Ruleset>, previous: Option<&mut Ruleset>, version: u16) -> Result<()> {
+    let ruleset = ruleset.ok_or(ErrorCode::RulesetNotPublished)?;
+    require!(ruleset.version == version, ErrorCode::RulesetNotPublished);
+    require!(Clock::get()?.slot >= ruleset.activation_slot, ErrorCode::RulesetNotActivatable);
+    ruleset.status = RulesetStatus::Active;
+    if let Some(previous) = previous.filter(|p| p.version != version) {
+        previous.status = RulesetStatus::Retired;
+    }
+    Ok(())
+}
+
+/// Applies a config change that may be subject to the timelock and emits its event.
+pub(crate) fn apply_admin_action(config: &mut Config, action: AdminAction, admin: Pubkey) -> Result<()> {
+    action.validate()?;
+    match action {
+        AdminAction::SetMinGrade { min_grade } => {
+            require!(min_grade < config.grade_scale(), ErrorCode::InvalidGrade);
+            let old_grade = config.min_grade;
+            config.min_grade = min_grade;
+            
+            emit!(MinGradeUpdated {
+                old_grade,
+                new_grade: min_grade,
+                admin,
+            });
+        }
+        AdminAction::BumpRulesetVersion { version } => {
+            let old_version = config.ruleset_version;
+            config.ruleset_version = version;
+            
+            emit!(RulesetVersionBumped {
+                old_version,
+                new_version: version,
+                admin,
+            });
+        }
+        AdminAction::SetTimelockDelay { delay } => {
+            let old_delay = config.timelock_delay;
+            config.timelock_delay = delay;
+            
+            emit!(TimelockDelayUpdated {
+                old_delay,
+                new_delay: delay,
+                admin,
+            });
+        }
+        AdminAction::SetGradeThresholds { verified_bps, green_bps, yellow_bps, red_bps } => {
+            config.verified_threshold_bps = verified_bps;
+            config.green_threshold_bps = green_bps;
+            config.yellow_threshold_bps = yellow_bps;
+            config.red_threshold_bps = red_bps;
+            
+            emit!(GradeThresholdsUpdated {
+                verified_bps,
+                green_bps,
+                yellow_bps,
+                red_bps,
+                admin,
+            });
+        }
+        AdminAction::SetGradeLevels { levels } => {
+            // Carry the launch bar across scales so it keeps gating the same tier
+            let old_levels = config.grade_scale();
+            config.min_grade = denormalize_grade(normalize_grade(config.min_grade, old_levels), levels);
+            config.grade_levels = levels;
+            
+            emit!(GradeLevelsUpdated {
+                old_levels,
+                new_levels: levels,
+                min_grade: config.min_grade,
+                admin,
+            });
+        }
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(verified: u16, green: u16, yellow: u16, red: u16) -> Config {
+        let mut config = Config::deserialize(&mut &[0u8; CONFIG_SPACE][8..]).unwrap();
+        config.grade_levels = GRADE_LEVELS_EXTENDED;
+        config.verified_threshold_bps = verified;
+        config.green_threshold_bps = green;
+        config.yellow_threshold_bps = yellow;
+        config.red_threshold_bps = red;
+        config
+    }
+
+    #[test]
+    fn require_grade_in_band_unchecked_without_thresholds() {
+        let config = config(0, 0, 0, 0);
+        for grade in 0..GRADE_LEVELS_EXTENDED {
+            assert!(require_grade_in_band(&config, 5000, grade).is_ok());
+        }
+    }
+
+    #[test]
+    fn require_grade_in_band_matches_band() {
+        let config = config(9000, 7000, 4000, 2000);
+        assert!(require_grade_in_band(&config, 9000, GRADE_VERIFIED).is_ok());
+        assert!(require_grade_in_band(&config, 7500, GRADE_GREEN).is_ok());
+        assert!(require_grade_in_band(&config, 1000, GRADE_CRITICAL).is_ok());
+    }
+
+    #[test]
+    fn require_grade_in_band_rejects_other_bands() {
+        let config = config(9000, 7000, 4000, 2000);
+        for grade in [GRADE_CRITICAL, GRADE_RED, GRADE_YELLOW, GRADE_VERIFIED] {
+            assert_eq!(
+                require_grade_in_band(&config, 7500, grade).unwrap_err(), 
+                ErrorCode::InconsistentGrade.into()
+            );
+        }
+    }
+}
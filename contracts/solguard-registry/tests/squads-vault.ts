@@ -0,0 +1,179 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import * as multisig from "@sqds/multisig";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+  TransactionMessage,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { SolguardRegistry } from "../target/types/solguard_registry";
+
+// Admin instructions executed from a Squads v4 vault: the vault PDA signs as
+// `admin` (and `payer`) through the Squads program's invoke_signed.
+describe("squads vault admin", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SolguardRegistry as Program<SolguardRegistry>;
+  const connection = provider.connection;
+  const member = (provider.wallet as anchor.Wallet).payer;
+
+  const createKey = Keypair.generate();
+  const [multisigPda] = multisig.getMultisigPda({ createKey: createKey.publicKey });
+  const [vaultPda] = multisig.getVaultPda({ multisigPda, index: 0 });
+
+  const pda = (seed: string) => PublicKey.findProgramAddressSync([Buffer.from(seed)], program.programId);
+  const [configPda] = pda("config");
+  const [auditLogPda] = pda("audit_log");
+  const [feeVaultPda, feeVaultBump] = pda("fee_vault");
+
+  const confirm = async (signature: string) => {
+    const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+    await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight }, "confirmed");
+  };
+
+  // Runs `ix` as a 1-of-1 vault transaction: create, propose, approve, execute
+  const executeFromVault = async (ix: TransactionInstruction) => {
+    const ms = await multisig.accounts.Multisig.fromAccountAddress(connection, multisigPda);
+    const transactionIndex = BigInt(ms.transactionIndex.toString()) + 1n;
+    const { blockhash } = await connection.getLatestBlockhash();
+
+    await confirm(
+      await multisig.rpc.vaultTransactionCreate({
+        connection,
+        feePayer: member,
+        multisigPda,
+        transactionIndex,
+        creator: member.publicKey,
+        vaultIndex: 0,
+        ephemeralSigners: 0,
+        transactionMessage: new TransactionMessage({
+          payerKey: vaultPda,
+          recentBlockhash: blockhash,
+          instructions: [ix],
+        }),
+      })
+    );
+    await confirm(
+      await multisig.rpc.proposalCreate({ connection, feePayer: member, multisigPda, transactionIndex, creator: member })
+    );
+    await confirm(
+      await multisig.rpc.proposalApprove({ connection, feePayer: member, multisigPda, transactionIndex, member })
+    );
+    await confirm(
+      await multisig.rpc.vaultTransactionExecute({
+        connection,
+        feePayer: member,
+        multisigPda,
+        transactionIndex,
+        member: member.publicKey,
+        signers: [member],
+      })
+    );
+  };
+
+  before(async () => {
+    const [programConfigPda] = multisig.getProgramConfigPda({});
+    const programConfig = await multisig.accounts.ProgramConfig.fromAccountAddress(connection, programConfigPda);
+    await confirm(
+      await multisig.rpc.multisigCreateV2({
+        connection,
+        treasury: programConfig.treasury,
+        createKey,
+        creator: member,
+        multisigPda,
+        configAuthority: null,
+        timeLock: 0,
+        members: [{ key: member.publicKey, permissions: multisig.types.Permissions.all() }],
+        threshold: 1,
+        rentCollector: null,
+      })
+    );
+
+    // The vault pays for the accounts its admin instructions create
+    await confirm(await connection.requestAirdrop(vaultPda, 2 * LAMPORTS_PER_SOL));
+
+    await program.methods
+      .initConfig(1, 1)
+      .accounts({
+        config: configPda,
+        payer: member.publicKey,
+        admin: vaultPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("creates accounts with the vault as admin and payer", async () => {
+    await executeFromVault(
+      await program.methods
+        .initAuditLog()
+        .accounts({
+          config: configPda,
+          admin: vaultPda,
+          adminSet: null,
+          roleGrant: null,
+          auditLog: auditLogPda,
+          payer: vaultPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+    await executeFromVault(
+      await program.methods
+        .initFeeVault()
+        .accounts({
+          config: configPda,
+          admin: vaultPda,
+          adminSet: null,
+          roleGrant: null,
+          auditLog: auditLogPda,
+          feeVault: feeVaultPda,
+          payer: vaultPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+
+    const feeVault = await program.account.feeVault.fetch(feeVaultPda);
+    expect(feeVault.bump).to.equal(feeVaultBump);
+  });
+
+  it("updates config with the vault as admin", async () => {
+    await executeFromVault(
+      await program.methods
+        .setSubscriptionPrice(new BN(1_000))
+        .accounts({
+          config: configPda,
+          admin: vaultPda,
+          adminSet: null,
+          roleGrant: null,
+          auditLog: auditLogPda,
+        })
+        .instruction()
+    );
+
+    const config = await program.account.config.fetch(configPda);
+    expect(config.subscriptionPrice.toNumber()).to.equal(1_000);
+  });
+
+  it("rejects a direct call from a vault member", async () => {
+    const err = await program.methods
+      .setSubscriptionPrice(new BN(0))
+      .accounts({
+        config: configPda,
+        admin: member.publicKey,
+        adminSet: null,
+        roleGrant: null,
+        auditLog: auditLogPda,
+      })
+      .rpc()
+      .then(() => null, (e) => e);
+
+    expect(err).to.be.instanceOf(anchor.AnchorError);
+    expect((err as anchor.AnchorError).error.errorCode.code).to.equal("Unauthorized");
+  });
+});
@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::pubkey;
+use anchor_spl::token_interface::TokenAccount;
+use solguard_cpi::SolguardRegistry;
+
+declare_id!("SoLGuaRdSwap1111111111111111111111111111111");
+
+// Routers the wrapper will forward to
+pub const JUPITER_V6: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+pub const RAYDIUM_AMM_V4: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+
+/// Policy applied when the trader has not registered one.
+pub const DEFAULT_MIN_GRADE: u8 = solguard_cpi::GRADE_YELLOW;
+
+#[program]
+pub mod solguard_swap_gate {
+    use super::*;
+
+    pub fn set_policy(
+        ctx: Context<SetPolicy>,
+        min_grade: u8,
+        allow_unattested: bool,
+        max_age_secs: Option<i64>
+    ) -> Result<()> {
+        require!(min_grade < solguard_cpi::GRADE_LEVELS_EXTENDED, SwapGateError::InvalidGrade);
+
+        let p = &mut ctx.accounts.policy;
+        p.owner = ctx.accounts.owner.key();
+        p.min_grade = min_grade;
+        p.allow_unattested = allow_unattested;
+        p.max_age_secs = max_age_secs;
+        p.bump = ctx.bumps.policy;
+
+        emit!(PolicyUpdated {
+            owner: p.owner,
+            min_grade,
+            allow_unattested,
+            max_age_secs,
+        });
+
+        Ok(())
+    }
+
+    /// Checks the output mint against the trader's policy, then forwards `data` and
+    /// the remaining accounts unchanged to the router. The destination token account
+    /// must be among the forwarded accounts so the route can only land in the gated mint.
+    pub fn gated_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, GatedSwap<'info>>,
+        ruleset_version: u16,
+        data: Vec<u8>
+    ) -> Result<()> {
+        let (min_grade, allow_unattested, max_age_secs) = match &ctx.accounts.policy {
+            Some(p) => (p.min_grade, p.allow_unattested, p.max_age_secs),
+            None => (DEFAULT_MIN_GRADE, false, None),
+        };
+
+        let attestation = &ctx.accounts.attestation;
+        let attested = attestation.owner == &solguard_cpi::ID && !attestation.data_is_empty();
+        // Allowlisted core assets and governance-graded mints verify without an attestation
+        let allowlisted = !ctx.accounts.allowlist.data_is_empty();
+        let overridden = !ctx.accounts.grade_override.data_is_empty();
+        if attested || allowlisted || overridden {
+            solguard_cpi::cpi::verify_attestation(
+                CpiContext::new_with_signer(
+                    ctx.accounts.registry_program.to_account_info(),
+                    solguard_cpi::cpi::VerifyAttestation {
+                        config: ctx.accounts.registry_config.to_account_info(),
+                        mint: ctx.accounts.output_mint.to_account_info(),
+                        attestation: attestation.to_account_info(),
+                        blacklist: ctx.accounts.blacklist.to_account_info(),
+                        allowlist: ctx.accounts.allowlist.to_account_info(),
+                        grade_override: ctx.accounts.grade_override.to_account_info(),
+                        consumer_config: None,
+                        caller: Some(ctx.accounts.caller.to_account_info()),
+                        subscription: ctx.accounts.subscription.as_ref().map(|s| s.to_account_info()),
+                    },
+                    &[&[solguard_cpi::CALLER_SEED, &[ctx.bumps.caller]]]
+                ),
+                ruleset_version,
+                min_grade,
+                max_age_secs
+            )?;
+        } else {
+            require!(allow_unattested, SwapGateError::OutputMintUnattested);
+            // Without the registry CPI nothing else would catch a blacklisted mint
+            require!(ctx.accounts.blacklist.data_is_empty(), SwapGateError::OutputMintBlacklisted);
+        }
+
+        let destination = ctx.accounts.destination_token.key();
+        require!(
+            ctx.remaining_accounts.iter().any(|a| a.key() == destination),
+            SwapGateError::DestinationNotRouted
+        );
+
+        let router = &ctx.accounts.router_program;
+        let ix = Instruction {
+            program_id: router.key(),
+            accounts: ctx.remaining_accounts
+                .iter()
+                .map(|a| AccountMeta {
+                    pubkey: a.key(),
+                    is_signer: a.is_signer,
+                    is_writable: a.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        let mut infos = ctx.remaining_accounts.to_vec();
+        infos.push(router.to_account_info());
+        invoke(&ix, &infos)?;
+
+        emit!(GatedSwapRouted {
+            trader: ctx.accounts.trader.key(),
+            output_mint: ctx.accounts.output_mint.key(),
+            router: router.key(),
+            attested,
+        });
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct SwapPolicy {
+    pub owner: Pubkey,
+    pub min_grade: u8,            // registry extended scale
+    pub allow_unattested: bool,   // route into mints the registry has never graded
+    pub max_age_secs: Option<i64>, // None defers to the registry default
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct SetPolicy<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 32 + 1 + 1 + 9 + 1, // discriminator + owner + grade + unattested + max age + bump
+        seeds = [b"policy", owner.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, SwapPolicy>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(ruleset_version: u16)]
+pub struct GatedSwap<'info> {
+    pub trader: Signer<'info>,
+    #[account(seeds = [b"policy", trader.key().as_ref()], bump = policy.bump)]
+    pub policy: Option<Account<'info, SwapPolicy>>,
+    /// CHECK: only the key is used, bound to the destination account below
+    pub output_mint: UncheckedAccount<'info>,
+    #[account(token::mint = output_mint, token::authority = trader)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: registry config, validated by the registry during the CPI
+    pub registry_config: UncheckedAccount<'info>,
+    /// CHECK: may not exist for unattested mints; validated by the registry otherwise
+    #[account(address = solguard_cpi::attestation_pda(&output_mint.key(), ruleset_version).0 @ SwapGateError::WrongAttestation)]
+    pub attestation: UncheckedAccount<'info>,
+    /// CHECK: output mint's blacklist PDA; checked here for unattested mints, by the registry otherwise
+    #[account(address = solguard_cpi::blacklist_pda(&output_mint.key()).0 @ SwapGateError::WrongAttestation)]
+    pub blacklist: UncheckedAccount<'info>,
+    /// CHECK: output mint's allowlist PDA; only its existence is checked
+    #[account(address = solguard_cpi::allowlist_pda(&output_mint.key()).0 @ SwapGateError::WrongAttestation)]
+    pub allowlist: UncheckedAccount<'info>,
+    /// CHECK: output mint's grade override PDA; only its existence is checked here
+    #[account(address = solguard_cpi::grade_override_pda(&output_mint.key()).0 @ SwapGateError::WrongAttestation)]
+    pub grade_override: UncheckedAccount<'info>,
+    /// CHECK: this program's registry subscription, validated by the registry during the CPI
+    pub subscription: Option<UncheckedAccount<'info>>,
+    /// CHECK: this program's caller PDA; signs the registry CPI so the subscription applies
+    #[account(seeds = [solguard_cpi::CALLER_SEED], bump)]
+    pub caller: UncheckedAccount<'info>,
+    pub registry_program: Program<'info, SolguardRegistry>,
+    /// CHECK: restricted to known routers
+    #[account(
+        executable,
+        constraint = router_program.key() == JUPITER_V6 || router_program.key() == RAYDIUM_AMM_V4 @ SwapGateError::UnsupportedRouter
+    )]
+    pub router_program: UncheckedAccount<'info>,
+}
+
+// Events
+
+#[event]
+pub struct PolicyUpdated {
+    pub owner: Pubkey,
+    pub min_grade: u8,
+    pub allow_unattested: bool,
+    pub max_age_secs: Option<i64>,
+}
+
+#[event]
+pub struct GatedSwapRouted {
+    pub trader: Pubkey,
+    pub output_mint: Pubkey,
+    pub router: Pubkey,
+    pub attested: bool,
+}
+
+#[error_code]
+pub enum SwapGateError {
+    #[msg("Invalid grade value")]
+    InvalidGrade,
+    #[msg("Output mint has no attestation and the policy requires one")]
+    OutputMintUnattested,
+    #[msg("Destination token account is not part of the route")]
+    DestinationNotRouted,
+    #[msg("Router program is not supported")]
+    UnsupportedRouter,
+    #[msg("Attestation account does not match the output mint")]
+    WrongAttestation,
+    #[msg("Output mint is blacklisted")]
+    OutputMintBlacklisted,
+}
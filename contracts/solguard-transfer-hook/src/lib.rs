@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_hook::TransferHook as TransferHookExtension, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use solguard_cpi::Attestation;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+
+declare_id!("SoLGuaRdHook1111111111111111111111111111111");
+
+// Offset of GateConfig.ruleset_version, read by the attestation PDA seed resolver
+const GATE_VERSION_OFFSET: u8 = 8 + 32 + 32;
+
+#[program]
+pub mod solguard_transfer_hook {
+    use super::*;
+
+    /// Registers the gate for a Token-2022 mint whose transfer-hook extension points
+    /// at this program. Only the mint authority may do this.
+    pub fn initialize_gate(ctx: Context<InitializeGate>, ruleset_version: u16, min_grade: u8) -> Result<()> {
+        require!(min_grade < solguard_cpi::GRADE_LEVELS_EXTENDED, GateError::InvalidGrade);
+        // A gate on a mint whose transfers never reach this program would enforce nothing
+        require!(
+            hook_program(&ctx.accounts.mint.to_account_info())? == Some(crate::ID),
+            GateError::HookNotThisProgram
+        );
+
+        let gate = &mut ctx.accounts.gate;
+        gate.authority = ctx.accounts.authority.key();
+        gate.mint = ctx.accounts.mint.key();
+        gate.ruleset_version = ruleset_version;
+        gate.min_grade = min_grade;
+        gate.bump = ctx.bumps.gate;
+
+        // Token-2022 resolves these for every transfer:
+        // 5 = registry program, 6 = gate, 7 = attestation PDA for (mint, gate.ruleset_version),
+        // 8 = the mint's registry Blacklist PDA
+        let extra_metas = extra_account_metas()?;
+        let size = ExtraAccountMetaList::size_of(extra_metas.len())?;
+        let mint_key = ctx.accounts.mint.key();
+        let seeds: &[&[u8]] = &[b"extra-account-metas", mint_key.as_ref(), &[ctx.bumps.extra_account_meta_list]];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.extra_account_meta_list.to_account_info(),
+                },
+                &[seeds],
+            ),
+            Rent::get()?.minimum_balance(size),
+            size as u64,
+            ctx.program_id,
+        )?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &extra_metas,
+        )?;
+
+        emit!(GateUpdated {
+            mint: gate.mint,
+            ruleset_version,
+            min_grade,
+            authority: gate.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Moves the gate to a new ruleset version or grade bar, e.g. after the registry
+    /// bumps its ruleset and the mint has been re-attested.
+    pub fn set_gate(ctx: Context<SetGate>, ruleset_version: u16, min_grade: u8) -> Result<()> {
+        require!(min_grade < solguard_cpi::GRADE_LEVELS_EXTENDED, GateError::InvalidGrade);
+
+        let gate = &mut ctx.accounts.gate;
+        gate.ruleset_version = ruleset_version;
+        gate.min_grade = min_grade;
+
+        emit!(GateUpdated {
+            mint: gate.mint,
+            ruleset_version,
+            min_grade,
+            authority: gate.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Transfer-hook `Execute`: fails the transfer unless the mint is not
+    /// blacklisted and has a valid, unrevoked attestation at or above the gate's grade.
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        require!(ctx.accounts.blacklist.data_is_empty(), GateError::MintBlacklisted);
+
+        let info = &ctx.accounts.attestation;
+        require!(
+            info.owner == &solguard_cpi::ID && !info.data_is_empty(),
+            GateError::MintNotAttested
+        );
+        let loader = AccountLoader::<Attestation>::try_from(info.as_ref())?;
+        let attestation = loader.load()?;
+
+        let gate = &ctx.accounts.gate;
+        solguard_cpi::check_attested(&attestation, &gate.mint, gate.ruleset_version, gate.min_grade)?;
+
+        Ok(())
+    }
+
+    /// Token-2022 invokes the hook with the interface's own discriminator,
+    /// which Anchor 0.29 does not dispatch on its own.
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8]
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                let amount_bytes = amount.to_le_bytes();
+                __private::__global::transfer_hook(program_id, accounts, &amount_bytes)
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
+    }
+}
+
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&solguard_cpi::ID, false, false)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"gate".to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal { bytes: b"attest".to_vec() },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData { account_index: 6, data_index: GATE_VERSION_OFFSET, length: 2 },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal { bytes: b"blacklist".to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            false,
+        )?,
+    ])
+}
+
+/// Program the mint's transfer-hook extension points at, if any.
+fn hook_program(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let Ok(hook) = state.get_extension::<TransferHookExtension>() else {
+        return Ok(None);
+    };
+    Ok(hook.program_id.into())
+}
+
+#[account]
+pub struct GateConfig {
+    pub authority: Pubkey,     // may retune the gate
+    pub mint: Pubkey,
+    pub ruleset_version: u16,  // attestation version transfers are checked against
+    pub min_grade: u8,         // registry extended scale
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGate<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: created and written in the handler via the TLV account resolution crate
+    #[account(
+        mut,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(
+        constraint = mint.mint_authority == COption::Some(authority.key()) @ GateError::NotMintAuthority
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 32 + 2 + 1 + 1, // discriminator + authority + mint + version + grade + bump
+        seeds = [b"gate", mint.key().as_ref()],
+        bump
+    )]
+    pub gate: Account<'info, GateConfig>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetGate<'info> {
+    #[account(
+        mut,
+        seeds = [b"gate", gate.mint.as_ref()],
+        bump = gate.bump,
+        has_one = authority
+    )]
+    pub gate: Account<'info, GateConfig>,
+    pub authority: Signer<'info>,
+}
+
+// Account order is fixed by the transfer-hook interface, extras follow in
+// the order registered by initialize_gate
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: source token account owner or delegate
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: extra account meta list
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    /// CHECK: only used to derive the attestation PDA
+    #[account(address = solguard_cpi::ID)]
+    pub registry_program: UncheckedAccount<'info>,
+    #[account(seeds = [b"gate", mint.key().as_ref()], bump = gate.bump)]
+    pub gate: Account<'info, GateConfig>,
+    /// CHECK: may not exist yet; ownership and contents checked in the handler
+    #[account(address = solguard_cpi::attestation_pda(&mint.key(), gate.ruleset_version).0)]
+    pub attestation: UncheckedAccount<'info>,
+    /// CHECK: the mint's Blacklist PDA; only its existence is checked
+    #[account(address = solguard_cpi::blacklist_pda(&mint.key()).0)]
+    pub blacklist: UncheckedAccount<'info>,
+}
+
+// Events
+
+#[event]
+pub struct GateUpdated {
+    pub mint: Pubkey,
+    pub ruleset_version: u16,
+    pub min_grade: u8,
+    pub authority: Pubkey,
+}
+
+#[error_code]
+pub enum GateError {
+    #[msg("Invalid grade value")]
+    InvalidGrade,
+    #[msg("Signer is not the mint authority")]
+    NotMintAuthority,
+    #[msg("Mint has no attestation under the gated ruleset version")]
+    MintNotAttested,
+    #[msg("Mint's transfer-hook extension does not point at this program")]
+    HookNotThisProgram,
+    #[msg("Mint is blacklisted")]
+    MintBlacklisted,
+}
@@ -3,15 +3,21 @@
  * Handles on-chain attestation of token scan results
  */
 
-import { Connection, PublicKey, Keypair, Transaction } from '@solana/web3.js';
-import { Program, AnchorProvider, Wallet } from '@coral-xyz/anchor';
+import { Connection, PublicKey, Keypair, Transaction, SystemProgram } from '@solana/web3.js';
+import { Program, AnchorProvider, Wallet, BN } from '@coral-xyz/anchor';
 import { keccak_256 } from '@noble/hashes/sha3';
 import { gradeFromRules, gradeToNumber, RuleResult, RuleSetResult, Grade } from '@solguard/core';
 import { TokenScanner, ScannerConfig } from './scanner';
 import * as fs from 'fs';
 
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+const GRADE_INDEX_PAGE_LEN = 64;
+const GRADE_LEVELS_EXTENDED = 5;
+
 export interface AttestorConfig extends ScannerConfig {
   oracleKeypairPath: string;
+  // Treasury keypair that funds rent and fees; defaults to the oracle keypair
+  payerKeypairPath?: string;
   programId: string;
   idlPath: string;
 }
@@ -21,6 +27,7 @@ export class TokenAttestor {
   private connection: Connection;
   private program: Program;
   private oracle: Keypair;
+  private payer: Keypair;
   private config: AttestorConfig;
 
   constructor(config: AttestorConfig) {
@@ -31,12 +38,15 @@ export class TokenAttestor {
     // Load oracle keypair
     const keypairData = JSON.parse(fs.readFileSync(config.oracleKeypairPath, 'utf8'));
     this.oracle = Keypair.fromSecretKey(new Uint8Array(keypairData));
+    this.payer = config.payerKeypairPath
+      ? Keypair.fromSecretKey(new Uint8Array(JSON.parse(fs.readFileSync(config.payerKeypairPath, 'utf8'))))
+      : this.oracle;
     
     // Setup Anchor program
     const idl = JSON.parse(fs.readFileSync(config.idlPath, 'utf8'));
     const provider = new AnchorProvider(
       this.connection,
-      new Wallet(this.oracle),
+      new Wallet(this.payer),
       { commitment: 'confirmed' }
     );
     
@@ -135,32 +145,155 @@ export class TokenAttestor {
       this.program.programId
     );
     
-    const [attestationPda] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from('attest'),
-        mintPk.toBuffer(),
-        Buffer.from(new Uint16Array([rulesetVersion]).buffer)
-      ],
+    const attestationPda = this.getAttestationPDA(mintAddress, rulesetVersion);
+    
+    const [oracleStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle_stats'), this.oracle.publicKey.toBuffer()],
+      this.program.programId
+    );
+    
+    const [historyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('history'), mintPk.toBuffer()],
+      this.program.programId
+    );
+    
+    const [latestPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('latest'), mintPk.toBuffer()],
+      this.program.programId
+    );
+    
+    // Claims proofHash for this mint; reusing it for another mint is rejected
+    const [proofMarkerPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('proof'), Buffer.from(new Uint16Array([rulesetVersion]).buffer), Buffer.from(proofHash)],
+      this.program.programId
+    );
+    
+    // Admin waiver of the score/grade band check, consumed by this write
+    const [gradeWaiverPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('grade_waiver'), mintPk.toBuffer(), Buffer.from(new Uint16Array([rulesetVersion]).buffer)],
+      this.program.programId
+    );
+    
+    const [registryStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('registry_stats')],
+      this.program.programId
+    );
+    
+    const [blacklistPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('blacklist'), mintPk.toBuffer()],
+      this.program.programId
+    );
+    
+    const [eventAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('__event_authority')],
+      this.program.programId
+    );
+    
+    const [feeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('fee_vault')],
+      this.program.programId
+    );
+    
+    const [metadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mintPk.toBuffer()],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    
+    const [riskBloomPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('risk_bloom')],
+      this.program.programId
+    );
+    
+    const [watchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('watch'), mintPk.toBuffer()],
+      this.program.programId
+    );
+    
+    // Only mints someone has registered a watcher for have a MintWatch to bump
+    const watch = await this.connection.getAccountInfo(watchPda);
+    
+    // Mints without Metaplex metadata are attested without a metadata hash
+    const metadata = await this.connection.getAccountInfo(metadataPda);
+    
+    // The vault only exists once the admin has enabled fees
+    const feeVault = await this.connection.getAccountInfo(feeVaultPda);
+    
+    // Only exists while an admin has waived the band check for this mint and version;
+    // consuming it refunds whoever funded it
+    const gradeWaiver = await this.program.account.gradeWaiver.fetchNullable(gradeWaiverPda);
+    
+    // Overwrites must carry the next nonce
+    const existing = await this.program.account.attestation.fetchNullable(attestationPda);
+    const nonce = existing ? (existing.nonce as BN).addn(1) : new BN(1);
+    
+    // attest_token lists the mint under its new grade, on the extended scale the index uses
+    const [gradeIndexEntryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('grade_index_entry'), mintPk.toBuffer()],
       this.program.programId
     );
+    const gradeNumber = gradeToNumber(grade as Grade);
+    const config = await this.program.account.config.fetch(configPda);
+    const indexGrade = config.gradeLevels === GRADE_LEVELS_EXTENDED ? gradeNumber : gradeNumber + 1;
+    const gradeIndex = await this.getGradeIndexPages(gradeIndexEntryPda, indexGrade);
+    
+    // Moving the mint to a newer ruleset version marks its previous attestation superseded
+    const latest = await this.program.account.latestAttestation.fetchNullable(latestPda);
+    // (closing or migrating that attestation clears the pointer)
+    const superseded = latest && !(latest.attestation as PublicKey).equals(PublicKey.default)
+      && (latest.rulesetVersion as number) < rulesetVersion
+      ? latest.attestation as PublicKey
+      : null;
     
     // Call attest_token instruction
     const tx = await this.program.methods
       .attestToken(
         rulesetVersion,
         Math.round(score * 10000), // Convert to basis points
-        gradeToNumber(grade as Grade),
-        Array.from(proofHash)
+        gradeNumber,
+        Array.from(proofHash),
+        nonce,
+        { scheme: 0, contentId: Array(32).fill(0) }, // no report pointer published yet
+        [0, 0, 0, 0, 0], // category sub-scores not computed by the scanner yet
+        new BN(0) // FLAG_* reasons not derived from the scanner's rules yet
       )
       .accounts({
         config: configPda,
         oracle: oraclePda,
         mint: mintPk,
+        metadata: metadata ? metadataPda : null,
+        blacklist: blacklistPda,
+        scopeEntry: null,
+        ruleset: null, // only needed for non-current ruleset versions
         attestation: attestationPda,
+        history: historyPda,
+        latest: latestPda,
+        proofMarker: proofMarkerPda,
+        gradeIndexEntry: gradeIndexEntryPda,
+        gradeIndex,
+        superseded,
+        gradeWaiver: gradeWaiver ? gradeWaiverPda : null,
+        gradeWaiverPayer: gradeWaiver ? gradeWaiver.payer as PublicKey : null,
+        watch: watch ? watchPda : null,
+        // Red grades are flagged in it once the admin has created it
+        riskBloom: riskBloomPda,
+        oracleStats: oracleStatsPda,
+        registryStats: registryStatsPda,
+        fee: {
+          feeVault: feeVault ? feeVaultPda : null,
+          // SPL fee accounts are only needed once the admin sets Config.feeMint
+          feeMint: null,
+          payerFeeToken: null,
+          feeTokenVault: null,
+          tokenProgram: null,
+        },
         signer: this.oracle.publicKey,
-        systemProgram: PublicKey.default,
+        payer: this.payer.publicKey,
+        systemProgram: SystemProgram.programId,
+        // TokenAttested events are emitted through a self-CPI signed by this PDA
+        eventAuthority: eventAuthorityPda,
+        program: this.program.programId,
       })
-      .signers([this.oracle])
+      .signers([this.oracle, this.payer])
       .rpc();
     
     return tx;
@@ -182,6 +315,41 @@ export class TokenAttestor {
     return attestationPda;
   }
 
+  /**
+   * Grade index pages attest_token moves the mint between: the page its
+   * GradeIndexEntry lists it on, and the first page of `grade` with room
+   */
+  private async getGradeIndexPages(
+    entryPda: PublicKey,
+    grade: number
+  ): Promise<{ from: PublicKey | null; to: PublicKey | null }> {
+    const entry = await this.program.account.gradeIndexEntry.fetchNullable(entryPda);
+    if (entry?.listed && entry.grade === grade) {
+      return { from: null, to: null };
+    }
+    
+    const from = entry?.listed ? this.getGradeIndexPagePDA(entry.grade as number, entry.page as number) : null;
+    for (let page = 0; ; page++) {
+      const to = this.getGradeIndexPagePDA(grade, page);
+      const account = await this.program.account.gradeIndexPage.fetchNullable(to);
+      if (!account) {
+        throw new Error(`Grade index for grade ${grade} is full; init_grade_index_page ${page} first`);
+      }
+      if ((account.mints as PublicKey[]).length < GRADE_INDEX_PAGE_LEN) {
+        return { from, to };
+      }
+    }
+  }
+
+  getGradeIndexPagePDA(grade: number, page: number): PublicKey {
+    const [pagePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('grade_index'), Buffer.from([grade]), Buffer.from(new Uint32Array([page]).buffer)],
+      this.program.programId
+    );
+    
+    return pagePda;
+  }
+
   /**
    * Check if token has valid attestation
    */
@@ -191,6 +359,7 @@ export class TokenAttestor {
     grade?: string;
     attestedAt?: Date;
     revoked?: boolean;
+    frozen?: boolean;
   }> {
     try {
       const attestationPda = this.getAttestationPDA(mintAddress, rulesetVersion);
@@ -200,12 +369,18 @@ export class TokenAttestor {
         return { exists: false };
       }
       
+      // Attestations predating grade_levels were issued on the 3-level scale
+      const gradeNames = (account.gradeLevels as number) === 5
+        ? ['critical', 'red', 'yellow', 'green', 'verified']
+        : ['red', 'yellow', 'green'];
+      
       return {
         exists: true,
         score: (account.scoreBps as number) / 10000,
-        grade: ['red', 'yellow', 'green'][account.grade as number],
+        grade: gradeNames[account.grade as number],
         attestedAt: new Date((account.attestedAt as number) * 1000),
-        revoked: account.revoked as boolean
+        revoked: (account.revoked as number) !== 0,
+        frozen: (account.frozen as number) !== 0
       };
       
     } catch (error) {